@@ -0,0 +1,66 @@
+use napi::{Error, Result};
+use napi_derive::napi;
+use serde_json::{Map, Value};
+
+/// `analyze(options)`: runs the analysis in-process and returns the report as a plain object
+/// (the same shape as `haadi --json`). Options are the CLI flags in camelCase, e.g.
+/// `{ root: "app", entries: ["src/cli.ts"], includeLowConfidence: true }`; `entries` maps to
+/// repeated `--entry` flags.
+#[napi(ts_args_type = "options?: Record<string, unknown>")]
+pub fn analyze(options: Option<Value>) -> Result<Value> {
+    let args = match options {
+        None | Some(Value::Null) => Vec::new(),
+        Some(Value::Object(options)) => options_to_args(&options)?,
+        Some(_) => return Err(Error::from_reason("analyze() expects an options object")),
+    };
+    let report = haadi::analyze_args(args).map_err(|err| Error::from_reason(format!("{err:#}")))?;
+    serde_json::to_value(report).map_err(|err| Error::from_reason(err.to_string()))
+}
+
+fn options_to_args(options: &Map<String, Value>) -> Result<Vec<String>> {
+    let mut args = Vec::new();
+    for (key, value) in options {
+        let flag = match key.as_str() {
+            "entries" => "--entry".to_string(),
+            _ => format!("--{}", kebab_case(key)),
+        };
+        match value {
+            Value::Null | Value::Bool(false) => {}
+            Value::Bool(true) => args.push(flag),
+            Value::Array(items) => {
+                for item in items {
+                    args.push(flag.clone());
+                    args.push(scalar(key, item)?);
+                }
+            }
+            _ => {
+                args.push(flag);
+                args.push(scalar(key, value)?);
+            }
+        }
+    }
+    Ok(args)
+}
+
+fn scalar(key: &str, value: &Value) -> Result<String> {
+    match value {
+        Value::String(text) => Ok(text.clone()),
+        Value::Number(number) => Ok(number.to_string()),
+        _ => Err(Error::from_reason(format!(
+            "option '{key}' must be a string, number, boolean, or an array of strings"
+        ))),
+    }
+}
+
+fn kebab_case(key: &str) -> String {
+    let mut out = String::with_capacity(key.len() + 4);
+    for c in key.chars() {
+        if c.is_ascii_uppercase() {
+            out.push('-');
+            out.push(c.to_ascii_lowercase());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
@@ -0,0 +1,14 @@
+use wasm_bindgen::prelude::*;
+
+/// `analyze(snapshot)`: runs haadi's graph analysis on an in-memory project. `snapshot` is
+/// the JSON form of `haadi::Snapshot`, e.g.
+/// `{"files": {"src/index.ts": "...", "package.json": "..."}, "entries": ["src/index.ts"]}`;
+/// the result is the report as JSON (the same shape as `haadi --json`).
+#[wasm_bindgen]
+pub fn analyze(snapshot: &str) -> Result<String, JsError> {
+    let snapshot: haadi::Snapshot = serde_json::from_str(snapshot)
+        .map_err(|err| JsError::new(&format!("Failed to parse snapshot: {err}")))?;
+    let report =
+        haadi::analyze_snapshot(&snapshot).map_err(|err| JsError::new(&format!("{err:#}")))?;
+    serde_json::to_string(&report).map_err(|err| JsError::new(&err.to_string()))
+}
@@ -0,0 +1,115 @@
+use super::*;
+
+/// Target options naming scripts the Angular CLI builds: `main` (the `browser` and `server`
+/// of the application builder), `polyfills`, and global `scripts`.
+const SCRIPT_OPTIONS: &[&str] = &["main", "browser", "server", "polyfills", "scripts"];
+/// Targets whose files only run under test.
+const DEV_TARGETS: &[&str] = &["test", "e2e"];
+
+/// Source files the projects of `angular.json` in `root` build from: every target's `main`,
+/// `browser`, `server`, `polyfills`, `scripts`, and `ssr.entry`, in its `options` and each of
+/// its `configurations`. Files of the `test` and `e2e` targets are `Dev`. Package names such
+/// as `zone.js` in `polyfills` are skipped.
+pub(crate) fn angular_entries(
+    root: &Path,
+    resolver: &Resolver,
+) -> Result<Vec<(PathBuf, EntryScope)>> {
+    let mut out = Vec::new();
+    for (option, path, scope) in angular_target_files(root) {
+        if option == "styles" {
+            continue;
+        }
+        if let Some(path) = resolver.resolve_path(&root.join(path.trim_start_matches("./")))? {
+            debug!(entry = %relative_display(root, &path), option, "angular entry");
+            out.push((path, scope));
+        }
+    }
+    Ok(out)
+}
+
+/// Stylesheets in the `styles` of `angular.json` targets in `root`, which the CLI bundles
+/// without any import pointing at them.
+pub(crate) fn angular_styles(root: &Path) -> Vec<PathBuf> {
+    angular_target_files(root)
+        .into_iter()
+        .filter(|(option, _, _)| option == "styles")
+        .map(|(_, path, _)| normalize_path(root.join(path.trim_start_matches("./"))))
+        .collect()
+}
+
+/// Every `(option, path, scope)` that the targets of `angular.json` in `root` name. Items of
+/// `styles` and `scripts` may be `{ "input": ... }` objects. A missing or unreadable
+/// `angular.json` names none.
+fn angular_target_files(root: &Path) -> Vec<(String, String, EntryScope)> {
+    let Ok(raw) = fs::read_to_string(root.join("angular.json")) else {
+        return Vec::new();
+    };
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(&sanitize_jsonc(&raw)) else {
+        return Vec::new();
+    };
+    let mut out = Vec::new();
+    let Some(projects) = value.get("projects").and_then(|v| v.as_object()) else {
+        return out;
+    };
+    for project in projects.values() {
+        let Some(targets) = project
+            .get("architect")
+            .or_else(|| project.get("targets"))
+            .and_then(|v| v.as_object())
+        else {
+            continue;
+        };
+        for (target_name, target) in targets {
+            let scope = if DEV_TARGETS.contains(&target_name.as_str()) {
+                EntryScope::Dev
+            } else {
+                EntryScope::Prod
+            };
+            let configurations = target
+                .get("configurations")
+                .and_then(|v| v.as_object())
+                .into_iter()
+                .flat_map(|map| map.values());
+            for options in target.get("options").into_iter().chain(configurations) {
+                for option in SCRIPT_OPTIONS.iter().copied().chain(["styles"]) {
+                    let Some(value) = options.get(option) else {
+                        continue;
+                    };
+                    let mut paths = Vec::new();
+                    collect_input_paths(value, &mut paths);
+                    out.extend(
+                        paths
+                            .into_iter()
+                            .map(|path| (option.to_string(), path, scope)),
+                    );
+                }
+                if let Some(entry) = options
+                    .get("ssr")
+                    .and_then(|ssr| ssr.get("entry"))
+                    .and_then(|v| v.as_str())
+                {
+                    out.push(("ssr".to_string(), entry.to_string(), scope));
+                }
+            }
+        }
+    }
+    out
+}
+
+/// A path, an array of paths, or `{ "input": ... }` items, as in `styles` and `scripts`.
+fn collect_input_paths(value: &serde_json::Value, out: &mut Vec<String>) {
+    match value {
+        serde_json::Value::String(path) => out.push(path.clone()),
+        serde_json::Value::Array(items) => {
+            for item in items {
+                collect_input_paths(item, out);
+            }
+        }
+        serde_json::Value::Object(map) => {
+            if let Some(input) = map.get("input") {
+                collect_input_paths(input, out);
+            }
+        }
+        _ => {}
+    }
+}
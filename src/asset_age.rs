@@ -0,0 +1,118 @@
+use super::*;
+use std::process::Command as Process;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Parses `--ignore-newer-than` and `--trash-max-age` values such as `14d`, `2w`, `36h`, or `90m`.
+pub(crate) fn parse_age(raw: &str) -> std::result::Result<Duration, String> {
+    let raw = raw.trim();
+    let split = raw
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(|| format!("missing unit in '{raw}' (use s, m, h, d, or w)"))?;
+    let (amount, unit) = raw.split_at(split);
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| format!("invalid number in '{raw}'"))?;
+    let seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => return Err(format!("unknown unit '{unit}' (use s, m, h, d, or w)")),
+    };
+    Ok(Duration::from_secs(amount.saturating_mul(seconds)))
+}
+
+/// Assets added less than `max_age` ago. The date a file was first committed comes from
+/// `git log` when the root is inside a git work tree, so a fresh checkout does not make old
+/// files look new; untracked files, projects without git, and shallow clones (whose oldest
+/// commit seems to add every file) fall back to the modification time.
+pub(crate) fn recently_added_assets(
+    root: &Path,
+    assets: &[&PathBuf],
+    max_age: Duration,
+) -> HashSet<PathBuf> {
+    let now = SystemTime::now();
+    let relative: Vec<String> = assets
+        .iter()
+        .map(|asset| relative_display(root, asset).replace('\\', "/"))
+        .collect();
+    let git_added = git_add_times(root, &relative);
+    assets
+        .iter()
+        .zip(&relative)
+        .filter(|(asset, relative)| {
+            let added = git_added
+                .get(*relative)
+                .map(|secs| UNIX_EPOCH + Duration::from_secs(*secs))
+                .or_else(|| fs::metadata(asset).and_then(|meta| meta.modified()).ok());
+            // Timestamps in the future count as new.
+            added.is_some_and(|added| now.duration_since(added).map_or(true, |age| age < max_age))
+        })
+        .map(|(asset, _)| (*asset).clone())
+        .collect()
+}
+
+const GIT_PATHSPEC_BATCH: usize = 512;
+
+/// Most recent commit time (Unix seconds) at which each of `paths` (relative to `root`, with
+/// forward slashes) was added. Empty when git is unavailable, `root` is not a work tree, or
+/// the clone is shallow.
+fn git_add_times(root: &Path, paths: &[String]) -> HashMap<String, u64> {
+    if is_shallow_clone(root) {
+        warn!(
+            "shallow git clone: using file modification times for --ignore-newer-than; fetch the full history (git fetch --unshallow) for commit dates"
+        );
+        return HashMap::new();
+    }
+    let mut added = HashMap::new();
+    // Batched so a large asset list stays under the platform's argument length limit.
+    for batch in paths.chunks(GIT_PATHSPEC_BATCH) {
+        let output = Process::new("git")
+            .arg("-C")
+            .arg(root)
+            .args([
+                "-c",
+                "core.quotePath=false",
+                "log",
+                "--diff-filter=A",
+                "--format=%x00%ct",
+                "--name-only",
+                "--relative",
+                "--",
+            ])
+            .args(batch.iter().map(|path| format!(":(literal){path}")))
+            .output();
+        let Ok(output) = output else {
+            return HashMap::new();
+        };
+        if !output.status.success() {
+            return HashMap::new();
+        }
+
+        // Commit headers start with a NUL byte, which cannot appear in a path. Newest commits
+        // come first, so the first time a path shows up is its latest addition.
+        let mut commit_time = None;
+        for line in String::from_utf8_lossy(&output.stdout).lines() {
+            if let Some(secs) = line.strip_prefix('\0') {
+                commit_time = secs.parse::<u64>().ok();
+            } else if !line.is_empty()
+                && let Some(secs) = commit_time
+            {
+                added.entry(line.to_string()).or_insert(secs);
+            }
+        }
+    }
+    added
+}
+
+/// Whether `root` is in a shallow clone, as CI checkouts usually are. Its oldest commit
+/// appears to add every file, so their add dates are meaningless.
+fn is_shallow_clone(root: &Path) -> bool {
+    Process::new("git")
+        .arg("-C")
+        .arg(root)
+        .args(["rev-parse", "--is-shallow-repository"])
+        .output()
+        .is_ok_and(|output| output.status.success() && output.stdout.trim_ascii() == b"true")
+}
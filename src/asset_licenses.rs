@@ -0,0 +1,115 @@
+use super::*;
+
+/// Stylesheets and text files are usually the project's own; attribution is about images,
+/// fonts, and media.
+const ATTRIBUTION_EXEMPT_EXTENSIONS: &[&str] = &["css", "scss", "sass", "less", "txt"];
+
+/// Directory-wide license files (`LICENSE`, `LICENSE.md`, `COPYING`, `NOTICE.txt`, ...).
+const DIRECTORY_LICENSE_NAMES: &[&str] = &["license", "licence", "copying", "notice"];
+
+/// `--audit-asset-licenses`: pairs assets with the license files that cover them. A sidecar
+/// (`logo.svg.license`, `logo.license.txt`) covers one asset; a `LICENSE*` file covers every
+/// asset in its directory and below, except the project root's own license. Returns unused
+/// assets that carry attribution and used assets that carry none.
+pub(crate) fn audit_asset_licenses(
+    root: &Path,
+    index: &PathIndex,
+    used: &HashSet<PathBuf>,
+    unused: &[&PathBuf],
+) -> (Vec<LicensedAsset>, Vec<String>) {
+    let mut sidecars: HashMap<PathBuf, Vec<PathBuf>> = HashMap::new();
+    let mut directory_licenses: HashMap<PathBuf, Vec<PathBuf>> = HashMap::new();
+    for file in index.files() {
+        if let Some(covered) = sidecar_target(file) {
+            sidecars.entry(covered).or_default().push(file.clone());
+        } else if is_directory_license(file)
+            && let Some(dir) = file.parent()
+            && dir != root
+        {
+            directory_licenses
+                .entry(dir.to_path_buf())
+                .or_default()
+                .push(file.clone());
+        }
+    }
+
+    let licenses_for = |asset: &Path| -> Vec<String> {
+        let mut found: Vec<&PathBuf> = Vec::new();
+        for key in [asset.to_path_buf(), asset.with_extension("")] {
+            found.extend(sidecars.get(&key).into_iter().flatten());
+        }
+        for dir in asset.ancestors().skip(1) {
+            if !dir.starts_with(root) || dir == root {
+                break;
+            }
+            found.extend(directory_licenses.get(dir).into_iter().flatten());
+        }
+        let mut found: Vec<String> = found
+            .into_iter()
+            .map(|path| relative_display(root, path))
+            .collect();
+        found.sort();
+        found.dedup();
+        found
+    };
+
+    let mut unused_licensed: Vec<LicensedAsset> = unused
+        .iter()
+        .filter(|asset| needs_attribution(asset))
+        .filter_map(|asset| {
+            let license_files = licenses_for(asset);
+            (!license_files.is_empty()).then(|| LicensedAsset {
+                file: relative_display(root, asset),
+                license_files,
+            })
+        })
+        .collect();
+    unused_licensed.sort_by(|a, b| a.file.cmp(&b.file));
+
+    let mut unlicensed_used: Vec<String> = used
+        .iter()
+        .filter(|asset| needs_attribution(asset) && licenses_for(asset).is_empty())
+        .map(|asset| relative_display(root, asset))
+        .collect();
+    unlicensed_used.sort();
+
+    (unused_licensed, unlicensed_used)
+}
+
+fn needs_attribution(asset: &Path) -> bool {
+    let exempt = asset
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            ATTRIBUTION_EXEMPT_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str())
+        });
+    !exempt && sidecar_target(asset).is_none() && !is_directory_license(asset)
+}
+
+/// The asset path a sidecar license names: `logo.svg.license` -> `logo.svg`,
+/// `logo.license.txt` -> `logo` (matched against the asset's path without extension).
+fn sidecar_target(path: &Path) -> Option<PathBuf> {
+    let name = path.file_name()?.to_str()?;
+    let lower = name.to_ascii_lowercase();
+    let stem_len = [".license.txt", ".license"]
+        .iter()
+        .find(|suffix| lower.ends_with(*suffix))
+        .map(|suffix| name.len() - suffix.len())?;
+    (stem_len > 0).then(|| path.with_file_name(&name[..stem_len]))
+}
+
+/// `LICENSE`, `COPYING.txt`, `NOTICE.md`, ...: a text file whose stem names a license. Other
+/// extensions (`license.svg`, `license.png`) are ordinary assets.
+fn is_directory_license(path: &Path) -> bool {
+    let text_like = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_none_or(|ext| ext.eq_ignore_ascii_case("txt") || ext.eq_ignore_ascii_case("md"));
+    text_like
+        && path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .is_some_and(|stem| {
+                DIRECTORY_LICENSE_NAMES.contains(&stem.to_ascii_lowercase().as_str())
+            })
+}
@@ -0,0 +1,107 @@
+use super::*;
+use crate::config_aliases::{find_config_files, has_js_config_extension};
+use crate::scanner::resolve_asset_specifier;
+use walkdir::WalkDir;
+
+/// `](./diagram.png)` targets of Markdown links and images.
+static MARKDOWN_LINK_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\]\(\s*<?([^)\s>]+)>?(?:\s+"[^"]*")?\s*\)"#).unwrap());
+/// Unquoted relative paths in YAML frontmatter, e.g. `cover: ./cover.jpg`.
+static FRONTMATTER_PATH_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r##"(?m):\s+(\.{1,2}/[^\s'"#]+)\s*$"##).unwrap());
+
+/// Scripts Astro loads by name, relative to `root` and without extension.
+const ASTRO_NAMED_SCRIPTS: &[&str] = &[
+    "src/content/config",
+    "src/content.config",
+    "src/middleware",
+    "src/middleware/index",
+];
+
+/// Whether `root` holds an Astro site, going by its `astro.config.*`.
+fn is_astro_site(root: &Path) -> bool {
+    !find_config_files(root, &[""], |name| {
+        name.starts_with("astro.config.") && has_js_config_extension(name)
+    })
+    .is_empty()
+}
+
+/// Files of an Astro site that haadi does not parse as modules but that import or reference
+/// project files: every `.astro` component under `src/`, and the `.md` and `.mdx` files of
+/// `src/pages/` and of the content collections in `src/content/`. Empty outside Astro sites.
+fn astro_documents(root: &Path) -> Vec<PathBuf> {
+    if !is_astro_site(root) {
+        return Vec::new();
+    }
+    let src = root.join("src");
+    let mut out: Vec<PathBuf> = WalkDir::new(&src)
+        .into_iter()
+        .filter_entry(|e| !is_ignored_dir(e.path()))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| e.into_path())
+        .filter(|path| match path.extension().and_then(|ext| ext.to_str()) {
+            Some("astro") => true,
+            Some("md" | "mdx") => {
+                path.starts_with(src.join("pages")) || path.starts_with(src.join("content"))
+            }
+            _ => false,
+        })
+        .collect();
+    out.sort();
+    out
+}
+
+/// Entries of an Astro site in `root`: the content collection config (`src/content/config.*`
+/// or `src/content.config.*`), `src/middleware.*`, and every source file that the `.astro`
+/// components, pages, or MDX content import. Astro renders those itself, so the pages under
+/// `src/pages/` and the collections they read stand in for entries.
+pub(crate) fn astro_entries(root: &Path, resolver: &Resolver) -> Result<Vec<PathBuf>> {
+    if !is_astro_site(root) {
+        return Ok(Vec::new());
+    }
+    let mut out = Vec::new();
+    for script in ASTRO_NAMED_SCRIPTS {
+        out.extend(resolver.resolve_path(&root.join(script))?);
+    }
+    for document in astro_documents(root) {
+        if document.extension().is_some_and(|ext| ext == "md") {
+            continue;
+        }
+        out.extend(component_imports(&document, resolver)?);
+    }
+    out.sort();
+    out.dedup();
+    for path in &out {
+        debug!(entry = %relative_display(root, path), "astro entry");
+    }
+    Ok(out)
+}
+
+/// Assets the Astro documents of `root` reference: quoted paths (imports, `src="..."`),
+/// Markdown links and images, and relative paths in frontmatter such as `cover: ./cover.jpg`.
+pub(crate) fn astro_used_assets(root: &Path, assets: &HashSet<PathBuf>) -> Result<Vec<PathBuf>> {
+    let mut out = Vec::new();
+    for document in astro_documents(root) {
+        let text = fs::read_to_string(&document).unwrap_or_default();
+        let quoted = STRING_LITERAL_RE.captures_iter(&text).filter_map(|caps| {
+            [1usize, 2, 3]
+                .into_iter()
+                .find_map(|idx| caps.get(idx).map(|m| m.as_str().to_string()))
+        });
+        let linked = MARKDOWN_LINK_RE
+            .captures_iter(&text)
+            .chain(FRONTMATTER_PATH_RE.captures_iter(&text))
+            .map(|caps| caps[1].to_string());
+        for reference in quoted.chain(linked) {
+            let specifier = normalize_specifier(&reference);
+            if specifier.is_empty() {
+                continue;
+            }
+            out.extend(resolve_asset_specifier(
+                root, &document, &specifier, assets,
+            )?);
+        }
+    }
+    Ok(out)
+}
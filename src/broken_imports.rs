@@ -0,0 +1,79 @@
+use super::*;
+
+/// Turns broken local imports into report rows, each with the closest existing source file
+/// as a fix suggestion.
+pub(crate) fn describe_broken_imports(
+    root: &Path,
+    files: &HashSet<PathBuf>,
+    resolver: &Resolver,
+    broken: &[UnresolvedImport],
+) -> Vec<BrokenImport> {
+    let mut candidates: Vec<String> = files
+        .iter()
+        .map(|file| strip_file_extension(&relative_display(root, file).replace('\\', "/")))
+        .collect();
+    candidates.sort();
+
+    broken
+        .iter()
+        .map(|item| {
+            let suggestion = resolver
+                .local_target_path(&item.from_file, &item.specifier)
+                .and_then(|target| {
+                    let target = relative_display(root, &target).replace('\\', "/");
+                    nearest_path(&strip_file_extension(&target), &candidates)
+                })
+                .and_then(|rel| {
+                    files
+                        .iter()
+                        .map(|file| relative_display(root, file).replace('\\', "/"))
+                        .filter(|path| strip_file_extension(path) == rel)
+                        .min()
+                });
+
+            BrokenImport {
+                file: relative_display(root, &item.from_file),
+                specifier: item.specifier.clone(),
+                suggestion,
+            }
+        })
+        .collect()
+}
+
+/// Picks the candidate with the smallest edit distance to `target` (a typo such as
+/// `components/Buton`), falling back to a file with the same name in another directory (a
+/// moved file). `dir/index` candidates also match `dir`.
+fn nearest_path(target: &str, candidates: &[String]) -> Option<String> {
+    let max_distance = (target.chars().count() / 5).max(2);
+    let best = candidates
+        .iter()
+        .map(|candidate| {
+            let key = candidate.strip_suffix("/index").unwrap_or(candidate);
+            (edit_distance(target, key), candidate)
+        })
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance);
+    if let Some((_, candidate)) = best {
+        return Some(candidate.clone());
+    }
+
+    let name = target.rsplit('/').next()?;
+    candidates
+        .iter()
+        .find(|candidate| candidate.rsplit('/').next() == Some(name))
+        .cloned()
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(prev[j + 1] + 1).min(current[j] + 1);
+        }
+        prev = current;
+    }
+    prev[b.len()]
+}
@@ -0,0 +1,219 @@
+use super::*;
+use crate::config_aliases::{
+    balanced_block, eval_path_expr, find_config_files, has_js_config_extension, split_key_value,
+    split_top_level,
+};
+
+static ROLLUP_OPTIONS_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\brollupOptions['"]?\s*:\s*\{"#).unwrap());
+/// `module.exports =` or `export default` before a webpack config.
+static CONFIG_EXPORT_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"(?:\bmodule\.exports\s*=|\bexport\s+default\b)\s*").unwrap());
+/// `return {` or `return ({` in the body of a config function.
+static RETURN_OBJECT_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\breturn\s*\(?\s*\{").unwrap());
+/// `<script type="module" src="/src/main.ts">` in an HTML entry.
+static SCRIPT_SRC_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?i)<script\b[^>]*\bsrc\s*=\s*["']([^"']+)["']"#).unwrap());
+
+/// Entry files of a Vite build: each `build.rollupOptions.input` of `vite.config.*` in `root`,
+/// or `index.html` when no input is set. Inputs are strings, arrays, or objects, written as
+/// paths or as `resolve(__dirname, 'nested/index.html')`. HTML inputs stand for the module
+/// scripts they load; a `/src/main.ts` script path is relative to the config's folder.
+pub(crate) fn vite_entries(root: &Path, resolver: &Resolver) -> Result<Vec<PathBuf>> {
+    let mut out = Vec::new();
+    for config_path in find_config_files(root, &[""], |name| {
+        name.starts_with("vite.config.") && has_js_config_extension(name)
+    }) {
+        let source = strip_comments(&fs::read_to_string(&config_path).unwrap_or_default());
+        let config_dir = config_path.parent().unwrap_or(root);
+        let mut inputs: Vec<PathBuf> = ROLLUP_OPTIONS_RE
+            .find_iter(&source)
+            .filter_map(|m| balanced_block(&source, m.end() - 1))
+            .filter_map(|body| {
+                split_top_level(body)
+                    .into_iter()
+                    .filter_map(split_key_value)
+                    .find_map(|(key, value)| (key == "input").then_some(value))
+            })
+            .flat_map(path_items)
+            .filter_map(|item| eval_path_expr(item, config_dir, config_dir, true))
+            .collect();
+        if inputs.is_empty() {
+            inputs.push(config_dir.join("index.html"));
+        }
+        for input in inputs {
+            if input.extension().is_some_and(|ext| ext == "html") {
+                out.extend(html_script_entries(&input, config_dir, resolver)?);
+            } else {
+                out.extend(resolver.resolve_path(&input)?);
+            }
+        }
+    }
+    for path in &out {
+        debug!(entry = %relative_display(root, path), "vite input entry");
+    }
+    Ok(out)
+}
+
+/// Entry files of a webpack build: the top-level `entry` of each config that
+/// `webpack*.config.*` in `root` or `config/` exports, as a string, an array, an object of
+/// named entries, or `{ import }` descriptors. Paths are relative to the config's top-level
+/// `context`, or to `root`, where webpack runs; `path.resolve(__dirname, ...)` forms are
+/// relative to the config file. `entry` and `context` keys nested deeper, as in plugin
+/// options, are not the build's. Entries computed at runtime and package names are skipped.
+pub(crate) fn webpack_entries(root: &Path, resolver: &Resolver) -> Result<Vec<PathBuf>> {
+    let mut out = Vec::new();
+    for config_path in find_config_files(root, &["", "config"], |name| {
+        name.starts_with("webpack") && has_js_config_extension(name)
+    }) {
+        let source = strip_comments(&fs::read_to_string(&config_path).unwrap_or_default());
+        let config_dir = config_path.parent().unwrap_or(root);
+        for config in exported_config_objects(&source) {
+            let keys: Vec<(String, &str)> = split_top_level(config)
+                .into_iter()
+                .filter_map(split_key_value)
+                .collect();
+            let context = keys
+                .iter()
+                .find(|(key, _)| key == "context")
+                .and_then(|(_, value)| eval_path_expr(value.trim(), config_dir, root, false))
+                .unwrap_or_else(|| root.to_path_buf());
+            let Some((_, entry)) = keys.iter().find(|(key, _)| key == "entry") else {
+                continue;
+            };
+            for item in path_items(entry) {
+                // `path.resolve(__dirname, ...)` starts at the config's folder instead.
+                let base = if item.contains("__dirname") || item.contains("import.meta") {
+                    config_dir
+                } else {
+                    &context
+                };
+                if let Some(path) = eval_path_expr(item, base, root, false) {
+                    out.extend(resolver.resolve_path(&path)?);
+                }
+            }
+        }
+    }
+    for path in &out {
+        debug!(entry = %relative_display(root, path), "webpack entry");
+    }
+    Ok(out)
+}
+
+/// The module scripts an HTML entry loads. A leading `/` is relative to `site_root`.
+fn html_script_entries(html: &Path, site_root: &Path, resolver: &Resolver) -> Result<Vec<PathBuf>> {
+    let Ok(text) = fs::read_to_string(html) else {
+        return Ok(Vec::new());
+    };
+    let html_dir = html.parent().unwrap_or(site_root);
+    let mut out = Vec::new();
+    for caps in SCRIPT_SRC_RE.captures_iter(&text) {
+        let src = &caps[1];
+        if src.contains("://") || src.starts_with("//") {
+            continue;
+        }
+        let script = match src.strip_prefix('/') {
+            Some(rest) => site_root.join(rest),
+            None => html_dir.join(src.trim_start_matches("./")),
+        };
+        out.extend(resolver.resolve_path(&script)?);
+    }
+    Ok(out)
+}
+
+/// The path expressions of an entry value: the value itself, each array item, or each object
+/// value, with `{ import: ... }` descriptors unwrapped.
+fn path_items(value: &str) -> Vec<&str> {
+    let value = value.trim();
+    match value.chars().next() {
+        Some('[') => balanced_block(value, 0)
+            .map(split_top_level)
+            .unwrap_or_default()
+            .into_iter()
+            .flat_map(path_items)
+            .collect(),
+        Some('{') => {
+            let items: Vec<(String, &str)> = balanced_block(value, 0)
+                .map(split_top_level)
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(split_key_value)
+                .collect();
+            match items.iter().find(|(key, _)| key == "import") {
+                Some((_, import)) => path_items(import),
+                None => items
+                    .into_iter()
+                    .flat_map(|(_, value)| path_items(value))
+                    .collect(),
+            }
+        }
+        _ => vec![value],
+    }
+}
+
+/// The bodies of the config objects a webpack config file exports: the exported object, each
+/// object of an exported array, the object an exported function returns, or what an exported
+/// variable is set to.
+fn exported_config_objects(source: &str) -> Vec<&str> {
+    CONFIG_EXPORT_RE
+        .find(source)
+        .map(|m| config_objects_at(source, m.end(), 0))
+        .unwrap_or_default()
+}
+
+fn config_objects_at(source: &str, start: usize, depth: usize) -> Vec<&str> {
+    let rest = source[start..].trim_start();
+    let start = source.len() - rest.len();
+    if rest.starts_with('{') {
+        return balanced_block(source, start).into_iter().collect();
+    }
+    if rest.starts_with('[') {
+        return balanced_block(source, start)
+            .map(split_top_level)
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|item| balanced_block(item.trim(), 0))
+            .collect();
+    }
+    if depth > 2 {
+        return Vec::new();
+    }
+    // `(env) => ({ ... })`, `(env) => { return { ... } }`, or `function (env) { ... }`.
+    let head_end = rest.find(['{', ';', '\n']).unwrap_or(rest.len());
+    let head = &rest[..head_end];
+    if let Some(arrow) = head.find("=>") {
+        let body = rest[arrow + 2..].trim_start();
+        let body_start = source.len() - body.len();
+        if let Some(object) = body.strip_prefix('(') {
+            return config_objects_at(source, source.len() - object.len(), depth + 1);
+        }
+        return balanced_block(source, body_start)
+            .and_then(|body| RETURN_OBJECT_RE.find(body))
+            .map(|m| config_objects_at(source, body_start + 1 + m.end() - 1, depth + 1))
+            .unwrap_or_default();
+    }
+    if head.trim_start().starts_with("function") || head.trim_start().starts_with("async") {
+        let body_start = start + head_end;
+        return balanced_block(source, body_start)
+            .and_then(|body| RETURN_OBJECT_RE.find(body))
+            .map(|m| config_objects_at(source, body_start + 1 + m.end() - 1, depth + 1))
+            .unwrap_or_default();
+    }
+    // `module.exports = config;` with `const config = { ... }` earlier in the file.
+    let name = head.trim().trim_end_matches(';');
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+    {
+        return Vec::new();
+    }
+    Regex::new(&format!(
+        r"\b(?:const|let|var)\s+{}\s*(?::[^=]+)?=\s*",
+        regex::escape(name)
+    ))
+    .ok()
+    .and_then(|re| re.find(source))
+    .map(|m| config_objects_at(source, m.end(), depth + 1))
+    .unwrap_or_default()
+}
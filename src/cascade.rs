@@ -0,0 +1,96 @@
+use super::*;
+
+/// Waves `--cascade` runs at most, in case findings never settle.
+const MAX_WAVES: usize = 10;
+
+/// Re-runs the analysis of `root` as if the unused files and assets of `report` were deleted,
+/// then those of each new wave too, until a run turns up no new unused file or asset. Each
+/// wave lists only findings no earlier wave had: exports whose names only deleted files
+/// mentioned, assets only deleted files referenced, and so on. Runs reuse `parse_cache`, so
+/// files are not parsed again.
+pub(crate) fn cascade_waves(
+    options: &AnalysisOptions,
+    parse_cache: &mut ParseCache,
+    root: &Path,
+    report: &Report,
+) -> Result<Vec<CascadeWave>> {
+    let wave_options = AnalysisOptions {
+        cascade: false,
+        save_graph: None,
+        fix: None,
+        stats: false,
+        timing: false,
+        progress: false,
+        resume_from: None,
+        ..options.clone()
+    };
+    let mut seen_paths: HashSet<String> = report
+        .unused_files
+        .iter()
+        .chain(&report.unused_assets)
+        .cloned()
+        .collect();
+    let mut seen_exports: HashSet<(String, String)> = report
+        .unused_exports
+        .iter()
+        .map(|item| (item.file.clone(), item.export.clone()))
+        .collect();
+    let mut seen_dependencies: HashSet<String> =
+        report.unused_dependencies.iter().cloned().collect();
+    let mut deleted: HashSet<PathBuf> = HashSet::new();
+    let mut newly_unused: Vec<String> = seen_paths.iter().cloned().collect();
+    let mut waves = Vec::new();
+
+    for wave in 2..=MAX_WAVES {
+        if newly_unused.is_empty() {
+            break;
+        }
+        check_interrupted()?;
+        deleted.extend(
+            newly_unused
+                .iter()
+                .map(|path| normalize_path(root.join(path))),
+        );
+        let (next, _) = analyze_excluding(&wave_options, parse_cache, false, &deleted)?;
+        let unused_files: Vec<String> = next
+            .unused_files
+            .into_iter()
+            .filter(|path| seen_paths.insert(path.clone()))
+            .collect();
+        let unused_assets: Vec<String> = next
+            .unused_assets
+            .into_iter()
+            .filter(|path| seen_paths.insert(path.clone()))
+            .collect();
+        let unused_exports: Vec<UnusedExport> = next
+            .unused_exports
+            .into_iter()
+            .filter(|item| seen_exports.insert((item.file.clone(), item.export.clone())))
+            .collect();
+        let unused_dependencies: Vec<String> = next
+            .unused_dependencies
+            .into_iter()
+            .filter(|name| seen_dependencies.insert(name.clone()))
+            .collect();
+        newly_unused = unused_files.iter().chain(&unused_assets).cloned().collect();
+        if newly_unused.is_empty() && unused_exports.is_empty() && unused_dependencies.is_empty() {
+            break;
+        }
+        debug!(
+            wave,
+            files = unused_files.len(),
+            assets = unused_assets.len(),
+            exports = unused_exports.len(),
+            dependencies = unused_dependencies.len(),
+            "cascade wave"
+        );
+        waves.push(CascadeWave {
+            wave,
+            unused_files,
+            unused_assets,
+            unused_exports,
+            unused_dependencies,
+        });
+    }
+    Ok(waves)
+}
@@ -0,0 +1,146 @@
+use super::*;
+
+/// Usage of component exports, attributed through the import graph.
+#[derive(Debug, Default)]
+pub(crate) struct ComponentUsage {
+    /// `(defining file, export name)` pairs some reachable file uses.
+    used: HashSet<(PathBuf, String)>,
+    /// Files whose exports are all used (namespace imports, entry re-exports).
+    all: HashSet<PathBuf>,
+}
+
+impl ComponentUsage {
+    pub(crate) fn is_used(&self, file: &Path, name: &str) -> bool {
+        self.all.contains(file) || self.used.contains(&(file.to_path_buf(), name.to_string()))
+    }
+}
+
+/// Component exports are PascalCase names exported from `.jsx` / `.tsx` files. Whether they
+/// are used is decided by [`trace_component_usage`] instead of the name-token heuristic, so a
+/// barrel re-export or a stray mention of the name does not keep a dead component alive.
+pub(crate) fn is_component_export(file: &Path, name: &str) -> bool {
+    matches!(
+        file.extension().and_then(|ext| ext.to_str()),
+        Some("jsx" | "tsx")
+    ) && name.starts_with(|c: char| c.is_ascii_uppercase())
+}
+
+/// Follows every import binding a reachable file renders as a JSX element or otherwise
+/// references, through barrel re-exports (`export { Button } from './Button'`, `export *`),
+/// to the file that defines it. Namespace, `require`, and dynamic imports use every export of
+/// their target, and the re-exports of entry files are public API, so they count as used.
+pub(crate) fn trace_component_usage(
+    reachable: &HashSet<PathBuf>,
+    entries: &[PathBuf],
+    modules: &HashMap<PathBuf, ModuleInfo>,
+    resolver: &Resolver,
+) -> Result<ComponentUsage> {
+    let mut tracer = Tracer {
+        modules,
+        resolver,
+        usage: ComponentUsage::default(),
+        seen: HashSet::new(),
+    };
+    for entry in entries {
+        tracer.use_all(entry)?;
+    }
+    for file in reachable {
+        let Some(module) = modules.get(file) else {
+            continue;
+        };
+        for import in module.imports.iter().filter(|import| !import.is_reexport) {
+            let targets = resolver.resolve_specifier_all(file, &import.specifier)?;
+            if import.uses_namespace {
+                for target in &targets {
+                    tracer.use_all(target)?;
+                }
+            }
+            for (local, imported) in &import.bindings {
+                let rendered = module.jsx_elements.contains(local);
+                if !rendered && !module.referenced_bindings.contains(local) {
+                    continue;
+                }
+                trace!(file = %shown_path(file), binding = %local, rendered, "component binding used");
+                for target in &targets {
+                    tracer.use_name(target, imported)?;
+                }
+            }
+        }
+    }
+    Ok(tracer.usage)
+}
+
+struct Tracer<'a> {
+    modules: &'a HashMap<PathBuf, ModuleInfo>,
+    resolver: &'a Resolver,
+    usage: ComponentUsage,
+    /// `(file, name)` pairs already followed; `*` stands for every export.
+    seen: HashSet<(PathBuf, String)>,
+}
+
+impl Tracer<'_> {
+    fn use_name(&mut self, file: &Path, name: &str) -> Result<()> {
+        if !self.seen.insert((file.to_path_buf(), name.to_string())) {
+            return Ok(());
+        }
+        let Some(module) = self.modules.get(file) else {
+            return Ok(());
+        };
+        let defined = module.exports.contains(name);
+        if defined {
+            self.usage
+                .used
+                .insert((file.to_path_buf(), name.to_string()));
+        }
+        for import in module.imports.iter().filter(|import| import.is_reexport) {
+            let targets = self
+                .resolver
+                .resolve_specifier_all(file, &import.specifier)?;
+            match import.bindings.get(name).map(String::as_str) {
+                // `export * as name from`
+                Some("*") => {
+                    for target in &targets {
+                        self.use_all(target)?;
+                    }
+                }
+                Some(imported) if !defined => {
+                    for target in &targets {
+                        self.use_name(target, imported)?;
+                    }
+                }
+                // `export * from`
+                None if !defined && import.uses_namespace && import.bindings.is_empty() => {
+                    for target in &targets {
+                        self.use_name(target, name)?;
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    fn use_all(&mut self, file: &Path) -> Result<()> {
+        if !self.seen.insert((file.to_path_buf(), "*".to_string())) {
+            return Ok(());
+        }
+        self.usage.all.insert(file.to_path_buf());
+        let Some(module) = self.modules.get(file) else {
+            return Ok(());
+        };
+        for import in module.imports.iter().filter(|import| import.is_reexport) {
+            for target in self
+                .resolver
+                .resolve_specifier_all(file, &import.specifier)?
+            {
+                if import.uses_namespace {
+                    self.use_all(&target)?;
+                }
+                for imported in import.bindings.values().filter(|name| *name != "*") {
+                    self.use_name(&target, imported)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,335 @@
+use super::*;
+use clap::ArgMatches;
+use clap::parser::ValueSource;
+use once_cell::sync::OnceCell;
+
+/// Project-level settings read from `haadi.json`, `haadi.toml`, or the `"haadi"` key in
+/// `package.json`. CLI flags always take precedence over these values. TOML files use the
+/// flag names (`resolve-extensions`), JSON uses camelCase.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct ProjectConfig {
+    #[serde(alias = "resolve-extensions")]
+    pub(crate) resolve_extensions: Vec<String>,
+    pub(crate) platforms: Vec<String>,
+    /// `from=to` folder rules applied to paths referenced by deploy files.
+    #[serde(alias = "dist-mappings")]
+    pub(crate) dist_mappings: Vec<String>,
+    /// Extra Storybook story globs, relative to the root.
+    pub(crate) stories: Vec<String>,
+}
+
+/// Per-user defaults from `config.toml` in the user config dir (see [`user_dir`]). Keys use
+/// the flag names; flags given on the command line win, and the project config wins over the
+/// list settings.
+#[derive(Debug, Default, Clone, Deserialize)]
+#[serde(rename_all = "kebab-case", default, deny_unknown_fields)]
+pub(crate) struct GlobalConfig {
+    /// Open the dashboard by default when printing to a terminal.
+    tui: Option<bool>,
+    on_conflict: Option<OnConflict>,
+    trash: Option<TrashMode>,
+    threads: Option<usize>,
+    resolve_extensions: Vec<String>,
+    platforms: Vec<String>,
+}
+
+/// Loaded by the CLI only, so library callers never pick up per-user settings.
+static GLOBAL_CONFIG: OnceCell<GlobalConfig> = OnceCell::new();
+static GLOBAL_CACHE_DIR: OnceCell<PathBuf> = OnceCell::new();
+
+/// Reads the global config, if any, applies its defaults to flags neither the command line
+/// nor a `HAADI_*` environment variable set, and enables the global cache dir.
+pub(crate) fn apply_global_config(cli: &mut Cli, matches: &ArgMatches) -> Result<()> {
+    if let Some(dir) = user_dir("XDG_CACHE_HOME", ".cache", "LOCALAPPDATA") {
+        let _ = GLOBAL_CACHE_DIR.set(dir);
+    }
+    let Some((path, config)) = read_user_config()? else {
+        return Ok(());
+    };
+    info!(config = %shown_path(&path), "loading global config");
+
+    // Machine-readable output and subcommands never switch to the dashboard.
+    if config.tui == Some(true)
+        && !set_by_user(matches, "tui")
+        && !cli.json
+        && cli.format.is_none()
+        && cli.command.is_none()
+        && std::io::IsTerminal::is_terminal(&std::io::stdout())
+    {
+        cli.tui = true;
+    }
+    if let Some(on_conflict) = config.on_conflict
+        && !set_by_user(matches, "on_conflict")
+    {
+        cli.on_conflict = on_conflict;
+    }
+    if cli.trash.is_none() {
+        cli.trash = config.trash;
+    }
+    if cli.analysis.threads.is_none() {
+        cli.analysis.threads = config.threads;
+    }
+    let _ = GLOBAL_CONFIG.set(config);
+    Ok(())
+}
+
+fn set_by_user(matches: &ArgMatches, id: &str) -> bool {
+    matches!(
+        matches.value_source(id),
+        Some(ValueSource::CommandLine | ValueSource::EnvVariable)
+    )
+}
+
+fn read_user_config() -> Result<Option<(PathBuf, GlobalConfig)>> {
+    let Some(path) = user_dir("XDG_CONFIG_HOME", ".config", "APPDATA")
+        .map(|dir| dir.join("config.toml"))
+        .filter(|path| path.is_file())
+    else {
+        return Ok(None);
+    };
+    let raw = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read global config: {}", shown_path(&path)))?;
+    let config = toml::from_str(&raw)
+        .with_context(|| format!("Failed to parse global config: {}", shown_path(&path)))?;
+    Ok(Some((path, config)))
+}
+
+/// Directory for caches shared by all projects, when the CLI enabled it.
+pub(crate) fn global_cache_dir() -> Option<&'static Path> {
+    GLOBAL_CACHE_DIR.get().map(PathBuf::as_path)
+}
+
+/// `$<xdg_var>/haadi` when set to an absolute path, else `%<windows_var>%\haadi` on Windows,
+/// else `~/<home_fallback>/haadi`.
+fn user_dir(xdg_var: &str, home_fallback: &str, windows_var: &str) -> Option<PathBuf> {
+    let var = |name: &str| {
+        std::env::var_os(name)
+            .map(PathBuf::from)
+            .filter(|dir| dir.is_absolute())
+    };
+    if let Some(dir) = var(xdg_var) {
+        return Some(dir.join("haadi"));
+    }
+    if cfg!(windows)
+        && let Some(dir) = var(windows_var)
+    {
+        return Some(dir.join("haadi"));
+    }
+    var("HOME").map(|home| home.join(home_fallback).join("haadi"))
+}
+
+/// The project config, with list settings it leaves empty taken from the global config.
+pub(crate) fn load_project_config(root: &Path) -> Result<ProjectConfig> {
+    let mut config = find_project_config(root)?
+        .map(|(path, config)| {
+            info!(config = %shown_path(&path), "loading project config");
+            config
+        })
+        .unwrap_or_default();
+    if let Some(global) = GLOBAL_CONFIG.get() {
+        if config.resolve_extensions.is_empty() {
+            config.resolve_extensions = global.resolve_extensions.clone();
+        }
+        if config.platforms.is_empty() {
+            config.platforms = global.platforms.clone();
+        }
+    }
+    Ok(config)
+}
+
+/// The project config and the file it came from: `haadi.json` or `haadi.toml` (having both is
+/// an error), else the `"haadi"` key in `package.json`.
+fn find_project_config(root: &Path) -> Result<Option<(PathBuf, ProjectConfig)>> {
+    let json_path = root.join("haadi.json");
+    let toml_path = root.join("haadi.toml");
+    if json_path.exists() && toml_path.exists() {
+        anyhow::bail!(
+            "Found both haadi.json and haadi.toml in {}; keep only one",
+            shown_path(root)
+        );
+    }
+    if json_path.exists() {
+        let raw = fs::read_to_string(&json_path)?;
+        let config = serde_json::from_str(&sanitize_jsonc(&raw))
+            .with_context(|| format!("Failed to parse config: {}", shown_path(&json_path)))?;
+        return Ok(Some((json_path, config)));
+    }
+    if toml_path.exists() {
+        let raw = fs::read_to_string(&toml_path)?;
+        let config = toml::from_str(&raw)
+            .with_context(|| format!("Failed to parse config: {}", shown_path(&toml_path)))?;
+        return Ok(Some((toml_path, config)));
+    }
+
+    let package_json = root.join("package.json");
+    if !package_json.exists() {
+        return Ok(None);
+    }
+
+    let raw = fs::read_to_string(&package_json)?;
+    let value: serde_json::Value = serde_json::from_str(&raw)?;
+    let Some(section) = value.get("haadi") else {
+        return Ok(None);
+    };
+    let config = serde_json::from_value(section.clone()).with_context(|| {
+        format!(
+            "Failed to parse \"haadi\" key in {}",
+            shown_path(&package_json)
+        )
+    })?;
+    Ok(Some((package_json, config)))
+}
+
+/// Settings one layer sets, as `(key, TOML value)` pairs.
+type Settings = Vec<(&'static str, String)>;
+
+/// `haadi config show`: the settings each layer sets, or with `--resolved` the value every
+/// setting ends up with and the layer it came from. Layers from highest precedence: command
+/// line, `HAADI_*` environment variables, project config, user config, built-in default.
+pub(crate) fn show_config(cli: &Cli, matches: &ArgMatches, resolved: bool) -> Result<()> {
+    let root = fs::canonicalize(&cli.analysis.root)
+        .with_context(|| format!("Failed to access root: {}", shown_path(&cli.analysis.root)))?;
+    let mut layers: Vec<(String, Settings)> = vec![
+        (
+            "command line".to_string(),
+            cli_settings(cli, matches, ValueSource::CommandLine),
+        ),
+        (
+            "environment".to_string(),
+            cli_settings(cli, matches, ValueSource::EnvVariable),
+        ),
+    ];
+    if let Some((path, config)) = find_project_config(&root)? {
+        let mut settings = list_settings(&config.resolve_extensions, &config.platforms);
+        if !config.dist_mappings.is_empty() {
+            settings.push(("dist-mappings", toml_list(&config.dist_mappings)));
+        }
+        if !config.stories.is_empty() {
+            settings.push(("stories", toml_list(&config.stories)));
+        }
+        layers.push((format!("project config {}", shown_path(&path)), settings));
+    }
+    if let Some((path, config)) = read_user_config()? {
+        let mut settings = Settings::new();
+        if let Some(tui) = config.tui {
+            settings.push(("tui", tui.to_string()));
+        }
+        if let Some(on_conflict) = config.on_conflict {
+            settings.push(("on-conflict", toml_string(on_conflict.label())));
+        }
+        if let Some(trash) = config.trash {
+            settings.push(("trash", toml_string(trash.label())));
+        }
+        if let Some(threads) = config.threads {
+            settings.push(("threads", threads.to_string()));
+        }
+        settings.extend(list_settings(&config.resolve_extensions, &config.platforms));
+        layers.push((format!("user config {}", shown_path(&path)), settings));
+    }
+
+    if !resolved {
+        for (name, settings) in &layers {
+            println!("# {name}");
+            if settings.is_empty() {
+                println!("# (nothing set)");
+            }
+            for (key, value) in settings {
+                println!("{key} = {value}");
+            }
+            println!();
+        }
+        return Ok(());
+    }
+
+    let defaults = [
+        ("tui", "false".to_string()),
+        ("on-conflict", toml_string("skip")),
+        ("trash", toml_string(TrashMode::resolve(None).label())),
+        ("threads", "0".to_string()),
+        ("resolve-extensions", toml_list(JS_TS_EXTENSIONS)),
+        ("platforms", "[]".to_string()),
+        ("dist-mappings", toml_list(DEFAULT_DIST_MAPPINGS)),
+        ("stories", "[]".to_string()),
+    ];
+    for (key, default) in defaults {
+        let (value, source) = layers
+            .iter()
+            .find_map(|(name, settings)| {
+                settings
+                    .iter()
+                    .find(|(set_key, _)| *set_key == key)
+                    .map(|(_, value)| (value.clone(), name.as_str()))
+            })
+            .unwrap_or((default, "default"));
+        println!("{:<40} # {source}", format!("{key} = {value}"));
+    }
+    Ok(())
+}
+
+/// Settings whose parsed value came from `source` (the command line or the environment).
+fn cli_settings(cli: &Cli, matches: &ArgMatches, source: ValueSource) -> Settings {
+    let from = |id: &str| matches.value_source(id) == Some(source);
+    let mut settings = Settings::new();
+    if from("tui") {
+        settings.push(("tui", cli.tui.to_string()));
+    }
+    if from("on_conflict") {
+        settings.push(("on-conflict", toml_string(cli.on_conflict.label())));
+    }
+    if from("trash")
+        && let Some(trash) = cli.trash
+    {
+        settings.push(("trash", toml_string(trash.label())));
+    }
+    if from("threads")
+        && let Some(threads) = cli.analysis.threads
+    {
+        settings.push(("threads", threads.to_string()));
+    }
+    if from("resolve_extensions") {
+        settings.push((
+            "resolve-extensions",
+            toml_list(&cli.analysis.resolve_extensions),
+        ));
+    }
+    if from("platforms") {
+        settings.push(("platforms", toml_list(&cli.analysis.platforms)));
+    }
+    if from("dist_mappings") {
+        settings.push(("dist-mappings", toml_list(&cli.analysis.dist_mappings)));
+    }
+    if from("stories") {
+        settings.push(("stories", toml_list(&cli.analysis.stories)));
+    }
+    settings
+}
+
+fn list_settings(resolve_extensions: &[String], platforms: &[String]) -> Settings {
+    let mut settings = Settings::new();
+    if !resolve_extensions.is_empty() {
+        settings.push(("resolve-extensions", toml_list(resolve_extensions)));
+    }
+    if !platforms.is_empty() {
+        settings.push(("platforms", toml_list(platforms)));
+    }
+    settings
+}
+
+fn toml_string(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_default()
+}
+
+fn toml_list(values: &[impl AsRef<str>]) -> String {
+    let items: Vec<String> = values.iter().map(|v| toml_string(v.as_ref())).collect();
+    format!("[{}]", items.join(", "))
+}
+
+/// CLI values win when present; otherwise fall back to the config file.
+pub(crate) fn merge_list(cli: &[String], config: &[String]) -> Vec<String> {
+    if cli.is_empty() {
+        config.to_vec()
+    } else {
+        cli.to_vec()
+    }
+}
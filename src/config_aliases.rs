@@ -0,0 +1,683 @@
+use super::*;
+
+/// Outcome of scanning a JS/TS config file for an alias object.
+#[derive(Debug, Default)]
+pub(crate) struct AliasExtraction {
+    pub(crate) aliases: Vec<(String, PathBuf)>,
+    /// Entries whose value could not be evaluated statically (function calls, variables, ...).
+    pub(crate) skipped: usize,
+    /// Whether an alias key was present at all.
+    pub(crate) found: bool,
+}
+
+/// Reads `resolve.alias` from webpack configs in the root and `config/` (CRA eject layout).
+pub(crate) fn apply_webpack_aliases(root: &Path, resolver: &mut Resolver) -> Result<()> {
+    for config_path in find_config_files(root, &["", "config"], |name| {
+        name.starts_with("webpack") && has_js_config_extension(name)
+    }) {
+        let source = strip_comments(&fs::read_to_string(&config_path).unwrap_or_default());
+        let config_dir = config_path.parent().unwrap_or(root);
+        let extraction = extract_aliases(&source, &ALIAS_OBJECT_RE, config_dir, root, false);
+        push_alias_rules(resolver, &extraction);
+    }
+
+    Ok(())
+}
+
+/// Reads webpack aliases added by CRA override tools: craco's `webpack.alias`, customize-cra's
+/// `addWebpackAlias({..})`, react-app-rewire-alias's `alias({..})`, and direct
+/// `config.resolve.alias = {..}` assignments in `config-overrides.js`.
+pub(crate) fn apply_cra_override_aliases(root: &Path, resolver: &mut Resolver) -> Result<()> {
+    for config_path in find_config_files(root, &[""], |name| {
+        (name.starts_with("craco.config.") || name.starts_with("config-overrides."))
+            && has_js_config_extension(name)
+    }) {
+        let source = strip_comments(&fs::read_to_string(&config_path).unwrap_or_default());
+        let config_dir = config_path.parent().unwrap_or(root);
+        let extraction = extract_aliases(&source, &CRA_OVERRIDE_ALIAS_RE, config_dir, root, false);
+        push_alias_rules(resolver, &extraction);
+    }
+
+    Ok(())
+}
+
+/// Reads `resolve.alias` from `vite.config.*`, plus both `resolve.alias` and `test.alias` from
+/// `vitest.config.*`. Vite treats `/src` as project-root relative.
+pub(crate) fn apply_vite_aliases(root: &Path, resolver: &mut Resolver) -> Result<()> {
+    for config_path in find_config_files(root, &[""], |name| {
+        (name.starts_with("vite.config.") || name.starts_with("vitest.config."))
+            && has_js_config_extension(name)
+    }) {
+        let source = strip_comments(&fs::read_to_string(&config_path).unwrap_or_default());
+        let config_dir = config_path.parent().unwrap_or(root);
+        let extraction = extract_aliases(&source, &ALIAS_OBJECT_RE, config_dir, root, true);
+        warn_on_skipped_aliases(resolver, root, &config_path, &extraction);
+        push_alias_rules(resolver, &extraction);
+    }
+
+    Ok(())
+}
+
+/// Converts jest `moduleNameMapper` entries (from `jest.config.*` or the `"jest"` key in
+/// `package.json`) into regex alias rules. Mappings to packages or mocks outside the project
+/// (`identity-obj-proxy`) are ignored.
+pub(crate) fn apply_jest_module_name_mapper(root: &Path, resolver: &mut Resolver) -> Result<()> {
+    let mut mappings: Vec<(String, String, PathBuf)> = Vec::new();
+
+    if let Some(value) = read_root_package_json(root)?
+        && let Some(mapper) = value
+            .get("jest")
+            .and_then(|v| v.get("moduleNameMapper"))
+            .and_then(|v| v.as_object())
+    {
+        for (key, target) in mapper {
+            let mut targets = Vec::new();
+            collect_strings(target, &mut targets);
+            if let Some(target) = targets.into_iter().next() {
+                mappings.push((key.clone(), target, root.to_path_buf()));
+            }
+        }
+    }
+
+    for config_path in find_config_files(root, &[""], |name| {
+        name.starts_with("jest.config.")
+            && (has_js_config_extension(name) || name.ends_with(".json"))
+    }) {
+        let raw = fs::read_to_string(&config_path).unwrap_or_default();
+        let config_dir = config_path.parent().unwrap_or(root).to_path_buf();
+
+        if config_path.extension().is_some_and(|ext| ext == "json") {
+            let Ok(value) = serde_json::from_str::<serde_json::Value>(&sanitize_jsonc(&raw)) else {
+                continue;
+            };
+            let Some(mapper) = value.get("moduleNameMapper").and_then(|v| v.as_object()) else {
+                continue;
+            };
+            for (key, target) in mapper {
+                let mut targets = Vec::new();
+                collect_strings(target, &mut targets);
+                if let Some(target) = targets.into_iter().next() {
+                    mappings.push((key.clone(), target, config_dir.clone()));
+                }
+            }
+            continue;
+        }
+
+        let source = strip_comments(&raw);
+        for m in MODULE_NAME_MAPPER_RE.find_iter(&source) {
+            let Some(body) = balanced_block(&source, m.end()) else {
+                continue;
+            };
+            for item in split_top_level(body) {
+                let Some((key, value)) = split_key_value(item) else {
+                    continue;
+                };
+                let target = plain_string_literal(value).or_else(|| {
+                    let open = value.find('[')?;
+                    split_top_level(balanced_block(value, open)?)
+                        .into_iter()
+                        .find_map(plain_string_literal)
+                });
+                if let Some(target) = target {
+                    mappings.push((unescape_js_string(&key), target, config_dir.clone()));
+                }
+            }
+        }
+    }
+
+    for (key, target, config_dir) in mappings {
+        let Ok(pattern) = Regex::new(&key) else {
+            continue;
+        };
+        let (base_dir, rest) = match target.strip_prefix("<rootDir>") {
+            Some(rest) => (config_dir, rest.to_string()),
+            None if is_relative_specifier(&target) => (config_dir, target),
+            // Bare module names and absolute paths are not project files.
+            None => continue,
+        };
+        resolver.pattern_alias_rules.push(PatternAliasRule {
+            pattern,
+            replacement: jest_replacement_to_regex(&rest),
+            base_dir,
+        });
+    }
+
+    Ok(())
+}
+
+/// Reads `babel-plugin-module-resolver` options from `.babelrc*`, `babel.config.*` and the
+/// `package.json` `"babel"` key. `alias` entries become alias rules and `root` directories are
+/// searched like a tsconfig `baseUrl`. Regex alias keys (`"^@(.+)"`) are not supported.
+pub(crate) fn apply_babel_module_resolver(root: &Path, resolver: &mut Resolver) -> Result<()> {
+    let mut json_configs: Vec<(PathBuf, serde_json::Value)> = Vec::new();
+    if let Some(value) = read_root_package_json(root)?
+        && let Some(babel) = value.get("babel")
+    {
+        json_configs.push((root.join("package.json"), babel.clone()));
+    }
+
+    for config_path in find_config_files(root, &[""], |name| {
+        name == ".babelrc"
+            || ((name.starts_with(".babelrc.") || name.starts_with("babel.config."))
+                && (has_js_config_extension(name) || name.ends_with(".json")))
+    }) {
+        let raw = fs::read_to_string(&config_path).unwrap_or_default();
+        let is_json = config_path
+            .extension()
+            .is_none_or(|ext| ext == "json" || ext == "babelrc");
+        if is_json {
+            if let Ok(value) = serde_json::from_str(&sanitize_jsonc(&raw)) {
+                json_configs.push((config_path, value));
+            }
+            continue;
+        }
+
+        let source = strip_comments(&raw);
+        let config_dir = config_path.parent().unwrap_or(root);
+        for m in BABEL_MODULE_RESOLVER_RE.find_iter(&source) {
+            let rest = &source[m.end()..];
+            let Some(options) = rest.find('{').and_then(|idx| balanced_block(rest, idx)) else {
+                continue;
+            };
+            let mut extraction =
+                extract_aliases(options, &ALIAS_OBJECT_RE, config_dir, root, false);
+            let before = extraction.aliases.len();
+            extraction.aliases.retain(|(key, _)| !key.starts_with('^'));
+            extraction.skipped += before - extraction.aliases.len();
+            warn_on_skipped_aliases(resolver, root, &config_path, &extraction);
+            push_alias_rules(resolver, &extraction);
+
+            for m in ROOT_OPTION_RE.find_iter(options) {
+                let value = &options[m.end()..];
+                let dirs: Vec<String> = if value.starts_with('[') {
+                    balanced_block(value, 0)
+                        .map(|body| {
+                            split_top_level(body)
+                                .into_iter()
+                                .filter_map(plain_string_literal)
+                                .collect()
+                        })
+                        .unwrap_or_default()
+                } else {
+                    value
+                        .split([',', '}', '\n'])
+                        .next()
+                        .and_then(plain_string_literal)
+                        .into_iter()
+                        .collect()
+                };
+                push_babel_root_dirs(resolver, config_dir, &dirs);
+            }
+        }
+    }
+
+    for (config_path, value) in json_configs {
+        let config_dir = config_path.parent().unwrap_or(root);
+        let mut options = Vec::new();
+        collect_module_resolver_options(&value, &mut options);
+        for options in options {
+            let mut extraction = AliasExtraction::default();
+            if let Some(alias) = options.get("alias").and_then(|v| v.as_object()) {
+                extraction.found = true;
+                for (key, target) in alias {
+                    let Some(target) = target.as_str() else {
+                        extraction.skipped += 1;
+                        continue;
+                    };
+                    if key.starts_with('^') {
+                        extraction.skipped += 1;
+                        continue;
+                    }
+                    // Bare targets alias one package to another (`"underscore": "lodash"`).
+                    let path = config_dir.join(target);
+                    if is_relative_specifier(target) || target == "." || path.exists() {
+                        extraction.aliases.push((key.clone(), path));
+                    }
+                }
+            }
+            warn_on_skipped_aliases(resolver, root, &config_path, &extraction);
+            push_alias_rules(resolver, &extraction);
+
+            let mut dirs = Vec::new();
+            if let Some(root_option) = options.get("root") {
+                collect_strings(root_option, &mut dirs);
+            }
+            push_babel_root_dirs(resolver, config_dir, &dirs);
+        }
+    }
+
+    Ok(())
+}
+
+/// Finds the options object of every `["module-resolver", { ... }]` plugin entry, including
+/// ones nested under `env` or `overrides`.
+fn collect_module_resolver_options<'a>(
+    value: &'a serde_json::Value,
+    out: &mut Vec<&'a serde_json::Map<String, serde_json::Value>>,
+) {
+    match value {
+        serde_json::Value::Array(items) => {
+            let is_plugin = items.first().and_then(|v| v.as_str()).is_some_and(|name| {
+                name == "module-resolver" || name == "babel-plugin-module-resolver"
+            });
+            if is_plugin {
+                if let Some(options) = items.get(1).and_then(|v| v.as_object()) {
+                    out.push(options);
+                }
+                return;
+            }
+            for item in items {
+                collect_module_resolver_options(item, out);
+            }
+        }
+        serde_json::Value::Object(map) => {
+            for item in map.values() {
+                collect_module_resolver_options(item, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// module-resolver `root` entries may be globs; only plain directories are used.
+fn push_babel_root_dirs(resolver: &mut Resolver, config_dir: &Path, dirs: &[String]) {
+    for dir in dirs.iter().filter(|dir| !dir.contains('*')) {
+        resolver.base_dirs.push(config_dir.join(dir));
+    }
+}
+
+/// Jest uses `$1`; the regex crate would read `$1abc` as a named group, so brace the index.
+fn jest_replacement_to_regex(target: &str) -> String {
+    let mut out = String::new();
+    let mut chars = target.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '$' && chars.peek().is_some_and(|n| n.is_ascii_digit()) {
+            let mut digits = String::new();
+            while let Some(d) = chars.peek().filter(|d| d.is_ascii_digit()) {
+                digits.push(*d);
+                chars.next();
+            }
+            out.push_str(&format!("${{{digits}}}"));
+        } else if c == '$' {
+            out.push_str("$$");
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// Undoes JS string escaping in regex keys written as `'^@/(.*)\\.js$'`.
+fn unescape_js_string(value: &str) -> String {
+    let mut out = String::new();
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            if let Some(next) = chars.next() {
+                out.push(next);
+            }
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+fn warn_on_skipped_aliases(
+    resolver: &mut Resolver,
+    root: &Path,
+    config_path: &Path,
+    extraction: &AliasExtraction,
+) {
+    if extraction.skipped > 0 {
+        resolver.config_warnings.push(format!(
+            "{}: {} alias entries are too dynamic to parse; imports using them may be unresolved.",
+            relative_display(root, config_path),
+            extraction.skipped
+        ));
+    }
+}
+
+pub(crate) fn has_js_config_extension(name: &str) -> bool {
+    [".js", ".cjs", ".mjs", ".ts", ".cts", ".mts"]
+        .iter()
+        .any(|ext| name.ends_with(ext))
+}
+
+/// Lists files in `root/<dir>` accepted by `matches`, sorted for deterministic rule order.
+pub(crate) fn find_config_files(
+    root: &Path,
+    dirs: &[&str],
+    matches: impl Fn(&str) -> bool,
+) -> Vec<PathBuf> {
+    let mut out = Vec::new();
+    for dir in dirs {
+        let Ok(entries) = fs::read_dir(root.join(dir)) else {
+            continue;
+        };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+            let matched = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(&matches);
+            if matched && path.is_file() {
+                debug!(config = %relative_display(root, &path), "reading bundler config");
+                out.push(path);
+            }
+        }
+    }
+    out.sort();
+    out
+}
+
+/// Registers bundler-style prefix aliases. `key$` is an exact-match alias (webpack); any other
+/// key matches both the bare key and `key/...`.
+pub(crate) fn push_alias_rules(resolver: &mut Resolver, extraction: &AliasExtraction) {
+    for (key, target) in &extraction.aliases {
+        debug!(alias = %key, target = %shown_path(target), "bundler alias");
+        if let Some(exact) = key.strip_suffix('$') {
+            resolver.alias_rules.push(AliasRule {
+                key: exact.to_string(),
+                target: String::new(),
+                base_dir: target.clone(),
+            });
+            continue;
+        }
+
+        let key = key.trim_end_matches('/');
+        if key.is_empty() {
+            continue;
+        }
+        resolver.alias_rules.push(AliasRule {
+            key: key.to_string(),
+            target: String::new(),
+            base_dir: target.clone(),
+        });
+        resolver.alias_rules.push(AliasRule {
+            key: format!("{key}/*"),
+            target: "*".to_string(),
+            base_dir: target.clone(),
+        });
+    }
+}
+
+/// Finds every object literal introduced by `key_re` (which must end right before `{`) and
+/// collects `key: <path expression>` entries whose value can be evaluated statically.
+pub(crate) fn extract_aliases(
+    source: &str,
+    key_re: &Regex,
+    config_dir: &Path,
+    root: &Path,
+    leading_slash_is_root: bool,
+) -> AliasExtraction {
+    let mut out = AliasExtraction::default();
+
+    for m in key_re.find_iter(source) {
+        let Some(body) = balanced_block(source, m.end()) else {
+            out.found = true;
+            out.skipped += 1;
+            continue;
+        };
+        out.found = true;
+
+        if source.as_bytes()[m.end()] == b'[' {
+            extract_alias_array(body, config_dir, root, leading_slash_is_root, &mut out);
+            continue;
+        }
+
+        for item in split_top_level(body) {
+            let Some((raw_key, raw_value)) = split_key_value(item) else {
+                if !item.trim().is_empty() {
+                    out.skipped += 1;
+                }
+                continue;
+            };
+            match eval_path_expr(raw_value, config_dir, root, leading_slash_is_root) {
+                Some(target) => out.aliases.push((raw_key, target)),
+                None => out.skipped += 1,
+            }
+        }
+    }
+
+    out
+}
+
+/// Array form: `[{ find: '@', replacement: ... }]` (Vite/Rollup) or `[{ name, alias }]` (webpack).
+fn extract_alias_array(
+    body: &str,
+    config_dir: &Path,
+    root: &Path,
+    leading_slash_is_root: bool,
+    out: &mut AliasExtraction,
+) {
+    for item in split_top_level(body) {
+        let item = item.trim();
+        let Some(inner) = item.find('{').and_then(|idx| balanced_block(item, idx)) else {
+            out.skipped += 1;
+            continue;
+        };
+
+        let mut key = None;
+        let mut target = None;
+        for field in split_top_level(inner) {
+            let Some((name, value)) = split_key_value(field) else {
+                continue;
+            };
+            match name.as_str() {
+                "find" | "name" => key = plain_string_literal(value),
+                "replacement" | "alias" => {
+                    target = eval_path_expr(value, config_dir, root, leading_slash_is_root)
+                }
+                _ => {}
+            }
+        }
+
+        match (key, target) {
+            (Some(key), Some(target)) => out.aliases.push((key, target)),
+            _ => out.skipped += 1,
+        }
+    }
+}
+
+/// The value of an expression that is exactly one string literal (regex `find`s are rejected).
+pub(crate) fn plain_string_literal(expr: &str) -> Option<String> {
+    let expr = expr.trim();
+    let caps = STRING_LITERAL_RE.captures(expr)?;
+    let whole = caps.get(0)?;
+    if whole.start() != 0 || whole.end() != expr.len() {
+        return None;
+    }
+    [1usize, 2, 3]
+        .into_iter()
+        .find_map(|idx| caps.get(idx).map(|m| m.as_str().to_string()))
+}
+
+/// Returns the contents between the bracket at `open_idx` (`{` or `[`) and its match,
+/// skipping over string literals.
+pub(crate) fn balanced_block(source: &str, open_idx: usize) -> Option<&str> {
+    let bytes = source.as_bytes();
+    let open = *bytes.get(open_idx)?;
+    let close = match open {
+        b'{' => b'}',
+        b'[' => b']',
+        _ => return None,
+    };
+
+    let mut depth = 0usize;
+    let mut in_string: Option<u8> = None;
+    let mut i = open_idx;
+    while i < bytes.len() {
+        let c = bytes[i];
+        if let Some(quote) = in_string {
+            if c == b'\\' {
+                i += 2;
+                continue;
+            }
+            if c == quote {
+                in_string = None;
+            }
+        } else if c == b'\'' || c == b'"' || c == b'`' {
+            in_string = Some(c);
+        } else if c == open {
+            depth += 1;
+        } else if c == close {
+            depth -= 1;
+            if depth == 0 {
+                return source.get(open_idx + 1..i);
+            }
+        }
+        i += 1;
+    }
+
+    None
+}
+
+/// Splits an object/array body on commas that are not nested in brackets or strings.
+pub(crate) fn split_top_level(body: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut in_string: Option<char> = None;
+    let mut escaped = false;
+    let mut start = 0;
+
+    for (i, c) in body.char_indices() {
+        if let Some(quote) = in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == quote {
+                in_string = None;
+            }
+            continue;
+        }
+        match c {
+            '\'' | '"' | '`' => in_string = Some(c),
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&body[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&body[start..]);
+
+    parts.into_iter().filter(|p| !p.trim().is_empty()).collect()
+}
+
+/// Splits `key: value` where key is an identifier or a quoted string.
+pub(crate) fn split_key_value(item: &str) -> Option<(String, &str)> {
+    let item = item.trim();
+    let (raw_key, value) =
+        if let Some(quote) = item.chars().next().filter(|c| *c == '\'' || *c == '"') {
+            let end = item[1..].find(quote)? + 1;
+            let rest = item[end + 1..].trim_start().strip_prefix(':')?;
+            (item[1..end].to_string(), rest)
+        } else {
+            let (key, value) = item.split_once(':')?;
+            let key = key.trim();
+            if key.is_empty()
+                || !key
+                    .chars()
+                    .all(|c| c.is_alphanumeric() || "_$@~#-".contains(c))
+            {
+                return None;
+            }
+            (key.to_string(), value)
+        };
+
+    Some((raw_key, value.trim()))
+}
+
+/// Evaluates the static path expressions commonly used for aliases:
+/// `'./src'`, `path.resolve(__dirname, 'src')`, `path.join(__dirname, './src')`,
+/// `fileURLToPath(new URL('./src', import.meta.url))`, and `'<rootDir>/src'`.
+/// Returns `None` for anything dynamic or for bare package names.
+pub(crate) fn eval_path_expr(
+    expr: &str,
+    config_dir: &Path,
+    root: &Path,
+    leading_slash_is_root: bool,
+) -> Option<PathBuf> {
+    let expr = expr.trim();
+    let literals: Vec<String> = STRING_LITERAL_RE
+        .captures_iter(expr)
+        .filter_map(|caps| {
+            [1usize, 2, 3]
+                .into_iter()
+                .find_map(|idx| caps.get(idx).map(|m| m.as_str().to_string()))
+        })
+        .collect();
+
+    let is_plain_literal = literals.len() == 1
+        && STRING_LITERAL_RE
+            .find(expr)
+            .is_some_and(|m| m.start() == 0 && m.end() == expr.len());
+
+    if is_plain_literal {
+        let value = &literals[0];
+        if let Some(rest) = value.strip_prefix("<rootDir>") {
+            return Some(root.join(rest.trim_start_matches('/')));
+        }
+        if let Some(rest) = value.strip_prefix('/') {
+            if leading_slash_is_root {
+                return Some(root.join(rest));
+            }
+            return Some(PathBuf::from(value));
+        }
+        if is_relative_specifier(value) || value == "." {
+            return Some(config_dir.join(value));
+        }
+        // Bare values (`'src'`) are only paths when they exist; otherwise they name a package.
+        let candidate = config_dir.join(value);
+        return candidate.exists().then_some(candidate);
+    }
+
+    let is_path_call = ["resolve(", "join(", "fileURLToPath(", "new URL("]
+        .iter()
+        .any(|call| expr.contains(call));
+    if !is_path_call || literals.is_empty() {
+        return None;
+    }
+
+    // Only plain string segments plus __dirname/cwd/import.meta.url may appear.
+    let mut residue = STRING_LITERAL_RE.replace_all(expr, "").to_string();
+    for allowed in [
+        "path.resolve",
+        "path.join",
+        "resolve",
+        "join",
+        "fileURLToPath",
+        "new URL",
+        "__dirname",
+        "process.cwd()",
+        "import.meta.url",
+        "import.meta.dirname",
+    ] {
+        residue = residue.replace(allowed, "");
+    }
+    if residue
+        .chars()
+        .any(|c| c.is_alphanumeric() || c == '_' || c == '$')
+    {
+        return None;
+    }
+
+    let mut out = if expr.contains("process.cwd()") {
+        root.to_path_buf()
+    } else {
+        config_dir.to_path_buf()
+    };
+    for literal in literals {
+        let segment = literal.trim_start_matches("<rootDir>");
+        if segment.starts_with('/') && !literal.starts_with("<rootDir>") {
+            out = PathBuf::from(segment);
+        } else {
+            out = out.join(segment.trim_start_matches('/'));
+        }
+    }
+    Some(out)
+}
@@ -0,0 +1,177 @@
+use super::*;
+
+/// A tool whose config names packages by string (`"extends": ["airbnb"]`).
+#[derive(Debug, Clone, Copy)]
+struct Tool {
+    /// Config file stem (`eslint` for `.eslintrc.json` and `eslint.config.js`).
+    name: &'static str,
+    /// `package.json` key holding inline config.
+    package_key: &'static str,
+    /// Shorthand kinds the tool expands: `airbnb` -> `eslint-config-airbnb`, `macros` ->
+    /// `babel-plugin-macros`. Tools without any take full package names.
+    kinds: &'static [&'static str],
+}
+
+const TOOLS: &[Tool] = &[
+    Tool {
+        name: "eslint",
+        package_key: "eslintConfig",
+        kinds: &["plugin", "config"],
+    },
+    Tool {
+        name: "babel",
+        package_key: "babel",
+        kinds: &["preset", "plugin"],
+    },
+    Tool {
+        name: "stylelint",
+        package_key: "stylelint",
+        kinds: &["config", "plugin"],
+    },
+    Tool {
+        name: "commitlint",
+        package_key: "commitlint",
+        kinds: &["config"],
+    },
+    Tool {
+        name: "postcss",
+        package_key: "postcss",
+        kinds: &[],
+    },
+    Tool {
+        name: "prettier",
+        package_key: "prettier",
+        kinds: &[],
+    },
+    Tool {
+        name: "tailwind",
+        package_key: "tailwind",
+        kinds: &[],
+    },
+];
+
+static CONFIG_TOKEN_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"[@\w][\w@./:-]*").unwrap());
+
+/// Declared dependencies that tool configs under `dir` reference: plugin and preset names
+/// in ESLint, Babel, PostCSS, Prettier, Stylelint, commitlint, and Tailwind configs (files and
+/// `package.json` keys), plus packages that any config file imports. Those configs are read
+/// by the tools rather than imported, so their packages would otherwise look unused. Only
+/// names matching a declared package count, after expanding each tool's shorthands.
+pub(crate) fn config_referenced_packages(
+    dir: &Path,
+    modules: &HashMap<PathBuf, ModuleInfo>,
+    resolver: &Resolver,
+    declared: &HashMap<String, DepKind>,
+) -> Result<HashSet<String>> {
+    let mut used = HashSet::new();
+    let mut config_files: Vec<&PathBuf> = resolver
+        .path_index
+        .files()
+        .filter(|path| {
+            path.starts_with(dir) && !path.components().any(|c| c.as_os_str() == "node_modules")
+        })
+        .collect();
+    config_files.sort();
+
+    for file in config_files {
+        if let Some(tool) = config_tool(file) {
+            let raw = fs::read_to_string(file).unwrap_or_default();
+            let text = if has_source_extension(file) {
+                strip_comments(&raw)
+            } else {
+                raw.lines()
+                    .filter(|line| !line.trim_start().starts_with('#'))
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            };
+            mark_referenced(tool, &text, declared, &mut used);
+        }
+        if !is_common_config_file(file) {
+            continue;
+        }
+        let canonical = fs::canonicalize(file).unwrap_or_else(|_| file.clone());
+        let Some(module) = modules.get(&canonical) else {
+            continue;
+        };
+        for import in &module.imports {
+            if let Some(package) = imported_package(&canonical, &import.specifier, resolver)?
+                && declared.contains_key(&package)
+            {
+                used.insert(package);
+            }
+        }
+    }
+
+    if let Some(package_json) = read_root_package_json(dir)? {
+        for tool in TOOLS {
+            if let Some(section) = package_json.get(tool.package_key) {
+                mark_referenced(*tool, &section.to_string(), declared, &mut used);
+            }
+        }
+    }
+
+    for package in &used {
+        debug!(%package, "referenced from tool config");
+    }
+    Ok(used)
+}
+
+/// The tool reading `path`: `.eslintrc`, `.eslintrc.*`, and `eslint.config.*` belong to
+/// ESLint, and likewise for the other tools. Tailwind has no rc file.
+fn config_tool(path: &Path) -> Option<Tool> {
+    let name = path.file_name()?.to_str()?;
+    TOOLS.iter().copied().find(|tool| {
+        let rc = format!(".{}rc", tool.name);
+        name.starts_with(&format!("{}.config.", tool.name))
+            || (tool.name != "tailwind" && (name == rc || name.starts_with(&format!("{rc}."))))
+    })
+}
+
+fn mark_referenced(
+    tool: Tool,
+    text: &str,
+    declared: &HashMap<String, DepKind>,
+    used: &mut HashSet<String>,
+) {
+    for token in CONFIG_TOKEN_RE.find_iter(text) {
+        let token = token.as_str().trim_end_matches(['.', '/', ':']);
+        for candidate in package_candidates(tool, token) {
+            if declared.contains_key(&candidate) {
+                used.insert(candidate);
+            }
+        }
+    }
+}
+
+/// Package names a config string may stand for. `plugin:react/recommended` names the
+/// `react` plugin; scoped names expand inside their scope (`@typescript-eslint` ->
+/// `@typescript-eslint/eslint-plugin`, `@babel/env` -> `@babel/preset-env`).
+fn package_candidates(tool: Tool, token: &str) -> Vec<String> {
+    let name = match token.strip_prefix("plugin:") {
+        Some(rest) => rest.rsplit_once('/').map_or(rest, |(plugin, _)| plugin),
+        None => token,
+    };
+    if !looks_like_package_specifier(name) {
+        return Vec::new();
+    }
+
+    let mut candidates = Vec::new();
+    // Tools with shorthands only take full names that mention the tool, so `"react"` in an
+    // ESLint config does not count as using the `react` package.
+    if tool.kinds.is_empty() || name.contains(tool.name) {
+        candidates.push(package_name(name));
+    }
+    for kind in tool.kinds {
+        let prefix = format!("{}-{kind}", tool.name);
+        match name.split_once('/') {
+            Some((scope, rest)) if scope.starts_with('@') => {
+                candidates.push(format!("{scope}/{prefix}-{rest}"));
+                candidates.push(format!("{scope}/{kind}-{rest}"));
+            }
+            None if name.starts_with('@') => candidates.push(format!("{name}/{prefix}")),
+            None if !name.starts_with(&prefix) => candidates.push(format!("{prefix}-{name}")),
+            _ => {}
+        }
+    }
+    candidates
+}
@@ -0,0 +1,155 @@
+use super::*;
+
+/// Why each declared dependency counts as used: the reachable files importing it, each import
+/// statement, and its other uses. `used` and `note` follow the report's own verdict, the
+/// `used` packages and the reported `unused` dependencies, and `kept` ones count as used.
+/// Unused dependencies get a note on how sure that verdict is: a dependency that unreachable
+/// files or comments still import, or one judged on an incomplete import graph, deserves a
+/// second look before removal.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn dependency_usage(
+    root: &Path,
+    declared: &HashMap<String, DepKind>,
+    files: &HashSet<PathBuf>,
+    reachable: &HashSet<PathBuf>,
+    used: &HashSet<String>,
+    unused: &[String],
+    kept: &[KeptDependency],
+    modules: &HashMap<PathBuf, ModuleInfo>,
+    resolver: &Resolver,
+    script_binaries: &HashSet<String>,
+    config_references: &HashSet<String>,
+    commented_out_imports: &[CommentedOutImport],
+    unresolved_imports: usize,
+) -> Result<Vec<DependencyUsage>> {
+    let mut importers: HashMap<&str, BTreeSet<String>> = HashMap::new();
+    let mut unreachable_importers: HashMap<&str, BTreeSet<String>> = HashMap::new();
+    let mut imports: HashMap<&str, Vec<DependencyImport>> = HashMap::new();
+    // Reachable declaration files are outside `files` but still count as importers.
+    for file in files.union(reachable) {
+        let Some(module) = modules.get(file) else {
+            continue;
+        };
+        for import in &module.imports {
+            let Some(package) = imported_package(file, &import.specifier, resolver)? else {
+                continue;
+            };
+            let Some((name, _)) = declared.get_key_value(&package) else {
+                continue;
+            };
+            let is_reachable = reachable.contains(file);
+            let target = if is_reachable {
+                &mut importers
+            } else {
+                &mut unreachable_importers
+            };
+            target
+                .entry(name.as_str())
+                .or_default()
+                .insert(relative_display(root, file));
+            imports
+                .entry(name.as_str())
+                .or_default()
+                .push(DependencyImport {
+                    file: relative_display(root, file),
+                    line: import.line,
+                    specifier: import.specifier.clone(),
+                    reachable: is_reachable,
+                });
+        }
+    }
+    let ambient: HashSet<String> = resolver
+        .ambient_modules
+        .iter()
+        .filter(|name| !name.contains('*') && looks_like_package_specifier(name))
+        .map(|name| package_name(name))
+        .collect();
+
+    // `@types/*` packages are never reported unused, so there is nothing to explain.
+    let mut names: Vec<&String> = declared
+        .keys()
+        .filter(|name| !name.starts_with("@types/"))
+        .collect();
+    names.sort();
+    Ok(names
+        .into_iter()
+        .map(|name| {
+            let files: Vec<String> = importers
+                .remove(name.as_str())
+                .unwrap_or_default()
+                .into_iter()
+                .collect();
+            let mut other_uses = Vec::new();
+            if script_binaries.contains(name) {
+                other_uses.push("package.json script".to_string());
+            }
+            if config_references.contains(name) {
+                other_uses.push("tool config".to_string());
+            }
+            if ambient.contains(name) {
+                other_uses.push("declare module".to_string());
+            }
+            let keep = kept.iter().find(|keep| keep.name == *name);
+            if let Some(keep) = keep {
+                other_uses.push(format!("kept by {}", keep.source));
+            }
+            let used = used.contains(name) || keep.is_some();
+            let note = if unused.contains(name) {
+                Some(unused_note(
+                    name,
+                    unreachable_importers.get(name.as_str()),
+                    commented_out_imports,
+                    unresolved_imports,
+                ))
+            } else if !used && declared[name] != DepKind::Prod {
+                Some("a devDependency, not checked without --include-non-prod-deps".to_string())
+            } else {
+                None
+            };
+            let mut imports = imports.remove(name.as_str()).unwrap_or_default();
+            imports.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+            DependencyUsage {
+                name: name.clone(),
+                used,
+                files,
+                other_uses,
+                note,
+                imports,
+            }
+        })
+        .collect())
+}
+
+fn unused_note(
+    name: &str,
+    unreachable_importers: Option<&BTreeSet<String>>,
+    commented_out_imports: &[CommentedOutImport],
+    unresolved_imports: usize,
+) -> String {
+    let mut notes = Vec::new();
+    if let Some(files) = unreachable_importers {
+        notes.push(format!(
+            "imported only by files no entry reaches ({})",
+            files.iter().cloned().collect::<Vec<_>>().join(", ")
+        ));
+    }
+    let commented: Vec<String> = commented_out_imports
+        .iter()
+        .filter(|item| looks_like_package_specifier(&item.specifier))
+        .filter(|item| package_name(&item.specifier) == name)
+        .map(|item| format!("{}:{}", item.file, item.line))
+        .collect();
+    if !commented.is_empty() {
+        notes.push(format!("imported in comments ({})", commented.join(", ")));
+    }
+    if unresolved_imports > 0 {
+        notes.push(format!(
+            "{unresolved_imports} local imports could not be resolved, so an importer may be missing"
+        ));
+    }
+    if notes.is_empty() {
+        "no reachable file, script, or tool config uses it".to_string()
+    } else {
+        notes.join("; ")
+    }
+}
@@ -0,0 +1,161 @@
+use super::*;
+
+/// Cypress and Playwright config file names, without the extension.
+const E2E_CONFIG_STEMS: &[&str] = &[
+    "cypress.config",
+    "playwright.config",
+    "playwright-ct.config",
+];
+/// Folders under `cypress/` that Cypress loads by itself: specs (`e2e`, `component`, and the
+/// pre-v10 `integration`), support files run before every spec, plugins, and fixtures.
+const CYPRESS_DIRS: &[&str] = &[
+    "e2e",
+    "component",
+    "integration",
+    "support",
+    "plugins",
+    "fixtures",
+];
+
+/// Config keys naming the files an e2e runner loads, with a string or an array of strings:
+/// Cypress `specPattern`, `supportFile`, and the `cypress.json` folders of Cypress 9;
+/// Playwright `testDir`, `testMatch`, and global setup / teardown.
+static E2E_CONFIG_KEY_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"["']?\b(specPattern|supportFile|pluginsFile|integrationFolder|componentFolder|fixturesFolder|testDir|testMatch|globalSetup|globalTeardown)["']?\s*:\s*(\[[^\]]*\]|'[^']*'|"[^"]*"|`[^`]*`)"#,
+    )
+    .unwrap()
+});
+
+/// Source files that Cypress and Playwright run: the runners' config files, everything in the
+/// conventional `cypress/` folders, and the spec patterns, folders, and setup files those
+/// configs name. Spec files named `*.cy.ts` or `*.spec.ts` are test files already; this adds
+/// what the runner loads without an import, such as support files and `cypress/plugins`.
+pub(crate) fn e2e_entries(
+    root: &Path,
+    files: &HashSet<PathBuf>,
+    resolver: &Resolver,
+) -> Result<Vec<PathBuf>> {
+    let mut out = Vec::new();
+    let mut dirs: Vec<PathBuf> = CYPRESS_DIRS
+        .iter()
+        .map(|dir| root.join("cypress").join(dir))
+        .collect();
+    let mut patterns: Vec<Regex> = Vec::new();
+
+    let mut configs: Vec<&PathBuf> = files.iter().filter(|file| is_e2e_config(file)).collect();
+    configs.sort();
+    let legacy_config = root.join("cypress.json");
+    for config in configs
+        .into_iter()
+        .chain(legacy_config.is_file().then_some(&legacy_config))
+    {
+        if files.contains(config) {
+            out.push(config.clone());
+        }
+        // The config still counts as an entry; only the folders it names are lost.
+        let raw = match fs::read_to_string(config) {
+            Ok(raw) => raw,
+            Err(err) => {
+                warn!(config = %shown_path(config), %err, "skipped unreadable e2e config");
+                continue;
+            }
+        };
+        let config_dir = config.parent().unwrap_or(root);
+        let mut test_dir = config_dir.to_path_buf();
+        let mut test_match = Vec::new();
+        for caps in E2E_CONFIG_KEY_RE.captures_iter(&strip_comments(&raw)) {
+            for value in STRING_LITERAL_RE.captures_iter(&caps[2]) {
+                let Some(value) = value.get(1).or(value.get(2)).or(value.get(3)) else {
+                    continue;
+                };
+                let value = value.as_str().trim_start_matches("./");
+                let path = config_dir.join(value);
+                match &caps[1] {
+                    "specPattern" => patterns.extend(glob_regexes(root, config_dir, value)),
+                    "testMatch" => test_match.push(value.to_string()),
+                    "testDir" => test_dir = path,
+                    "integrationFolder" | "componentFolder" | "fixturesFolder" => dirs.push(path),
+                    _ => out.extend(resolver.resolve_path(&path)?),
+                }
+            }
+        }
+        // Playwright runs the files matching `testMatch` anywhere below `testDir`; its default
+        // match is `*.spec.ts` / `*.test.ts`, which are test files anyway.
+        for glob in test_match {
+            let glob = if glob.starts_with("**/") {
+                glob
+            } else {
+                format!("**/{glob}")
+            };
+            patterns.extend(glob_regexes(root, &test_dir, &glob));
+        }
+    }
+
+    let mut matched: Vec<PathBuf> = files
+        .iter()
+        .filter(|file| {
+            dirs.iter().any(|dir| file.starts_with(dir)) || {
+                let rel = relative_display(root, file).replace('\\', "/");
+                patterns.iter().any(|re| re.is_match(&rel))
+            }
+        })
+        .cloned()
+        .collect();
+    matched.sort();
+    for file in &matched {
+        debug!(file = %relative_display(root, file), "e2e entry");
+    }
+    out.extend(matched);
+    Ok(out)
+}
+
+/// Assets in `cypress/fixtures`, which specs load by name with `cy.fixture('logo.png')`.
+pub(crate) fn is_e2e_fixture(path: &Path) -> bool {
+    path.to_string_lossy()
+        .replace('\\', "/")
+        .contains("/cypress/fixtures/")
+}
+
+fn is_e2e_config(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .and_then(|name| name.rsplit_once('.'))
+        .is_some_and(|(stem, _)| E2E_CONFIG_STEMS.contains(&stem))
+}
+
+/// Root-relative path regexes for a glob written relative to `base`, one per alternative of a
+/// `{ts,tsx}` group. `**/` also matches no folder at all, as in the runners.
+fn glob_regexes(root: &Path, base: &Path, glob: &str) -> Vec<Regex> {
+    let prefix = base
+        .strip_prefix(root)
+        .map(|dir| dir.to_string_lossy().replace('\\', "/"))
+        .unwrap_or_default();
+    expand_braces(glob)
+        .into_iter()
+        .filter_map(|glob| {
+            let glob = if prefix.is_empty() {
+                glob
+            } else {
+                format!("{prefix}/{glob}")
+            };
+            Regex::new(&glob_path_pattern_to_regex(&glob).replace(".*/", "(?:.*/)?")).ok()
+        })
+        .collect()
+}
+
+/// `cypress/e2e/**/*.cy.{js,ts}` as `cypress/e2e/**/*.cy.js` and `cypress/e2e/**/*.cy.ts`.
+fn expand_braces(glob: &str) -> Vec<String> {
+    let Some(open) = glob.find('{') else {
+        return vec![glob.to_string()];
+    };
+    let Some(close) = glob[open..].find('}').map(|idx| open + idx) else {
+        return vec![glob.to_string()];
+    };
+    glob[open + 1..close]
+        .split(',')
+        .flat_map(|choice| {
+            expand_braces(&format!("{}{choice}{}", &glob[..open], &glob[close + 1..]))
+        })
+        .collect()
+}
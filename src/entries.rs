@@ -1,27 +1,165 @@
 use super::*;
+
+/// Who runs an entry. Files reachable only from `Dev` entries are used by tooling alone.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum EntryScope {
+    /// Shipped code: package.json fields and `exports`, default entry files, framework routes,
+    /// files started by deploy configs, and `--entry` files that are not tests or stories.
+    Prod,
+    /// Tests, Cypress and Playwright files, and scripts run from CI workflows or git hooks.
+    Dev,
+    /// Storybook stories: tooling too, but tracked apart so that code only stories use is
+    /// reported on its own.
+    Story,
+}
+
+/// Records an entry; an entry found by both a prod and a dev source stays `Prod`.
+pub(crate) fn add_entry(
+    entries: &mut BTreeMap<PathBuf, EntryScope>,
+    path: PathBuf,
+    scope: EntryScope,
+) {
+    entries
+        .entry(path)
+        .and_modify(|existing| *existing = (*existing).min(scope))
+        .or_insert(scope);
+}
+
+/// The files under `dir` with the given extension, sorted, skipping ignored folders and hidden
+/// ones such as `.nuxt` or `.svelte-kit`, where frameworks write generated code.
+pub(crate) fn files_with_extension(dir: &Path, extension: &str) -> Vec<PathBuf> {
+    let mut out: Vec<PathBuf> = walkdir::WalkDir::new(dir)
+        .into_iter()
+        .filter_entry(|e| {
+            e.depth() == 0
+                || !(is_ignored_dir(e.path())
+                    || e.file_type().is_dir() && e.file_name().to_string_lossy().starts_with('.'))
+        })
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .map(|e| e.into_path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == extension))
+        .collect();
+    out.sort();
+    out
+}
+
+/// Source files that a component haadi does not parse as a module (`.astro`, `.svelte`,
+/// `.vue`, MDX) imports: `import ... from`, side-effect imports, and dynamic `import()`,
+/// resolved from the component's folder. Imports of other components do not resolve and are
+/// skipped.
+pub(crate) fn component_imports(document: &Path, resolver: &Resolver) -> Result<Vec<PathBuf>> {
+    let text = fs::read_to_string(document).unwrap_or_default();
+    let specifiers = IMPORT_FROM_RE
+        .captures_iter(&text)
+        .map(|caps| caps[2].to_string())
+        .chain(
+            IMPORT_SIDE_EFFECT_RE
+                .captures_iter(&text)
+                .chain(DYN_IMPORT_RE.captures_iter(&text))
+                .map(|caps| caps[1].to_string()),
+        );
+    let mut out = Vec::new();
+    for specifier in specifiers {
+        out.extend(resolver.resolve_specifier(document, &specifier)?);
+    }
+    Ok(out)
+}
+
 pub(crate) fn discover_entries(
     root: &Path,
     files: &HashSet<PathBuf>,
+    resolver: &Resolver,
     cli_entries: &[String],
-) -> Result<Vec<PathBuf>> {
-    let mut entries: BTreeSet<PathBuf> = BTreeSet::new();
+    dist_mappings: &[DistMapping],
+) -> Result<BTreeMap<PathBuf, EntryScope>> {
+    let mut entries: BTreeMap<PathBuf, EntryScope> = BTreeMap::new();
 
     for entry in cli_entries {
-        if let Some(path) = resolve_candidate_path(&root.join(entry), files)? {
-            entries.insert(path);
+        if let Some(path) = resolver.resolve_path(&root.join(entry))? {
+            let scope = if is_story_file(&path) {
+                EntryScope::Story
+            } else if is_test_like_file(&path) {
+                EntryScope::Dev
+            } else {
+                EntryScope::Prod
+            };
+            add_entry(&mut entries, path, scope);
         }
     }
 
     if !entries.is_empty() {
-        return Ok(entries.into_iter().collect());
+        return Ok(entries);
     }
 
     for entry in package_json_entry_candidates(root)? {
-        if let Some(path) = resolve_candidate_path(&root.join(&entry), files)? {
-            entries.insert(path);
+        if let Some(path) = resolver.resolve_path(&root.join(&entry))? {
+            add_entry(&mut entries, path, EntryScope::Prod);
+        }
+    }
+
+    for (path, _) in package_export_entries(root, resolver)? {
+        add_entry(&mut entries, path, EntryScope::Prod);
+    }
+
+    let package_json = root.join("package.json");
+    for (reference, scope) in package_script_entry_candidates(root)? {
+        if let Some(path) =
+            resolve_infra_reference(root, &package_json, &reference, resolver, dist_mappings)?
+        {
+            add_entry(&mut entries, path, scope);
         }
     }
 
+    for path in infra_entries(root, resolver, dist_mappings)? {
+        add_entry(&mut entries, path, EntryScope::Prod);
+    }
+
+    for path in vite_entries(root, resolver)? {
+        add_entry(&mut entries, path, EntryScope::Prod);
+    }
+
+    for path in webpack_entries(root, resolver)? {
+        add_entry(&mut entries, path, EntryScope::Prod);
+    }
+
+    for (path, scope) in angular_entries(root, resolver)? {
+        add_entry(&mut entries, path, scope);
+    }
+
+    for path in nuxt_entries(root, files, resolver)? {
+        add_entry(&mut entries, path, EntryScope::Prod);
+    }
+
+    for path in sveltekit_entries(root, files, resolver)? {
+        add_entry(&mut entries, path, EntryScope::Prod);
+    }
+
+    for path in astro_entries(root, resolver)? {
+        add_entry(&mut entries, path, EntryScope::Prod);
+    }
+
+    for path in gatsby_entries(root, files, resolver)? {
+        add_entry(&mut entries, path, EntryScope::Prod);
+    }
+
+    for entry in workflow_entry_candidates(root)? {
+        if let Some(path) = resolver.resolve_path(&root.join(&entry))? {
+            add_entry(&mut entries, path, EntryScope::Dev);
+        }
+    }
+
+    for entry in git_hook_entry_candidates(root)? {
+        if let Some(path) = resolver.resolve_path(&root.join(&entry))? {
+            add_entry(&mut entries, path, EntryScope::Dev);
+        }
+    }
+
+    for path in e2e_entries(root, files, resolver)? {
+        add_entry(&mut entries, path, EntryScope::Dev);
+    }
+
     for candidate in [
         "src/index.ts",
         "src/index.tsx",
@@ -34,18 +172,29 @@ pub(crate) fn discover_entries(
         "index.ts",
         "index.js",
     ] {
-        if let Some(path) = resolve_candidate_path(&root.join(candidate), files)? {
-            entries.insert(path);
+        if let Some(path) = resolver.resolve_path(&root.join(candidate))? {
+            add_entry(&mut entries, path, EntryScope::Prod);
         }
     }
 
     for file in files {
-        if is_framework_convention_entry(root, file) || is_test_like_file(file) {
-            entries.insert(file.clone());
+        if is_framework_convention_entry(root, file) {
+            add_entry(&mut entries, file.clone(), EntryScope::Prod);
+        } else if is_story_file(file) {
+            add_entry(&mut entries, file.clone(), EntryScope::Story);
+        } else if is_test_like_file(file) {
+            add_entry(&mut entries, file.clone(), EntryScope::Dev);
         }
     }
 
-    Ok(entries.into_iter().collect())
+    Ok(entries)
+}
+
+/// Storybook stories (`Button.stories.tsx`), loaded by Storybook's own glob.
+pub(crate) fn is_story_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|name| name.contains(".stories.") || name.contains(".story."))
 }
 
 fn is_framework_convention_entry(root: &Path, file: &Path) -> bool {
@@ -71,6 +220,157 @@ fn is_framework_convention_entry(root: &Path, file: &Path) -> bool {
     false
 }
 
+/// Collects script paths invoked from `run:` steps in `.github/workflows/*.yml`, e.g.
+/// `node scripts/release.js` or `npx ts-node tools/check.ts`.
+fn workflow_entry_candidates(root: &Path) -> Result<Vec<String>> {
+    let workflows_dir = root.join(".github").join("workflows");
+    let Ok(dir_entries) = fs::read_dir(&workflows_dir) else {
+        return Ok(Vec::new());
+    };
+
+    let mut out = Vec::new();
+    for dir_entry in dir_entries.filter_map(|e| e.ok()) {
+        let path = dir_entry.path();
+        let is_yaml = path
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .is_some_and(|ext| ext == "yml" || ext == "yaml");
+        if !is_yaml {
+            continue;
+        }
+
+        // One unreadable workflow (permissions, invalid UTF-8) should not fail the analysis.
+        let raw = match fs::read_to_string(&path) {
+            Ok(raw) => raw,
+            Err(err) => {
+                warn!(workflow = %shown_path(&path), %err, "skipped unreadable workflow");
+                continue;
+            }
+        };
+        for command in workflow_run_commands(&raw) {
+            out.extend(script_file_references(&command));
+        }
+    }
+
+    Ok(out)
+}
+
+/// Extracts the commands of `run:` keys, including `run: |` and `run: >` block scalars.
+fn workflow_run_commands(raw: &str) -> Vec<String> {
+    let mut commands = Vec::new();
+    let mut block: Option<(usize, String)> = None;
+
+    for line in raw.lines() {
+        let indent = line.len() - line.trim_start().len();
+
+        if let Some((block_indent, mut text)) = block.take() {
+            if line.trim().is_empty() || indent > block_indent {
+                text.push_str(line.trim());
+                text.push('\n');
+                block = Some((block_indent, text));
+                continue;
+            }
+            commands.push(text);
+        }
+
+        let trimmed = line.trim_start().trim_start_matches("- ").trim_start();
+        let Some(value) = trimmed.strip_prefix("run:") else {
+            continue;
+        };
+        let value = value.trim();
+        if value.starts_with('|') || value.starts_with('>') {
+            block = Some((indent, String::new()));
+        } else {
+            commands.push(value.to_string());
+        }
+    }
+
+    if let Some((_, text)) = block {
+        commands.push(text);
+    }
+
+    commands
+}
+
+/// Collects script paths invoked from git hooks: `.husky/*` hook files, the `lint-staged`
+/// config (package.json key, `.lintstagedrc*` or `lint-staged.config.*`) and the
+/// `simple-git-hooks` / husky v4 `husky.hooks` package.json keys.
+fn git_hook_entry_candidates(root: &Path) -> Result<Vec<String>> {
+    let mut out = Vec::new();
+
+    if let Ok(dir_entries) = fs::read_dir(root.join(".husky")) {
+        for dir_entry in dir_entries.filter_map(|e| e.ok()) {
+            let path = dir_entry.path();
+            if !path.is_file() {
+                continue;
+            }
+            let raw = fs::read_to_string(&path).unwrap_or_default();
+            for line in raw
+                .lines()
+                .filter(|line| !line.trim_start().starts_with('#'))
+            {
+                out.extend(script_file_references(line));
+            }
+        }
+    }
+
+    if let Some(value) = read_root_package_json(root)? {
+        let mut commands = Vec::new();
+        for hooks in [
+            value.get("lint-staged"),
+            value.get("simple-git-hooks"),
+            value.get("husky").and_then(|v| v.get("hooks")),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            collect_strings(hooks, &mut commands);
+        }
+        for command in commands {
+            out.extend(script_file_references(&command));
+        }
+    }
+
+    for name in [".lintstagedrc", ".lintstagedrc.json"] {
+        let Ok(raw) = fs::read_to_string(root.join(name)) else {
+            continue;
+        };
+        let Ok(value) = serde_json::from_str::<serde_json::Value>(&sanitize_jsonc(&raw)) else {
+            continue;
+        };
+        let mut commands = Vec::new();
+        collect_strings(&value, &mut commands);
+        for command in commands {
+            out.extend(script_file_references(&command));
+        }
+    }
+
+    for name in [
+        ".lintstagedrc.js",
+        ".lintstagedrc.cjs",
+        ".lintstagedrc.mjs",
+        "lint-staged.config.js",
+        "lint-staged.config.cjs",
+        "lint-staged.config.mjs",
+    ] {
+        if let Ok(raw) = fs::read_to_string(root.join(name)) {
+            out.extend(script_file_references(&strip_comments(&raw)));
+        }
+    }
+
+    Ok(out)
+}
+
+/// Path-like source file arguments in a shell command line.
+pub(crate) fn script_file_references(command: &str) -> Vec<String> {
+    SCRIPT_FILE_REF_RE
+        .captures_iter(command)
+        .filter_map(|caps| caps.get(1))
+        .map(|m| m.as_str().trim_start_matches("./").to_string())
+        .filter(|path| !path.contains("://"))
+        .collect()
+}
+
 fn package_json_entry_candidates(root: &Path) -> Result<Vec<String>> {
     let package_json = root.join("package.json");
     if !package_json.exists() {
@@ -99,14 +399,140 @@ fn package_json_entry_candidates(root: &Path) -> Result<Vec<String>> {
         }
     }
 
-    if let Some(exports) = value.get("exports") {
-        collect_strings(exports, &mut out);
+    Ok(out)
+}
+
+/// Source files named in `package.json` scripts, such as `"seed": "node scripts/seed.js"` or
+/// `"migrate": "tsx src/db/migrate.ts"`. `start` runs the app, so its files are prod entries;
+/// files of other scripts are dev entries.
+fn package_script_entry_candidates(root: &Path) -> Result<Vec<(String, EntryScope)>> {
+    let Some(value) = read_root_package_json(root)? else {
+        return Ok(Vec::new());
+    };
+    let Some(scripts) = value.get("scripts").and_then(|v| v.as_object()) else {
+        return Ok(Vec::new());
+    };
+    let mut out = Vec::new();
+    for (name, command) in scripts {
+        let Some(command) = command.as_str() else {
+            continue;
+        };
+        let scope = if name == "start" {
+            EntryScope::Prod
+        } else {
+            EntryScope::Dev
+        };
+        out.extend(
+            script_file_references(command)
+                .into_iter()
+                .filter(|reference| !reference.contains("node_modules/"))
+                .map(|reference| (reference, scope)),
+        );
+    }
+    Ok(out)
+}
+
+/// Resolves every local target of the root `package.json` `exports` map, keeping the subpath
+/// and condition chain each one came from. Directory targets (`"./utils/": "./src/utils/"`)
+/// and `*` patterns expand to all matching source files; `null` targets are skipped.
+pub(crate) fn package_export_entries(
+    root: &Path,
+    resolver: &Resolver,
+) -> Result<Vec<(PathBuf, ExportEntry)>> {
+    let Some(exports) = read_root_package_json(root)?.and_then(|v| v.get("exports").cloned())
+    else {
+        return Ok(Vec::new());
+    };
+
+    let mut targets = Vec::new();
+    collect_export_condition_targets(&exports, ".", &mut Vec::new(), &mut targets);
+
+    let mut out = Vec::new();
+    let mut seen = HashSet::new();
+    for (subpath, conditions, target) in targets {
+        for path in resolve_export_target(root, resolver, &target)? {
+            let entry = ExportEntry {
+                file: relative_display(root, &path),
+                subpath: subpath.clone(),
+                conditions: conditions.clone(),
+            };
+            if seen.insert((path.clone(), subpath.clone(), conditions.clone())) {
+                out.push((path, entry));
+            }
+        }
     }
 
+    out.sort_by(|a, b| a.1.file.cmp(&b.1.file).then(a.1.subpath.cmp(&b.1.subpath)));
     Ok(out)
 }
 
-fn collect_strings(value: &serde_json::Value, out: &mut Vec<String>) {
+fn collect_export_condition_targets(
+    value: &serde_json::Value,
+    subpath: &str,
+    conditions: &mut Vec<String>,
+    out: &mut Vec<(String, Vec<String>, String)>,
+) {
+    match value {
+        serde_json::Value::String(target) => {
+            out.push((subpath.to_string(), conditions.clone(), target.clone()));
+        }
+        serde_json::Value::Array(items) => {
+            for item in items {
+                collect_export_condition_targets(item, subpath, conditions, out);
+            }
+        }
+        serde_json::Value::Object(map) => {
+            for (key, target) in map {
+                if key.starts_with('.') {
+                    collect_export_condition_targets(target, key, &mut Vec::new(), out);
+                } else {
+                    conditions.push(key.clone());
+                    collect_export_condition_targets(target, subpath, conditions, out);
+                    conditions.pop();
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn resolve_export_target(root: &Path, resolver: &Resolver, target: &str) -> Result<Vec<PathBuf>> {
+    let rel = target.trim_start_matches("./");
+    if !target.starts_with("./") || rel.starts_with("../") {
+        return Ok(Vec::new());
+    }
+
+    if rel.contains('*') {
+        // In `exports` patterns `*` may span directories.
+        let Ok(re) = Regex::new(&glob_path_pattern_to_regex(&rel.replace('*', "**"))) else {
+            return Ok(Vec::new());
+        };
+        let mut matched: Vec<PathBuf> = resolver
+            .files
+            .iter()
+            .filter(|file| re.is_match(&relative_display(root, file)))
+            .cloned()
+            .collect();
+        matched.sort();
+        return Ok(matched);
+    }
+
+    let path = root.join(rel);
+    if rel.ends_with('/') || path.is_dir() {
+        let mut matched: Vec<PathBuf> = resolver
+            .files
+            .iter()
+            .filter(|file| file.starts_with(&path))
+            .cloned()
+            .collect();
+        matched.sort();
+        return Ok(matched);
+    }
+
+    Ok(resolver.resolve_path(&path)?.into_iter().collect())
+}
+
+pub(crate) fn collect_strings(value: &serde_json::Value, out: &mut Vec<String>) {
     match value {
         serde_json::Value::String(s) => out.push(s.to_string()),
         serde_json::Value::Object(map) => {
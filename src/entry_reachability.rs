@@ -0,0 +1,32 @@
+use super::*;
+
+/// For every reachable source file, lists the entries whose import graph reaches it
+/// (`--per-entry`). Entry files themselves are included and reach themselves.
+pub(crate) fn compute_entry_reachability(
+    root: &Path,
+    files: &HashSet<PathBuf>,
+    entries: &BTreeMap<PathBuf, EntryScope>,
+    modules: &HashMap<PathBuf, ModuleInfo>,
+    resolver: &Resolver,
+) -> Result<Vec<EntryReachability>> {
+    let mut reached_by: BTreeMap<&PathBuf, Vec<(&PathBuf, EntryScope)>> = BTreeMap::new();
+    for (entry, scope) in entries {
+        for file in reachable_files(std::slice::from_ref(entry), modules, resolver)? {
+            if let Some(file) = files.get(&file) {
+                reached_by.entry(file).or_default().push((entry, *scope));
+            }
+        }
+    }
+
+    Ok(reached_by
+        .into_iter()
+        .map(|(file, reached)| EntryReachability {
+            file: relative_display(root, file),
+            dev_only: reached.iter().all(|(_, scope)| *scope != EntryScope::Prod),
+            entries: reached
+                .iter()
+                .map(|(entry, _)| relative_display(root, entry))
+                .collect(),
+        })
+        .collect())
+}
@@ -0,0 +1,91 @@
+use super::*;
+
+/// Variables provided by tooling rather than `.env` files.
+const BUILTIN_ENV_VARS: &[&str] = &["NODE_ENV", "MODE", "DEV", "PROD", "SSR", "BASE_URL"];
+
+/// Cross-references variables declared in `.env*` files at the project root with
+/// `process.env.X` / `import.meta.env.X` reads in source files.
+pub(crate) fn analyze_env_vars(
+    root: &Path,
+    files: &HashSet<PathBuf>,
+) -> Result<(Vec<UnusedEnvVar>, Vec<UndeclaredEnvVar>)> {
+    let declared = collect_declared_env_vars(root)?;
+
+    let mut reads: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for file in files {
+        let source = strip_comments(&fs::read_to_string(file).unwrap_or_default());
+        for caps in ENV_READ_RE.captures_iter(&source) {
+            let names: Vec<String> = match (caps.get(1).or(caps.get(2)), caps.get(3)) {
+                (Some(name), _) => vec![name.as_str().to_string()],
+                (None, Some(list)) => parse_destructured_names(list.as_str())
+                    .into_iter()
+                    .map(|name| name.trim_start_matches("...").to_string())
+                    .collect(),
+                (None, None) => continue,
+            };
+            for name in names {
+                reads
+                    .entry(name)
+                    .or_default()
+                    .insert(relative_display(root, file));
+            }
+        }
+    }
+
+    let unused = declared
+        .iter()
+        .filter(|(name, _)| !reads.contains_key(*name))
+        .map(|(name, env_files)| UnusedEnvVar {
+            name: name.clone(),
+            env_files: env_files.iter().cloned().collect(),
+        })
+        .collect();
+
+    let undeclared = reads
+        .into_iter()
+        .filter(|(name, _)| {
+            !declared.contains_key(name) && !BUILTIN_ENV_VARS.contains(&name.as_str())
+        })
+        .map(|(name, files)| UndeclaredEnvVar {
+            name,
+            files: files.into_iter().collect(),
+        })
+        .collect();
+
+    Ok((unused, undeclared))
+}
+
+fn collect_declared_env_vars(root: &Path) -> Result<BTreeMap<String, BTreeSet<String>>> {
+    let mut declared: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+
+    for entry in fs::read_dir(root)? {
+        let path = entry?.path();
+        let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        if !path.is_file() || !(file_name == ".env" || file_name.starts_with(".env.")) {
+            continue;
+        }
+
+        let raw = fs::read_to_string(&path).unwrap_or_default();
+        for line in raw.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let Some((key, _)) = line.split_once('=') else {
+                continue;
+            };
+            let key = key.trim();
+            if IDENT_TOKEN_RE.find(key).is_some_and(|m| m.as_str() == key) {
+                declared
+                    .entry(key.to_string())
+                    .or_default()
+                    .insert(file_name.to_string());
+            }
+        }
+    }
+
+    Ok(declared)
+}
@@ -0,0 +1,152 @@
+use super::*;
+use crate::findings::{BROKEN_IMPORT, UNUSED_ASSET, UNUSED_DEPENDENCY, UNUSED_EXPORT, UNUSED_FILE};
+
+/// How much a [`FindingEvent`] calls for action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// Something is broken now, such as an import of a missing file.
+    Error,
+    /// Dead code or an unneeded dependency that can likely be removed.
+    Warning,
+    /// A note about the analysis itself.
+    Info,
+}
+
+/// How far a [`FindingEvent`] can be trusted. Findings made while some local imports could not
+/// be resolved (kept with `--include-low-confidence`) are `Low`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Confidence {
+    High,
+    Low,
+}
+
+/// What a [`FindingEvent`] is about. Paths are relative to the analyzed root, with `/`
+/// separators.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum FindingKind {
+    UnusedFile {
+        path: String,
+    },
+    UnusedAsset {
+        path: String,
+    },
+    UnusedDependency {
+        name: String,
+    },
+    UnusedExport {
+        file: String,
+        export: String,
+        line: usize,
+        column: usize,
+    },
+    BrokenImport {
+        file: String,
+        specifier: String,
+        suggestion: Option<String>,
+    },
+    /// One of the report's warnings, such as findings skipped for low graph confidence.
+    Warning {
+        message: String,
+    },
+}
+
+/// One finding of a [`Report`] as a typed value, for reporters that should not depend on the
+/// report's layout. `id` is the [`finding_id`] of the finding; warnings get one from their
+/// message.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FindingEvent {
+    pub id: String,
+    pub severity: Severity,
+    pub confidence: Confidence,
+    #[serde(flatten)]
+    pub kind: FindingKind,
+}
+
+impl Report {
+    /// Unused files, assets, dependencies, and exports, then broken imports and warnings, as
+    /// [`FindingEvent`]s in report order.
+    pub fn finding_events(&self) -> impl Iterator<Item = FindingEvent> + '_ {
+        let graph_confidence = if self.summary.high_confidence_graph {
+            Confidence::High
+        } else {
+            Confidence::Low
+        };
+        let event = move |id: String, severity: Severity, kind: FindingKind| FindingEvent {
+            id,
+            severity,
+            confidence: if severity == Severity::Warning {
+                graph_confidence
+            } else {
+                Confidence::High
+            },
+            kind,
+        };
+        let unused_files = self.unused_files.iter().map(move |path| {
+            event(
+                finding_id(UNUSED_FILE, path, None),
+                Severity::Warning,
+                FindingKind::UnusedFile {
+                    path: path.replace('\\', "/"),
+                },
+            )
+        });
+        let unused_assets = self.unused_assets.iter().map(move |path| {
+            event(
+                finding_id(UNUSED_ASSET, path, None),
+                Severity::Warning,
+                FindingKind::UnusedAsset {
+                    path: path.replace('\\', "/"),
+                },
+            )
+        });
+        let unused_dependencies = self.unused_dependencies.iter().map(move |name| {
+            event(
+                finding_id(UNUSED_DEPENDENCY, name, None),
+                Severity::Warning,
+                FindingKind::UnusedDependency { name: name.clone() },
+            )
+        });
+        let unused_exports = self.unused_exports.iter().map(move |item| {
+            event(
+                finding_id(UNUSED_EXPORT, &item.file, Some(&item.export)),
+                Severity::Warning,
+                FindingKind::UnusedExport {
+                    file: item.file.replace('\\', "/"),
+                    export: item.export.clone(),
+                    line: item.line,
+                    column: item.column,
+                },
+            )
+        });
+        let broken_imports = self.broken_imports.iter().map(move |item| {
+            event(
+                finding_id(BROKEN_IMPORT, &item.file, Some(&item.specifier)),
+                Severity::Error,
+                FindingKind::BrokenImport {
+                    file: item.file.replace('\\', "/"),
+                    specifier: item.specifier.clone(),
+                    suggestion: item.suggestion.clone(),
+                },
+            )
+        });
+        let warnings = self.warnings.iter().map(move |message| {
+            event(
+                finding_id("warning", message, None),
+                Severity::Info,
+                FindingKind::Warning {
+                    message: message.clone(),
+                },
+            )
+        });
+        unused_files
+            .chain(unused_assets)
+            .chain(unused_dependencies)
+            .chain(unused_exports)
+            .chain(broken_imports)
+            .chain(warnings)
+    }
+}
@@ -0,0 +1,201 @@
+use super::*;
+
+pub(crate) const UNUSED_FILE: &str = "unused_file";
+pub(crate) const TOOLING_ONLY_FILE: &str = "tooling_only_file";
+pub(crate) const STORYBOOK_ONLY_FILE: &str = "storybook_only_file";
+pub(crate) const UNUSED_ASSET: &str = "unused_asset";
+pub(crate) const UNUSED_DEPENDENCY: &str = "unused_dependency";
+pub(crate) const MISSING_DEPENDENCY: &str = "missing_dependency";
+pub(crate) const MISPLACED_DEPENDENCY: &str = "misplaced_dependency";
+pub(crate) const DEV_DEPENDENCY_IN_PRODUCTION: &str = "dev_dependency_in_production";
+pub(crate) const ORPHANED_TYPES_PACKAGE: &str = "orphaned_types_package";
+pub(crate) const UNUSED_EXPORT: &str = "unused_export";
+pub(crate) const STORYBOOK_ONLY_EXPORT: &str = "storybook_only_export";
+pub(crate) const DUPLICATE_ASSET: &str = "duplicate_asset";
+pub(crate) const UNUSED_ENV_VAR: &str = "unused_env_var";
+pub(crate) const UNDECLARED_ENV_VAR: &str = "undeclared_env_var";
+pub(crate) const BROKEN_IMPORT: &str = "broken_import";
+pub(crate) const BROKEN_ASSET_REFERENCE: &str = "broken_asset_reference";
+pub(crate) const COMMENTED_CODE: &str = "commented_code";
+pub(crate) const UNUSED_SCRIPT: &str = "unused_script";
+
+/// Stable identifier for a finding: 16 hex digits of FNV-1a over the category, the path (or
+/// dependency / variable name) with `/` separators, and the export name. It only changes when
+/// one of those does, so baselines and suppression files can refer to it across versions.
+pub fn finding_id(category: &str, subject: &str, export: Option<&str>) -> String {
+    let subject = subject.replace('\\', "/");
+    let mut bytes = Vec::new();
+    for part in [category, subject.as_str(), export.unwrap_or_default()] {
+        bytes.extend_from_slice(part.as_bytes());
+        bytes.push(0);
+    }
+    format!("{:016x}", fnv1a(&bytes))
+}
+
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    const OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    bytes.iter().fold(OFFSET, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(PRIME)
+    })
+}
+
+/// Flattens every finding category of a report into [`Finding`]s, in report order. Findings
+/// about a file carry a hash of its contents, read through `read_file` with the path relative
+/// to the root, so a later run can recognize a move or rename.
+pub(crate) fn collect_findings(
+    report: &Report,
+    read_file: impl Fn(&str) -> Option<Vec<u8>>,
+) -> Vec<Finding> {
+    let mut content_hashes: HashMap<String, Option<String>> = HashMap::new();
+    let mut out = Vec::new();
+    let mut push = |category: &str, subject: &str, export: Option<&str>| {
+        let is_file = !matches!(
+            category,
+            UNUSED_DEPENDENCY
+                | MISSING_DEPENDENCY
+                | MISPLACED_DEPENDENCY
+                | DEV_DEPENDENCY_IN_PRODUCTION
+                | ORPHANED_TYPES_PACKAGE
+                | UNUSED_ENV_VAR
+                | UNDECLARED_ENV_VAR
+        );
+        let content_hash = if is_file {
+            content_hashes
+                .entry(subject.to_string())
+                .or_insert_with(|| {
+                    read_file(subject).map(|bytes| format!("{:016x}", fnv1a(&bytes)))
+                })
+                .clone()
+        } else {
+            None
+        };
+        out.push(Finding {
+            id: finding_id(category, subject, export),
+            category: category.to_string(),
+            subject: subject.replace('\\', "/"),
+            export: export.map(str::to_string),
+            content_hash,
+        });
+    };
+
+    for path in &report.unused_files {
+        push(UNUSED_FILE, path, None);
+    }
+    for path in &report.tooling_only_files {
+        push(TOOLING_ONLY_FILE, path, None);
+    }
+    for path in &report.storybook_only_files {
+        push(STORYBOOK_ONLY_FILE, path, None);
+    }
+    for path in &report.unused_assets {
+        push(UNUSED_ASSET, path, None);
+    }
+    for dep in &report.unused_dependencies {
+        push(UNUSED_DEPENDENCY, dep, None);
+    }
+    for item in &report.missing_dependencies {
+        push(MISSING_DEPENDENCY, &item.name, None);
+    }
+    for item in &report.misplaced_dependencies {
+        push(MISPLACED_DEPENDENCY, &item.name, None);
+    }
+    for item in &report.dev_dependencies_in_production {
+        push(DEV_DEPENDENCY_IN_PRODUCTION, &item.name, None);
+    }
+    for name in &report.orphaned_types_packages {
+        push(ORPHANED_TYPES_PACKAGE, name, None);
+    }
+    for item in &report.unused_exports {
+        push(UNUSED_EXPORT, &item.file, Some(&item.export));
+    }
+    for item in &report.storybook_only_exports {
+        push(STORYBOOK_ONLY_EXPORT, &item.file, Some(&item.export));
+    }
+    for item in &report.duplicate_assets {
+        push(DUPLICATE_ASSET, &item.file, None);
+    }
+    for item in &report.unused_env_vars {
+        push(UNUSED_ENV_VAR, &item.name, None);
+    }
+    for item in &report.undeclared_env_vars {
+        push(UNDECLARED_ENV_VAR, &item.name, None);
+    }
+    for item in &report.broken_imports {
+        push(BROKEN_IMPORT, &item.file, Some(&item.specifier));
+    }
+    for item in &report.broken_asset_references {
+        push(BROKEN_ASSET_REFERENCE, &item.file, Some(&item.specifier));
+    }
+    for item in &report.commented_code {
+        push(COMMENTED_CODE, &item.file, Some(&item.statement));
+    }
+    for item in &report.unused_scripts {
+        push(UNUSED_SCRIPT, &item.package_json, Some(&item.name));
+    }
+
+    out
+}
+
+/// Diffs the findings of a previous report against the current ones. A finding that vanished
+/// and one that appeared in the same category with the same content hash (and export name)
+/// are reported as a rename instead of a fix plus a new finding.
+pub(crate) fn compare_findings(
+    baseline: &str,
+    previous: &[Finding],
+    current: &[Finding],
+) -> FindingComparison {
+    let previous_ids: HashSet<&str> = previous.iter().map(|f| f.id.as_str()).collect();
+    let current_ids: HashSet<&str> = current.iter().map(|f| f.id.as_str()).collect();
+
+    let mut fixed: Vec<Finding> = previous
+        .iter()
+        .filter(|f| !current_ids.contains(f.id.as_str()))
+        .cloned()
+        .collect();
+    let mut added = Vec::new();
+    let mut renamed = Vec::new();
+
+    for finding in current
+        .iter()
+        .filter(|f| !previous_ids.contains(f.id.as_str()))
+    {
+        let candidates: Vec<usize> = fixed
+            .iter()
+            .enumerate()
+            .filter(|(_, old)| {
+                finding.content_hash.is_some()
+                    && old.category == finding.category
+                    && old.export == finding.export
+                    && old.content_hash == finding.content_hash
+            })
+            .map(|(idx, _)| idx)
+            .collect();
+        // Identical files (e.g. empty stubs) may match several; prefer the same file name.
+        let rename_of = candidates
+            .iter()
+            .copied()
+            .find(|idx| file_name(&fixed[*idx].subject) == file_name(&finding.subject))
+            .or_else(|| candidates.first().copied());
+        match rename_of {
+            Some(idx) => renamed.push(RenamedFinding {
+                from: fixed.remove(idx),
+                to: finding.clone(),
+            }),
+            None => added.push(finding.clone()),
+        }
+    }
+
+    FindingComparison {
+        baseline: baseline.to_string(),
+        unchanged_count: current.len() - added.len() - renamed.len(),
+        added,
+        fixed,
+        renamed,
+    }
+}
+
+fn file_name(subject: &str) -> &str {
+    subject.rsplit('/').next().unwrap_or(subject)
+}
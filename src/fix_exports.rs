@@ -0,0 +1,225 @@
+use super::*;
+
+/// Where an export is declared in a source file.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct ExportLocation {
+    /// Byte range of the exported name (or of `export default`).
+    pub(crate) range: (usize, usize),
+    /// Byte range of `export ` in a declaration such as `export const x`, which can be removed
+    /// to keep the declaration local. `None` for export lists and default exports.
+    pub(crate) keyword: Option<(usize, usize)>,
+}
+
+pub(crate) fn locate_export(source: &str, export: &str) -> Option<ExportLocation> {
+    if export == "default" {
+        let m = EXPORT_DEFAULT_RE.find(source)?;
+        let start = m.start() + (m.as_str().len() - m.as_str().trim_start().len());
+        return Some(ExportLocation {
+            range: (start, m.end()),
+            keyword: None,
+        });
+    }
+
+    for caps in EXPORT_DECL_RE.captures_iter(source) {
+        let (Some(all), Some(name)) = (caps.get(0), caps.get(1)) else {
+            continue;
+        };
+        if name.as_str() != export {
+            continue;
+        }
+        let keyword_start = all.start() + all.as_str().find("export")?;
+        let after_keyword = &source[keyword_start + "export".len()..];
+        let keyword_end = keyword_start
+            + "export".len()
+            + (after_keyword.len() - after_keyword.trim_start().len());
+        return Some(ExportLocation {
+            range: (name.start(), name.end()),
+            keyword: Some((keyword_start, keyword_end)),
+        });
+    }
+
+    for caps in EXPORT_LIST_RE.captures_iter(source) {
+        let Some(list) = caps.get(1) else {
+            continue;
+        };
+        let found = IDENT_TOKEN_RE
+            .find_iter(list.as_str())
+            .find(|token| token.as_str() == export);
+        if let Some(token) = found {
+            return Some(ExportLocation {
+                range: (list.start() + token.start(), list.start() + token.end()),
+                keyword: None,
+            });
+        }
+    }
+
+    None
+}
+
+/// Plans `--fix exports`: for every unused export, checks that its name appears in no other
+/// scanned file, counted over the raw identifier tokens rather than the import graph, so
+/// dynamic lookups, string-keyed mocks, and same-named re-exports keep the export. Only
+/// `export` keywords of declarations are stripped; export lists, default exports, and
+/// statements declaring several variables are refused because removing them means editing
+/// more than one token.
+pub(crate) fn plan_export_fixes(
+    root: &Path,
+    files: &HashSet<PathBuf>,
+    unused_exports: &[UnusedExport],
+) -> Vec<ExportFix> {
+    let names: HashSet<&str> = unused_exports
+        .iter()
+        .map(|item| item.export.as_str())
+        .filter(|name| *name != "default")
+        .collect();
+    let counts = count_export_name_files(files, &HashSet::new(), &HashSet::new(), &names, |file| {
+        fs::read_to_string(file).unwrap_or_default()
+    })
+    .project;
+
+    let mut fixes = Vec::new();
+    for item in unused_exports {
+        let path = root.join(&item.file);
+        let source = fs::read_to_string(&path).unwrap_or_default();
+        let in_own_file = IDENT_TOKEN_RE
+            .find_iter(&source)
+            .any(|token| token.as_str() == item.export);
+        let outside_occurrences = counts
+            .get(&item.export)
+            .copied()
+            .unwrap_or(0)
+            .saturating_sub(usize::from(in_own_file));
+
+        let reason = if item.export == "default" {
+            Some("default exports are left for manual review".to_string())
+        } else if outside_occurrences > 0 {
+            Some(format!(
+                "'{}' appears in {} other file(s); it may be used by name",
+                item.export, outside_occurrences
+            ))
+        } else if EXPORT_ALL_RE.captures_iter(&source).any(|caps| {
+            caps.get(1)
+                .is_some_and(|alias| alias.as_str() == item.export)
+        }) {
+            Some("namespace re-export; remove the `export * as` statement by hand".to_string())
+        } else {
+            match locate_export(&source, &item.export) {
+                Some(ExportLocation {
+                    keyword: Some(_),
+                    range,
+                }) if declares_several(&source, range) => Some(
+                    "declared together with other variables; split the statement by hand"
+                        .to_string(),
+                ),
+                Some(ExportLocation {
+                    keyword: Some(_), ..
+                }) => None,
+                Some(_) => Some("declared in an export list; edit the list by hand".to_string()),
+                None => Some("export declaration not found in the file".to_string()),
+            }
+        };
+
+        fixes.push(ExportFix {
+            file: item.file.clone(),
+            export: item.export.clone(),
+            safe: reason.is_none(),
+            outside_occurrences,
+            reason,
+            applied: false,
+        });
+    }
+    fixes
+}
+
+/// Whether the `const`/`let`/`var` statement declaring the name at `range` has more
+/// declarators (`export const a = 1, b = 2`): stripping its `export` would unexport them all.
+/// Looks for a comma outside brackets, strings, and comments before the statement ends at a
+/// `;` or at a line break that no operator continues.
+fn declares_several(source: &str, range: (usize, usize)) -> bool {
+    let before = source[..range.0].trim_end();
+    if !["const", "let", "var"]
+        .iter()
+        .any(|keyword| before.ends_with(keyword))
+    {
+        return false;
+    }
+    let rest = &source[range.1..];
+    let mut depth = 0usize;
+    let mut chars = rest.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => {
+                if depth == 0 {
+                    return false;
+                }
+                depth -= 1;
+            }
+            '\'' | '"' | '`' => {
+                while let Some((_, inner)) = chars.next() {
+                    if inner == '\\' {
+                        chars.next();
+                    } else if inner == c {
+                        break;
+                    }
+                }
+            }
+            '/' if rest[index + 1..].starts_with('/') => {
+                while chars.next_if(|(_, inner)| *inner != '\n').is_some() {}
+            }
+            '/' if rest[index + 1..].starts_with('*') => {
+                let end = rest[index + 2..]
+                    .find("*/")
+                    .map_or(rest.len(), |end| index + end + 4);
+                while chars.next_if(|(inner, _)| *inner < end).is_some() {}
+            }
+            ',' if depth == 0 => return true,
+            ';' if depth == 0 => return false,
+            '\n' if depth == 0 => {
+                // An operator at either side of the break carries the statement on.
+                const OPERATORS: &[char] = &[
+                    '=', '+', '-', '*', '/', '%', '&', '|', '^', '<', '>', '?', ':',
+                ];
+                let continued = rest[..index].trim_end().ends_with(OPERATORS)
+                    || rest[index..].trim_start().starts_with(|next: char| {
+                        next == ',' || next == '.' || OPERATORS.contains(&next)
+                    });
+                if !continued {
+                    return false;
+                }
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Strips the `export ` keyword of every safe fix, re-locating each declaration in the
+/// current file contents and editing from the end of the file so earlier offsets stay valid.
+pub(crate) fn apply_export_fixes(root: &Path, fixes: &mut [ExportFix]) -> Result<()> {
+    let mut by_file: BTreeMap<String, Vec<&mut ExportFix>> = BTreeMap::new();
+    for fix in fixes.iter_mut().filter(|fix| fix.safe) {
+        by_file.entry(fix.file.clone()).or_default().push(fix);
+    }
+
+    for (file, fixes) in by_file {
+        let path = root.join(&file);
+        let mut source = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read source file: {}", shown_path(&path)))?;
+        let mut edits: Vec<((usize, usize), &mut ExportFix)> = fixes
+            .into_iter()
+            .filter_map(|fix| {
+                let keyword = locate_export(&source, &fix.export)?.keyword?;
+                Some((keyword, fix))
+            })
+            .collect();
+        edits.sort_by_key(|((start, _), _)| std::cmp::Reverse(*start));
+        for ((start, end), fix) in edits {
+            source.replace_range(start..end, "");
+            fix.applied = true;
+        }
+        fs::write(&path, source)
+            .with_context(|| format!("Failed to write source file: {}", shown_path(&path)))?;
+    }
+    Ok(())
+}
@@ -0,0 +1,110 @@
+use super::*;
+use crate::config_aliases::{eval_path_expr, find_config_files, has_js_config_extension};
+
+/// `path.resolve(...)`, `path.join(...)`, and `require.resolve(...)` calls with their
+/// arguments, which is how `gatsby-node.*` names page templates:
+/// `component: path.resolve('./src/templates/post.tsx')`.
+static PATH_CALL_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"\b(require\.resolve|(?:path\.)?(?:resolve|join))\s*\(([^()]*(?:\([^()]*\)[^()]*)*)\)"#,
+    )
+    .unwrap()
+});
+
+/// Files Gatsby loads by name from a site or a local plugin, without extension.
+const GATSBY_API_FILES: &[&str] = &[
+    "gatsby-config",
+    "gatsby-node",
+    "gatsby-browser",
+    "gatsby-ssr",
+];
+
+/// Whether `root` holds a Gatsby site, going by its `gatsby-config.*` or `gatsby-node.*`.
+fn is_gatsby_site(root: &Path) -> bool {
+    !find_config_files(root, &[""], |name| {
+        (name.starts_with("gatsby-config.") || name.starts_with("gatsby-node."))
+            && has_js_config_extension(name)
+    })
+    .is_empty()
+}
+
+/// Entries of a Gatsby site in `root`: `gatsby-config.*`, `gatsby-node.*`, `gatsby-browser.*`,
+/// and `gatsby-ssr.*` of the site and of each local plugin in `plugins/<name>/`, plus the
+/// serverless functions under `src/api/`. Page templates are reached from `gatsby-node.*`
+/// through [`gatsby_template_imports`].
+pub(crate) fn gatsby_entries(
+    root: &Path,
+    files: &HashSet<PathBuf>,
+    resolver: &Resolver,
+) -> Result<Vec<PathBuf>> {
+    if !is_gatsby_site(root) {
+        return Ok(Vec::new());
+    }
+    let mut dirs = vec![root.to_path_buf()];
+    if let Ok(plugins) = fs::read_dir(root.join("plugins")) {
+        let mut plugin_dirs: Vec<PathBuf> = plugins
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|path| path.is_dir())
+            .collect();
+        plugin_dirs.sort();
+        dirs.extend(plugin_dirs);
+    }
+    let mut out = Vec::new();
+    for dir in &dirs {
+        for name in GATSBY_API_FILES {
+            out.extend(resolver.resolve_path(&dir.join(name))?);
+        }
+    }
+    let api_dir = root.join("src").join("api");
+    let mut functions: Vec<PathBuf> = files
+        .iter()
+        .filter(|file| file.starts_with(&api_dir) && !is_test_like_file(file))
+        .cloned()
+        .collect();
+    functions.sort();
+    out.extend(functions);
+    for path in &out {
+        debug!(entry = %relative_display(root, path), "gatsby entry");
+    }
+    Ok(out)
+}
+
+/// For a `gatsby-node.*` file, an import of every source file its path calls name, such as the
+/// page templates given to `createPage`. Gatsby reads a template's default export, `query`,
+/// and `Head`, so each import uses the whole module. Only files that exist count, so paths to
+/// folders or generated output add nothing.
+pub(crate) fn gatsby_template_imports(file: &Path, source: &str) -> Vec<ImportRecord> {
+    let is_gatsby_node = file.file_stem().is_some_and(|stem| stem == "gatsby-node");
+    let Some(dir) = file.parent().filter(|_| is_gatsby_node) else {
+        return Vec::new();
+    };
+    let source = strip_comments(source);
+    let mut out = Vec::new();
+    for caps in PATH_CALL_RE.captures_iter(&source) {
+        let call = &caps[0];
+        let expr = if caps[1].starts_with("require") {
+            caps[2].to_string()
+        } else {
+            call.to_string()
+        };
+        let Some(target) = eval_path_expr(&expr, dir, dir, false) else {
+            continue;
+        };
+        let target = normalize_path(target);
+        if !has_source_extension(&target) || !target.is_file() {
+            continue;
+        }
+        let Ok(rel) = target.strip_prefix(dir) else {
+            continue;
+        };
+        let offset = caps.get(0).map_or(0, |m| m.start());
+        out.push(ImportRecord {
+            specifier: format!("./{}", rel.display()).replace('\\', "/"),
+            line: line_col(&source, offset).0,
+            uses_namespace: true,
+            ..Default::default()
+        });
+    }
+    out
+}
@@ -0,0 +1,154 @@
+use super::*;
+
+/// Bumped whenever the saved layout changes; older artifacts are rejected instead of being
+/// half-read.
+const GRAPH_FORMAT_VERSION: u32 = 1;
+
+/// Resolved module graph of one analysis run, as written by `haadi graph --save`. Paths are
+/// relative to `root`. The report is stored alongside so a loaded graph renders without
+/// rescanning the project.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ModuleGraph {
+    pub version: u32,
+    pub root: String,
+    pub entries: Vec<String>,
+    pub modules: Vec<GraphModule>,
+    pub report: Report,
+}
+
+/// A parsed file (source or `.d.ts`) and its outgoing imports.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphModule {
+    pub file: String,
+    pub reachable: bool,
+    pub exports: Vec<String>,
+    pub has_default_export: bool,
+    pub imports: Vec<GraphEdge>,
+}
+
+/// One import statement. `targets` holds every local file the specifier resolves to (several
+/// for platform variants), empty for packages and unresolved imports.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphEdge {
+    pub specifier: String,
+    pub targets: Vec<String>,
+}
+
+impl ModuleGraph {
+    /// Shortest import chain from an entry to `file` (both relative to the root), e.g.
+    /// `["src/index.ts", "src/app.ts", "src/util.ts"]`. `None` when no entry reaches it.
+    pub fn import_chain(&self, file: &str) -> Option<Vec<String>> {
+        let edges: HashMap<&str, Vec<&str>> = self
+            .modules
+            .iter()
+            .map(|module| {
+                let targets = module
+                    .imports
+                    .iter()
+                    .flat_map(|edge| edge.targets.iter().map(String::as_str))
+                    .collect();
+                (module.file.as_str(), targets)
+            })
+            .collect();
+
+        let mut parent: HashMap<&str, Option<&str>> = HashMap::new();
+        let mut queue: VecDeque<&str> = VecDeque::new();
+        for entry in &self.entries {
+            if parent.insert(entry.as_str(), None).is_none() {
+                queue.push_back(entry.as_str());
+            }
+        }
+        while let Some(current) = queue.pop_front() {
+            if current == file {
+                let mut chain = vec![current.to_string()];
+                let mut cursor = current;
+                while let Some(Some(prev)) = parent.get(cursor) {
+                    chain.push(prev.to_string());
+                    cursor = prev;
+                }
+                chain.reverse();
+                return Some(chain);
+            }
+            for next in edges.get(current).into_iter().flatten() {
+                if !parent.contains_key(next) {
+                    parent.insert(next, Some(current));
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+}
+
+pub(crate) fn build_module_graph(
+    root: &Path,
+    entries: &[PathBuf],
+    modules: &HashMap<PathBuf, ModuleInfo>,
+    reachable: &HashSet<PathBuf>,
+    resolver: &Resolver,
+    report: &Report,
+) -> Result<ModuleGraph> {
+    let mut graph_modules = Vec::new();
+    for (file, module) in modules {
+        let mut imports = Vec::new();
+        for import in &module.imports {
+            let mut targets: Vec<String> = resolver
+                .resolve_specifier_all(file, &import.specifier)?
+                .iter()
+                .map(|target| relative_display(root, target))
+                .collect();
+            targets.sort();
+            imports.push(GraphEdge {
+                specifier: import.specifier.clone(),
+                targets,
+            });
+        }
+        let mut exports: Vec<String> = module.exports.iter().cloned().collect();
+        exports.sort();
+        graph_modules.push(GraphModule {
+            file: relative_display(root, file),
+            reachable: reachable.contains(file),
+            exports,
+            has_default_export: module.has_default_export,
+            imports,
+        });
+    }
+    graph_modules.sort_by(|a, b| a.file.cmp(&b.file));
+
+    Ok(ModuleGraph {
+        version: GRAPH_FORMAT_VERSION,
+        root: root.display().to_string(),
+        entries: entries
+            .iter()
+            .map(|entry| relative_display(root, entry))
+            .collect(),
+        modules: graph_modules,
+        report: report.clone(),
+    })
+}
+
+/// Writes `graph` to `path`, with the root hidden under `--redact-paths`. A redacted graph
+/// reads its files relative to the current directory when loaded.
+pub(crate) fn save_graph(path: &Path, graph: &ModuleGraph) -> Result<()> {
+    let mut graph = graph.clone();
+    redact_report(&mut graph.report);
+    graph.root = graph.report.root.clone();
+    let raw = serde_json::to_vec(&graph)?;
+    fs::write(path, raw).with_context(|| format!("Failed to write graph: {}", shown_path(path)))
+}
+
+pub(crate) fn load_graph(path: &Path) -> Result<ModuleGraph> {
+    let raw =
+        fs::read(path).with_context(|| format!("Failed to read graph: {}", shown_path(path)))?;
+    let graph: ModuleGraph = serde_json::from_slice(&raw)
+        .with_context(|| format!("Failed to parse graph: {}", shown_path(path)))?;
+    if graph.version != GRAPH_FORMAT_VERSION {
+        anyhow::bail!(
+            "Graph {} has format version {}, expected {}; save it again with this haadi version",
+            shown_path(path),
+            graph.version,
+            GRAPH_FORMAT_VERSION
+        );
+    }
+    Ok(graph)
+}
@@ -0,0 +1,168 @@
+use super::*;
+
+/// Computes, for every reachable file, how many other local files and bytes it pulls in
+/// transitively. Sorted heaviest first.
+///
+/// Import cycles are condensed first: every file of a cycle reaches the same files, so each
+/// strongly connected component gets one closure, built from its successors' closures. That
+/// keeps large monorepos from paying a graph walk per file.
+pub(crate) fn compute_import_costs(
+    root: &Path,
+    reachable: &HashSet<PathBuf>,
+    modules: &HashMap<PathBuf, ModuleInfo>,
+    resolver: &Resolver,
+) -> Result<Vec<ImportCost>> {
+    let mut files: Vec<&PathBuf> = reachable.iter().collect();
+    files.sort();
+    let index: HashMap<&PathBuf, usize> = files
+        .iter()
+        .enumerate()
+        .map(|(idx, file)| (*file, idx))
+        .collect();
+    let mut graph: Vec<Vec<usize>> = Vec::with_capacity(files.len());
+    let mut sizes: Vec<u64> = Vec::with_capacity(files.len());
+    for file in &files {
+        let mut deps: Vec<usize> = resolver
+            .linked_files(file)
+            .iter()
+            .filter_map(|dep| index.get(dep).copied())
+            .collect();
+        if let Some(module) = modules.get(*file) {
+            for import in &module.imports {
+                for dep in resolver.resolve_specifier_all(file, &import.specifier)? {
+                    deps.extend(index.get(&dep).copied());
+                }
+            }
+        }
+        let own = index[file];
+        deps.retain(|dep| *dep != own);
+        deps.sort_unstable();
+        deps.dedup();
+        graph.push(deps);
+        sizes.push(fs::metadata(file).map(|m| m.len()).unwrap_or(0));
+    }
+
+    let (components, component_of) = strongly_connected_components(&graph);
+    let words = files.len().div_ceil(64);
+    // Files each component reaches outside itself, as a bitset over file indexes. Components
+    // come sinks first, so every successor's closure is complete when it is needed.
+    let mut below: Vec<Vec<u64>> = Vec::with_capacity(components.len());
+    // `below` of a component plus its own files, what its importers inherit.
+    let mut full: Vec<Vec<u64>> = Vec::with_capacity(components.len());
+    for (component, members) in components.iter().enumerate() {
+        let mut bits = vec![0u64; words];
+        for &file in members {
+            for &dep in &graph[file] {
+                let target = component_of[dep];
+                if target != component {
+                    for (word, dep_word) in bits.iter_mut().zip(&full[target]) {
+                        *word |= dep_word;
+                    }
+                }
+            }
+        }
+        let mut with_members = bits.clone();
+        for &file in members {
+            with_members[file / 64] |= 1 << (file % 64);
+        }
+        below.push(bits);
+        full.push(with_members);
+    }
+
+    let mut costs = Vec::new();
+    for (file, path) in files.iter().enumerate() {
+        let component = component_of[file];
+        let members = &components[component];
+        let mut transitive_files = 0;
+        let mut transitive_bytes = 0;
+        for (word_idx, word) in below[component].iter().enumerate() {
+            let mut word = *word;
+            while word != 0 {
+                let bit = word.trailing_zeros() as usize;
+                transitive_files += 1;
+                transitive_bytes += sizes[word_idx * 64 + bit];
+                word &= word - 1;
+            }
+        }
+        // The other files of an import cycle are reached too.
+        for &other in members.iter().filter(|other| **other != file) {
+            transitive_files += 1;
+            transitive_bytes += sizes[other];
+        }
+        costs.push(ImportCost {
+            file: relative_display(root, path),
+            direct_imports: graph[file].len(),
+            transitive_files,
+            transitive_bytes,
+        });
+    }
+
+    costs.sort_by(|a, b| {
+        b.transitive_files
+            .cmp(&a.transitive_files)
+            .then(b.transitive_bytes.cmp(&a.transitive_bytes))
+            .then(a.file.cmp(&b.file))
+    });
+    Ok(costs)
+}
+
+/// Tarjan's algorithm, without recursion so deep import chains cannot overflow the stack.
+/// Returns the components in reverse topological order (a component comes after everything
+/// it imports) and the component of each node.
+fn strongly_connected_components(graph: &[Vec<usize>]) -> (Vec<Vec<usize>>, Vec<usize>) {
+    const UNVISITED: usize = usize::MAX;
+    let mut order = vec![UNVISITED; graph.len()];
+    let mut low = vec![0; graph.len()];
+    let mut on_stack = vec![false; graph.len()];
+    let mut stack = Vec::new();
+    let mut component_of = vec![0; graph.len()];
+    let mut components = Vec::new();
+    let mut next_order = 0;
+
+    for start in 0..graph.len() {
+        if order[start] != UNVISITED {
+            continue;
+        }
+        // Each frame is a node and how many of its edges have been followed.
+        let mut frames = vec![(start, 0usize)];
+        order[start] = next_order;
+        low[start] = next_order;
+        next_order += 1;
+        stack.push(start);
+        on_stack[start] = true;
+        while let Some(frame) = frames.last_mut() {
+            let (node, edge) = *frame;
+            if let Some(&next) = graph[node].get(edge) {
+                frame.1 += 1;
+                if order[next] == UNVISITED {
+                    order[next] = next_order;
+                    low[next] = next_order;
+                    next_order += 1;
+                    stack.push(next);
+                    on_stack[next] = true;
+                    frames.push((next, 0));
+                } else if on_stack[next] {
+                    low[node] = low[node].min(order[next]);
+                }
+                continue;
+            }
+            frames.pop();
+            if let Some(&(parent, _)) = frames.last() {
+                low[parent] = low[parent].min(low[node]);
+            }
+            if low[node] == order[node] {
+                let mut members = Vec::new();
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component_of[member] = components.len();
+                    members.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                components.push(members);
+            }
+        }
+    }
+    (components, component_of)
+}
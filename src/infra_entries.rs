@@ -0,0 +1,196 @@
+use super::*;
+
+/// Mapping rules used when neither `--dist-mappings` nor the project config sets any.
+pub(crate) const DEFAULT_DIST_MAPPINGS: &[&str] = &["dist=src", "build=src", "out=src"];
+
+/// A `from=to` rule: a reference through a `<from>` folder also tries the `<to>` folder, so a
+/// deploy file pointing at `dist/server.js` keeps `src/server.ts` alive. Both sides may span
+/// several folders (`dist/server=src`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct DistMapping {
+    from: String,
+    to: String,
+}
+
+impl DistMapping {
+    /// Replaces the first `from` folder in `path`, at any depth: `packages/api/dist/main.js`
+    /// becomes `packages/api/src/main.js`.
+    fn apply(&self, path: &str) -> Option<String> {
+        let prefix = format!("{}/", self.from);
+        if let Some(rest) = path.strip_prefix(&prefix) {
+            return Some(format!("{}/{rest}", self.to));
+        }
+        let (before, rest) = path.split_once(&format!("/{prefix}"))?;
+        Some(format!("{before}/{}/{rest}", self.to))
+    }
+}
+
+pub(crate) fn parse_dist_mappings(rules: &[String]) -> Result<Vec<DistMapping>> {
+    let rules: Vec<&str> = if rules.is_empty() {
+        DEFAULT_DIST_MAPPINGS.to_vec()
+    } else {
+        rules.iter().map(String::as_str).collect()
+    };
+    rules
+        .into_iter()
+        .map(|rule| {
+            let trim = |dir: &str| {
+                dir.trim()
+                    .trim_start_matches("./")
+                    .trim_matches('/')
+                    .to_string()
+            };
+            match rule.split_once('=') {
+                Some((from, to)) if !trim(from).is_empty() && !trim(to).is_empty() => {
+                    Ok(DistMapping {
+                        from: trim(from),
+                        to: trim(to),
+                    })
+                }
+                _ => anyhow::bail!(
+                    "Invalid dist mapping '{rule}': expected <from>=<to>, e.g. dist=src"
+                ),
+            }
+        })
+        .collect()
+}
+
+/// Source files that deploy and process-manager files start: PM2 ecosystem files, systemd
+/// units, Kubernetes manifests, Compose files, Dockerfiles, and Procfiles under `root`. Their
+/// script paths usually point at build output (`node dist/server.js`), so each reference is
+/// tried as written and then through the dist mapping rules, relative to the referencing
+/// file's folder and each parent up to the root. Absolute paths (`/srv/app/dist/server.js`)
+/// are matched by their trailing components.
+pub(crate) fn infra_entries(
+    root: &Path,
+    resolver: &Resolver,
+    mappings: &[DistMapping],
+) -> Result<Vec<PathBuf>> {
+    let mut infra_files: Vec<&PathBuf> = resolver
+        .path_index
+        .files()
+        .filter(|path| path.starts_with(root) && is_infra_file(path))
+        .collect();
+    infra_files.sort();
+
+    let mut out = Vec::new();
+    for infra_file in infra_files {
+        let raw = fs::read_to_string(infra_file).unwrap_or_default();
+        let text = if has_source_extension(infra_file) {
+            strip_comments(&raw)
+        } else {
+            raw.lines()
+                .filter(|line| !line.trim_start().starts_with('#'))
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        // Any YAML file may be a Kubernetes manifest; other YAML (CI, lockfiles) is skipped.
+        if is_yaml(infra_file) && !is_named_deploy_yaml(infra_file) && !is_k8s_manifest(&text) {
+            continue;
+        }
+        for reference in script_file_references(&text) {
+            if reference.contains("node_modules/") {
+                continue;
+            }
+            if let Some(path) =
+                resolve_infra_reference(root, infra_file, &reference, resolver, mappings)?
+            {
+                debug!(
+                    file = %relative_display(root, infra_file),
+                    %reference,
+                    entry = %relative_display(root, &path),
+                    "infra entry"
+                );
+                out.push(path);
+            }
+        }
+    }
+    Ok(out)
+}
+
+/// Resolves a script path named in `infra_file`, relative to its folder and each parent up to
+/// `root`, as written and then through the dist mapping rules.
+pub(crate) fn resolve_infra_reference(
+    root: &Path,
+    infra_file: &Path,
+    reference: &str,
+    resolver: &Resolver,
+    mappings: &[DistMapping],
+) -> Result<Option<PathBuf>> {
+    let components: Vec<&str> = reference.split('/').filter(|c| !c.is_empty()).collect();
+    let suffixes: Vec<String> = if reference.starts_with('/') {
+        (0..components.len())
+            .map(|skip| components[skip..].join("/"))
+            .collect()
+    } else {
+        vec![reference.to_string()]
+    };
+
+    for suffix in &suffixes {
+        let mut candidates = vec![suffix.clone()];
+        candidates.extend(mappings.iter().filter_map(|mapping| mapping.apply(suffix)));
+        for base in infra_file
+            .ancestors()
+            .skip(1)
+            .take_while(|dir| dir.starts_with(root))
+        {
+            for candidate in &candidates {
+                let path = base.join(candidate);
+                if let Some(resolved) = resolver.resolve_path(&path)? {
+                    return Ok(Some(resolved));
+                }
+                // Build output is `.js`; the source may be `.ts`, so retry without the
+                // extension and let resolution pick it.
+                let is_output = matches!(
+                    path.extension().and_then(|ext| ext.to_str()),
+                    Some("js" | "cjs" | "mjs")
+                );
+                if is_output
+                    && let Some(resolved) = resolver.resolve_path(&path.with_extension(""))?
+                {
+                    return Ok(Some(resolved));
+                }
+            }
+        }
+    }
+    Ok(None)
+}
+
+fn is_infra_file(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    name.starts_with("ecosystem.config.")
+        || name == "ecosystem.json"
+        || name == "pm2.json"
+        || name.ends_with(".service")
+        || name == "Procfile"
+        || name == "Dockerfile"
+        || name.starts_with("Dockerfile.")
+        || name.ends_with(".dockerfile")
+        || is_yaml(path)
+}
+
+fn is_yaml(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext == "yml" || ext == "yaml")
+}
+
+/// Compose files and PM2 process files in YAML.
+fn is_named_deploy_yaml(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|name| {
+            name.starts_with("docker-compose")
+                || name.starts_with("compose.")
+                || name.starts_with("ecosystem.")
+                || name.starts_with("pm2.")
+        })
+}
+
+/// Kubernetes objects (Deployments, Jobs, CronJobs, ...) carry both `apiVersion` and `kind`.
+fn is_k8s_manifest(text: &str) -> bool {
+    text.lines().any(|line| line.starts_with("apiVersion:"))
+        && text.lines().any(|line| line.starts_with("kind:"))
+}
@@ -0,0 +1,109 @@
+use super::*;
+#[cfg(feature = "tui")]
+use crossterm::cursor::Show;
+#[cfg(feature = "tui")]
+use crossterm::execute;
+#[cfg(feature = "tui")]
+use crossterm::terminal::{
+    EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+static INTERRUPTED: AtomicBool = AtomicBool::new(false);
+/// Live [`Interruptible`] scopes; Ctrl-C outside all of them exits right away.
+static SCOPES: AtomicUsize = AtomicUsize::new(0);
+/// Set while the TUI owns the terminal (raw mode plus the alternate screen).
+#[cfg(feature = "tui")]
+static TERMINAL_TAKEN: AtomicBool = AtomicBool::new(false);
+
+/// Installs the CLI's Ctrl-C (SIGINT) handler. Inside an [`Interruptible`] scope the first
+/// Ctrl-C only raises a flag: analysis stops at its next checkpoint, an in-flight trash batch
+/// rolls back, and the TUI restores the terminal before exiting. A second Ctrl-C, or one
+/// outside any scope, restores the terminal and exits with status 130.
+pub(crate) fn install_interrupt_handler() {
+    #[cfg(not(target_family = "wasm"))]
+    let _ = ctrlc::set_handler(|| {
+        if SCOPES.load(Ordering::SeqCst) > 0 && !INTERRUPTED.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        // `process::exit` flushes stdout unless another thread holds it (the servers do).
+        restore_terminal();
+        eprintln!("Interrupted.");
+        std::process::exit(130);
+    });
+}
+
+/// True once Ctrl-C was pressed inside an [`Interruptible`] scope.
+pub(crate) fn interrupted() -> bool {
+    INTERRUPTED.load(Ordering::SeqCst)
+}
+
+/// Checkpoint for long-running work: fails with "Interrupted" after Ctrl-C.
+pub(crate) fn check_interrupted() -> Result<()> {
+    if interrupted() {
+        anyhow::bail!("Interrupted");
+    }
+    Ok(())
+}
+
+/// Marks work that polls [`interrupted`] and can stop cleanly on Ctrl-C.
+pub(crate) struct Interruptible;
+
+impl Interruptible {
+    pub(crate) fn enter() -> Self {
+        SCOPES.fetch_add(1, Ordering::SeqCst);
+        Self
+    }
+}
+
+impl Drop for Interruptible {
+    fn drop(&mut self) {
+        SCOPES.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+/// Raw mode and the alternate screen for the TUI, undone on drop so an error or panic never
+/// leaves the shell unusable.
+#[cfg(feature = "tui")]
+pub(crate) struct TerminalGuard;
+
+#[cfg(feature = "tui")]
+impl TerminalGuard {
+    pub(crate) fn enter() -> Result<Self> {
+        take_terminal()?;
+        Ok(Self)
+    }
+
+    /// Hands the terminal back for `run` (an external editor, say) and takes it again after.
+    pub(crate) fn suspend<T>(run: impl FnOnce() -> T) -> Result<T> {
+        restore_terminal();
+        let out = run();
+        take_terminal()?;
+        Ok(out)
+    }
+}
+
+#[cfg(feature = "tui")]
+fn take_terminal() -> Result<()> {
+    enable_raw_mode()?;
+    TERMINAL_TAKEN.store(true, Ordering::SeqCst);
+    execute!(std::io::stdout(), EnterAlternateScreen)?;
+    Ok(())
+}
+
+#[cfg(feature = "tui")]
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+/// Leaves raw mode and the alternate screen if the TUI took them; a no-op otherwise, so piped
+/// output never receives terminal escapes.
+fn restore_terminal() {
+    #[cfg(feature = "tui")]
+    if TERMINAL_TAKEN.swap(false, Ordering::SeqCst) {
+        let _ = disable_raw_mode();
+        let _ = execute!(std::io::stdout(), LeaveAlternateScreen, Show);
+    }
+}
@@ -0,0 +1,117 @@
+use super::*;
+
+/// One `keepDependencies` entry: a bare name, or a name with a reason.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum KeepEntry {
+    Name(String),
+    Detailed {
+        name: String,
+        #[serde(default)]
+        reason: Option<String>,
+    },
+}
+
+/// Drops the dependencies kept on purpose from `unused` and returns every keep. Keeps come
+/// from `haadi.keepDependencies` in the `package.json` in `dir`, and with
+/// `include_project_config` from `keepDependencies` (`keep-dependencies` in TOML) in
+/// `haadi.json` / `haadi.toml` there too. An entry is a name or `{ "name", "reason" }`; in the
+/// config files a comment on the entry's line, or the line above, also gives the reason.
+pub(crate) fn apply_kept_dependencies(
+    root: &Path,
+    dir: &Path,
+    include_project_config: bool,
+    unused: &mut Vec<String>,
+) -> Result<Vec<KeptDependency>> {
+    let mut keeps = Vec::new();
+    let package_json = dir.join("package.json");
+    if let Some(manifest) = read_root_package_json(dir)?
+        && let Some(entries) = manifest
+            .get("haadi")
+            .and_then(|v| v.get("keepDependencies"))
+    {
+        keeps.extend(parse_entries(entries, &package_json, None)?);
+    }
+    if include_project_config {
+        let json_path = dir.join("haadi.json");
+        let toml_path = dir.join("haadi.toml");
+        if let Ok(raw) = fs::read_to_string(&json_path) {
+            let value: serde_json::Value = serde_json::from_str(&sanitize_jsonc(&raw))
+                .with_context(|| format!("Failed to parse config: {}", shown_path(&json_path)))?;
+            if let Some(entries) = value.get("keepDependencies") {
+                keeps.extend(parse_entries(entries, &json_path, Some((&raw, "//")))?);
+            }
+        } else if let Ok(raw) = fs::read_to_string(&toml_path) {
+            let value: toml::Value = toml::from_str(&raw)
+                .with_context(|| format!("Failed to parse config: {}", shown_path(&toml_path)))?;
+            let entries = value
+                .get("keep-dependencies")
+                .or_else(|| value.get("keepDependencies"));
+            if let Some(entries) = entries {
+                let entries = serde_json::to_value(entries)?;
+                keeps.extend(parse_entries(&entries, &toml_path, Some((&raw, "#")))?);
+            }
+        }
+    }
+
+    let mut kept: Vec<KeptDependency> = Vec::new();
+    for (name, reason, source) in keeps {
+        if kept.iter().any(|keep| keep.name == name) {
+            continue;
+        }
+        let suppressed = unused.contains(&name);
+        unused.retain(|dep| *dep != name);
+        kept.push(KeptDependency {
+            name,
+            reason,
+            source: relative_display(root, &source),
+            suppressed,
+        });
+    }
+    Ok(kept)
+}
+
+/// `(name, reason, source)` of each entry. `comments` is the raw file and its line-comment
+/// marker, for reasons written as comments.
+fn parse_entries(
+    entries: &serde_json::Value,
+    source: &Path,
+    comments: Option<(&str, &str)>,
+) -> Result<Vec<(String, Option<String>, PathBuf)>> {
+    let entries: Vec<KeepEntry> = serde_json::from_value(entries.clone()).with_context(|| {
+        format!(
+            "Failed to parse keepDependencies in {}: expected names or {{ name, reason }} objects",
+            shown_path(source)
+        )
+    })?;
+    Ok(entries
+        .into_iter()
+        .map(|entry| {
+            let (name, reason) = match entry {
+                KeepEntry::Name(name) => (name, None),
+                KeepEntry::Detailed { name, reason } => (name, reason),
+            };
+            let reason = reason
+                .filter(|reason| !reason.trim().is_empty())
+                .or_else(|| comments.and_then(|(raw, marker)| comment_reason(raw, marker, &name)));
+            (name, reason, source.to_path_buf())
+        })
+        .collect())
+}
+
+/// The comment after `"name"` on its line, else a comment line right above it.
+fn comment_reason(raw: &str, marker: &str, name: &str) -> Option<String> {
+    let quoted = format!("\"{name}\"");
+    let lines: Vec<&str> = raw.lines().collect();
+    let idx = lines.iter().position(|line| line.contains(&quoted))?;
+    let line = lines[idx];
+    let after = &line[line.find(&quoted)? + quoted.len()..];
+    let trailing = after
+        .find(marker)
+        .map(|pos| after[pos + marker.len()..].trim().to_string());
+    let above = idx
+        .checked_sub(1)
+        .and_then(|prev| lines[prev].trim().strip_prefix(marker))
+        .map(|text| text.trim().to_string());
+    trailing.or(above).filter(|reason| !reason.is_empty())
+}
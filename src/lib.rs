@@ -1,28 +1,151 @@
 use anyhow::{Context, Result};
-use clap::Parser;
+use clap::builder::BoolishValueParser;
+use clap::{Args, CommandFactory, FromArgMatches, Parser, Subcommand, ValueEnum};
 use once_cell::sync::Lazy;
 use regex::Regex;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::fs;
 use std::path::{Path, PathBuf};
-
+use tracing::{debug, info, trace, warn};
+
+mod angular_entries;
+mod asset_age;
+mod asset_licenses;
+mod astro_entries;
+mod broken_imports;
+mod bundler_entries;
+mod cascade;
+mod component_usage;
+mod config;
+mod config_aliases;
+mod config_refs;
+mod dependency_usage;
+mod e2e_entries;
 mod entries;
+mod entry_reachability;
+mod env;
+mod events;
+mod findings;
+mod fix_exports;
+mod gatsby_entries;
+mod graph;
+mod import_cost;
+mod infra_entries;
+mod interrupt;
+mod keep_deps;
+mod logging;
+mod lsp;
+mod mcp;
+mod misplaced_deps;
+mod missing_deps;
+mod nuxt_entries;
+#[cfg_attr(not(feature = "tui"), allow(dead_code))]
 mod output;
 mod parser;
+mod pipeline;
+mod progress;
+mod quickfix;
+mod root_guard;
+mod routes;
+mod sample;
 mod scanner;
+mod script_bins;
+mod serve;
+mod snapshot;
+mod stats;
+mod sveltekit_entries;
+mod system_trash;
+mod timing;
 mod tokens;
-
-use entries::discover_entries;
-use output::{print_human_report, print_tui_report, relative_display};
-use parser::{parse_module, strip_comments};
-use scanner::{collect_asset_files, collect_source_files, collect_used_assets};
+mod unused_scripts;
+#[cfg(feature = "watch")]
+mod watch;
+mod workspaces;
+
+use angular_entries::{angular_entries, angular_styles};
+use asset_age::{parse_age, recently_added_assets};
+use asset_licenses::audit_asset_licenses;
+use astro_entries::{astro_entries, astro_used_assets};
+use broken_imports::describe_broken_imports;
+use bundler_entries::{vite_entries, webpack_entries};
+use cascade::cascade_waves;
+use component_usage::{ComponentUsage, is_component_export, trace_component_usage};
+use config::{apply_global_config, global_cache_dir, load_project_config, merge_list, show_config};
+use config_aliases::{
+    apply_babel_module_resolver, apply_cra_override_aliases, apply_jest_module_name_mapper,
+    apply_vite_aliases, apply_webpack_aliases,
+};
+use config_refs::config_referenced_packages;
+use dependency_usage::dependency_usage;
+use e2e_entries::{e2e_entries, is_e2e_fixture};
+use entries::{
+    EntryScope, add_entry, collect_strings, component_imports, discover_entries,
+    files_with_extension, is_story_file, package_export_entries, script_file_references,
+};
+use entry_reachability::compute_entry_reachability;
+use env::analyze_env_vars;
+pub use events::{Confidence, FindingEvent, FindingKind, Severity};
+pub use findings::finding_id;
+use findings::{collect_findings, compare_findings};
+use fix_exports::{apply_export_fixes, plan_export_fixes};
+use gatsby_entries::{gatsby_entries, gatsby_template_imports};
+pub use graph::{GraphEdge, GraphModule, ModuleGraph};
+use graph::{build_module_graph, load_graph, save_graph};
+use import_cost::compute_import_costs;
+use infra_entries::{
+    DEFAULT_DIST_MAPPINGS, DistMapping, infra_entries, parse_dist_mappings, resolve_infra_reference,
+};
+#[cfg(feature = "tui")]
+use interrupt::TerminalGuard;
+use interrupt::{Interruptible, check_interrupted, install_interrupt_handler, interrupted};
+use keep_deps::apply_kept_dependencies;
+use logging::init_logging;
+use misplaced_deps::find_misplaced_dependencies;
+use missing_deps::find_missing_dependencies;
+use nuxt_entries::{apply_nuxt_aliases, nuxt_entries};
+use output::{
+    TrashOptions, print_dependency_why, print_human_report, print_tui_report, redact_json,
+    redact_paths_under, redact_report, relative_display, restore_from_trash, shown_path,
+    trash_report_files,
+};
+use parser::{
+    ParseCache, ident_tokens, line_col, parse_destructured_names, parse_source, strip_comments,
+};
+pub use pipeline::Stage;
+use pipeline::{PipelineOutcome, run_pipeline};
+use progress::Progress;
+use quickfix::print_quickfix_report;
+use root_guard::{DEFAULT_MAX_FILES, check_root_after_scan, check_root_before_scan};
+use routes::group_by_route;
+use sample::{in_sample, parse_sample, sample_report};
+use scanner::{
+    PathIndex, collect_asset_files, collect_declaration_files, collect_source_files,
+    collect_used_assets, filter_files_by_tsconfig, find_assets_duplicated_in_dependencies,
+    glob_path_pattern_to_regex, normalize_path,
+};
+use script_bins::script_binary_packages;
+use serve::serve_report;
+pub use snapshot::{Snapshot, analyze_snapshot};
+use stats::record_run_stats;
+use sveltekit_entries::{apply_sveltekit_aliases, sveltekit_entries};
+use system_trash::{move_to_system_trash, put_back_from_system_trash, system_trash_for};
+use timing::PhaseTimer;
 use tokens::{
-    build_file_token_cache, count_tokens_in_scope, export_appears_in_other_project_files,
+    count_export_name_files, export_appears_in_other_project_files,
     export_appears_in_other_reachable_files,
 };
+use unused_scripts::find_unused_scripts;
+#[cfg(feature = "watch")]
+use watch::watch;
+use workspaces::{
+    WorkspacePackage, build_package_reports, discover_workspace_entries,
+    discover_workspace_packages, match_workspace_package, resolve_workspace_subpath,
+};
 
 const JS_TS_EXTENSIONS: &[&str] = &["js", "jsx", "ts", "tsx", "mjs", "cjs"];
+const DEFAULT_PLATFORM_SUFFIXES: &[&str] = &["ios", "android", "native", "web"];
 const ASSET_EXTENSIONS: &[&str] = &[
     "png", "jpg", "jpeg", "gif", "webp", "avif", "svg", "ico", "bmp", "tiff", "mp4", "webm", "mp3",
     "wav", "ogg", "woff", "woff2", "ttf", "otf", "eot", "pdf", "txt", "css", "scss", "sass",
@@ -45,46 +168,102 @@ const NEXT_APP_ROUTE_FILES: &[&str] = &[
     "head",
 ];
 
-static IMPORT_FROM_RE: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r#"(?ms)^\s*import\s+(.+?)\s+from\s+['\"]([^'\"]+)['\"]"#).unwrap()
-});
+/// An ECMAScript IdentifierName: `$`, `_`, or a Unicode ID_Start letter, then any of those,
+/// ID_Continue characters (digits, marks, connector punctuation), ZWNJ, or ZWJ. A macro so
+/// that `concat!` can splice it into the statement patterns.
+macro_rules! ident_pattern {
+    () => {
+        r"[\p{XID_Start}_$][\p{XID_Continue}$\x{200C}\x{200D}]*"
+    };
+}
+
+// Import and export statements, from their keyword on. The `_RE` forms find them at any line
+// start; the anchored `_AT_RE` forms match at one offset, for the parser's single-pass scan.
+const IMPORT_FROM_PATTERN: &str = r#"import\s+((?s:.+?))\s+from\s+['\"]([^'\"]+)['\"]"#;
+const IMPORT_SIDE_EFFECT_PATTERN: &str = r#"import\s+['\"]([^'\"]+)['\"]"#;
+const EXPORT_DECL_PATTERN: &str = concat!(
+    r"export\s+(?:const|let|var|function|class|interface|type|enum)\s+(",
+    ident_pattern!(),
+    ")"
+);
+const EXPORT_LIST_PATTERN: &str =
+    r#"export\s+(?:type\s+)?\{\s*([^}]+)\s*\}(?:\s*from\s*['\"]([^'\"]+)['\"])?"#;
+const EXPORT_DEFAULT_PATTERN: &str = r#"export\s+default\b"#;
+const EXPORT_ALL_PATTERN: &str = concat!(
+    r"export\s+(?:type\s+)?\*\s*(?:as\s+(",
+    ident_pattern!(),
+    r#")\s*)?from\s+['\"]([^'\"]+)['\"]"#
+);
+
+fn line_start_regex(pattern: &str) -> Regex {
+    Regex::new(&format!(r"(?m)^\s*{pattern}")).unwrap()
+}
+
+fn anchored_regex(pattern: &str) -> Regex {
+    Regex::new(&format!(r"\A(?:{pattern})")).unwrap()
+}
+
+static IMPORT_FROM_RE: Lazy<Regex> = Lazy::new(|| line_start_regex(IMPORT_FROM_PATTERN));
+static IMPORT_FROM_AT_RE: Lazy<Regex> = Lazy::new(|| anchored_regex(IMPORT_FROM_PATTERN));
 static IMPORT_SIDE_EFFECT_RE: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"(?m)^\s*import\s+['\"]([^'\"]+)['\"]"#).unwrap());
-static EXPORT_DECL_RE: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(r#"(?m)^\s*export\s+(?:const|let|var|function|class|interface|type|enum)\s+([A-Za-z_$][\w$]*)"#)
-        .unwrap()
-});
-static EXPORT_LIST_RE: Lazy<Regex> = Lazy::new(|| {
-    Regex::new(
-        r#"(?ms)^\s*export\s+(?:type\s+)?\{\s*([^}]+)\s*\}(?:\s*from\s*['\"]([^'\"]+)['\"])?"#,
-    )
-    .unwrap()
-});
-static EXPORT_DEFAULT_RE: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"(?m)^\s*export\s+default\b"#).unwrap());
-static EXPORT_ALL_RE: Lazy<Regex> =
-    Lazy::new(|| {
-        Regex::new(
-            r#"(?ms)^\s*export\s+(?:type\s+)?\*\s*(?:as\s+[A-Za-z_$][\w$]*\s*)?from\s+['\"]([^'\"]+)['\"]"#,
-        )
-        .unwrap()
-    });
+    Lazy::new(|| line_start_regex(IMPORT_SIDE_EFFECT_PATTERN));
+static IMPORT_SIDE_EFFECT_AT_RE: Lazy<Regex> =
+    Lazy::new(|| anchored_regex(IMPORT_SIDE_EFFECT_PATTERN));
+static EXPORT_DECL_RE: Lazy<Regex> = Lazy::new(|| line_start_regex(EXPORT_DECL_PATTERN));
+static EXPORT_DECL_AT_RE: Lazy<Regex> = Lazy::new(|| anchored_regex(EXPORT_DECL_PATTERN));
+static EXPORT_LIST_RE: Lazy<Regex> = Lazy::new(|| line_start_regex(EXPORT_LIST_PATTERN));
+static EXPORT_LIST_AT_RE: Lazy<Regex> = Lazy::new(|| anchored_regex(EXPORT_LIST_PATTERN));
+static EXPORT_DEFAULT_RE: Lazy<Regex> = Lazy::new(|| line_start_regex(EXPORT_DEFAULT_PATTERN));
+static EXPORT_DEFAULT_AT_RE: Lazy<Regex> = Lazy::new(|| anchored_regex(EXPORT_DEFAULT_PATTERN));
+static EXPORT_ALL_RE: Lazy<Regex> = Lazy::new(|| line_start_regex(EXPORT_ALL_PATTERN));
+static EXPORT_ALL_AT_RE: Lazy<Regex> = Lazy::new(|| anchored_regex(EXPORT_ALL_PATTERN));
 static REQUIRE_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r#"(?m)(?:^|\s|=)require\(\s*['\"]([^'\"]+)['\"]\s*\)"#).unwrap());
 static DESTRUCTURE_REQUIRE_RE: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r#"(?m)\{\s*([^}]+)\s*\}\s*=\s*require\(\s*['\"]([^'\"]+)['\"]\s*\)"#).unwrap()
 });
+static JSX_ELEMENT_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"(?:^|[^\w$.)\]])<([A-Z][\w$]*)"#).unwrap());
 static DYN_IMPORT_RE: Lazy<Regex> =
     Lazy::new(|| Regex::new(r#"import\(\s*['\"]([^'\"]+)['\"]\s*\)"#).unwrap());
+static ENV_READ_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(concat!(
+        r"(?:process\.env|import\.meta\.env)(?:\.(",
+        ident_pattern!(),
+        r#")|\[\s*['"]([^'"]+)['"]\s*\])|\{([^}]*)\}\s*=\s*(?:process\.env|import\.meta\.env)\b"#,
+    ))
+    .unwrap()
+});
+static ALIAS_OBJECT_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\balias['"]?\s*:\s*"#).unwrap());
+static CRA_OVERRIDE_ALIAS_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?:\balias['"]?\s*[:=]|\b(?:addWebpackAlias|aliasDangerous|alias)\s*\()\s*"#)
+        .unwrap()
+});
+static DECLARE_MODULE_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\bdeclare\s+module\s+['"]([^'"]+)['"]"#).unwrap());
+static BABEL_MODULE_RESOLVER_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"['"](?:babel-plugin-)?module-resolver['"]"#).unwrap());
+static ROOT_OPTION_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\broot['"]?\s*:\s*"#).unwrap());
+static SCRIPT_FILE_REF_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?:^|[\s'"=(])((?:\.{1,2}/)?[\w@.\-/]+\.(?:js|jsx|ts|tsx|mjs|cjs|mts|cts))\b"#)
+        .unwrap()
+});
+static MODULE_NAME_MAPPER_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\bmoduleNameMapper['"]?\s*:\s*"#).unwrap());
 static TRAILING_COMMA_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r#",\s*([}\]])"#).unwrap());
-static IDENT_TOKEN_RE: Lazy<Regex> =
-    Lazy::new(|| Regex::new(r#"[A-Za-z_$][A-Za-z0-9_$]*"#).unwrap());
+static IDENT_TOKEN_RE: Lazy<Regex> = Lazy::new(|| Regex::new(ident_pattern!()).unwrap());
 static STRING_LITERAL_RE: Lazy<Regex> = Lazy::new(|| {
     Regex::new(
         r#"(?s)(?:'([^'\\]*(?:\\.[^'\\]*)*)'|"([^"\\]*(?:\\.[^"\\]*)*)"|`([^`\\]*(?:\\.[^`\\]*)*)`)"#,
     )
     .unwrap()
 });
+static COMMENTED_STATEMENT_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(
+        r#"^(?:import\s+(?:[\w$*{},\s]+\s+from\s+)?['"]|export\s+(?:default|const|let|var|function|class|interface|type|enum|async|abstract|declare)\b|export\s*[{*])"#,
+    )
+    .unwrap()
+});
 static IMPORT_META_GLOB_RE: Lazy<Regex> = Lazy::new(|| {
     Regex::new(
         r#"import\.meta\.(?:glob|globEager)\s*\(\s*(?:'([^'\\]*(?:\\.[^'\\]*)*)'|"([^"\\]*(?:\\.[^"\\]*)*)"|`([^`\\]*(?:\\.[^`\\]*)*)`)"#,
@@ -92,65 +271,540 @@ static IMPORT_META_GLOB_RE: Lazy<Regex> = Lazy::new(|| {
     .unwrap()
 });
 
-#[derive(Parser, Debug)]
-#[command(name = "haadi")]
-#[command(about = "Find high-confidence unused files, dependencies, and exports in JS/TS projects")]
-struct Cli {
+/// What to analyze and which optional checks to run. These are the analysis flags of the
+/// command line; [`analyze`] takes them directly when haadi is used as a library.
+#[derive(Args, Debug, Clone)]
+pub struct AnalysisOptions {
     /// Project root
-    #[arg(long, default_value = ".")]
-    root: PathBuf,
+    #[arg(global = true, long, default_value = ".", env = "HAADI_ROOT")]
+    pub root: PathBuf,
 
     /// Entry files (can be used multiple times)
-    #[arg(long = "entry")]
-    entries: Vec<String>,
+    #[arg(
+        global = true,
+        long = "entry",
+        value_delimiter = ',',
+        env = "HAADI_ENTRY"
+    )]
+    pub entries: Vec<String>,
 
     /// Include dev/peer/optional dependencies in unused dependency checks
-    #[arg(long)]
-    include_non_prod_deps: bool,
+    #[arg(global = true, long, value_parser = BoolishValueParser::new(), env = "HAADI_INCLUDE_NON_PROD_DEPS")]
+    pub include_non_prod_deps: bool,
 
     /// Emit low-confidence findings too (may increase false positives)
-    #[arg(long)]
-    include_low_confidence: bool,
+    #[arg(global = true, long, value_parser = BoolishValueParser::new(), env = "HAADI_INCLUDE_LOW_CONFIDENCE")]
+    pub include_low_confidence: bool,
 
     /// Limit asset analysis to these roots (repeatable or comma-separated), e.g. --asset-roots src/assets,public
-    #[arg(long = "asset-roots", value_delimiter = ',')]
-    asset_roots: Vec<String>,
+    #[arg(
+        global = true,
+        long = "asset-roots",
+        value_delimiter = ',',
+        env = "HAADI_ASSET_ROOTS"
+    )]
+    pub asset_roots: Vec<String>,
+
+    /// Platform suffixes tried during resolution, in priority order (repeatable or comma-separated), e.g. --platforms ios,android,native,web
+    #[arg(
+        global = true,
+        long = "platforms",
+        value_delimiter = ',',
+        env = "HAADI_PLATFORMS"
+    )]
+    pub platforms: Vec<String>,
+
+    /// Extensions tried during resolution, in priority order (repeatable or comma-separated), e.g. --resolve-extensions ts,tsx,dev.ts,js
+    #[arg(
+        global = true,
+        long = "resolve-extensions",
+        value_delimiter = ',',
+        env = "HAADI_RESOLVE_EXTENSIONS"
+    )]
+    pub resolve_extensions: Vec<String>,
+
+    /// Build-output to source folder rules for paths that deploy files (PM2, systemd, Kubernetes, Dockerfile) run (repeatable or comma-separated; default dist=src,build=src,out=src), e.g. --dist-mappings dist/server=src
+    #[arg(
+        global = true,
+        long = "dist-mappings",
+        value_delimiter = ',',
+        env = "HAADI_DIST_MAPPINGS"
+    )]
+    pub dist_mappings: Vec<String>,
+
+    /// Extra Storybook story globs, relative to the root (repeatable or comma-separated); `*.stories.*` and `*.story.*` files are always stories, e.g. --stories "src/**/*.examples.tsx"
+    #[arg(
+        global = true,
+        long = "stories",
+        value_delimiter = ',',
+        env = "HAADI_STORIES"
+    )]
+    pub stories: Vec<String>,
+
+    /// Don't report assets added more recently than this (e.g. 14d, 2w, 36h) as unused; uses git add dates, or file mtimes outside git
+    #[arg(global = true, long, value_parser = parse_age, env = "HAADI_IGNORE_NEWER_THAN")]
+    pub ignore_newer_than: Option<std::time::Duration>,
+
+    /// Report only a stable sample of findings (e.g. 10%), picked by a hash of each file or name, to roll out enforcement gradually
+    #[arg(global = true, long, value_name = "PERCENT", value_parser = parse_sample, env = "HAADI_SAMPLE")]
+    pub sample: Option<f64>,
+
+    /// Report local assets that are byte-identical to files shipped by declared dependencies
+    #[arg(global = true, long, value_parser = BoolishValueParser::new(), env = "HAADI_FIND_DUPLICATE_ASSETS")]
+    pub find_duplicate_assets: bool,
+
+    /// Monorepo mode: discover entries in every workspace package and group findings by package
+    #[arg(global = true, long, value_parser = BoolishValueParser::new(), env = "HAADI_WORKSPACES")]
+    pub workspaces: bool,
+
+    /// Report .env variables that are never read, and env reads that no .env file declares
+    #[arg(global = true, long, value_parser = BoolishValueParser::new(), env = "HAADI_CHECK_ENV")]
+    pub check_env: bool,
+
+    /// Report @types packages whose runtime package is neither declared nor imported
+    #[arg(global = true, long, value_parser = BoolishValueParser::new(), env = "HAADI_ORPHANED_TYPES")]
+    pub orphaned_types: bool,
+
+    /// Report blocks of commented-out code that contain import or export statements
+    #[arg(global = true, long, value_parser = BoolishValueParser::new(), env = "HAADI_COMMENTED_CODE")]
+    pub commented_code: bool,
+
+    /// Report package.json scripts that no other script, CI or deploy file, git hook, or convention runs
+    #[arg(global = true, long, value_parser = BoolishValueParser::new(), env = "HAADI_UNUSED_SCRIPTS")]
+    pub unused_scripts: bool,
+
+    /// Match import paths to files ignoring letter case (auto-detected on case-insensitive filesystems)
+    #[arg(global = true, long, value_parser = BoolishValueParser::new(), env = "HAADI_CASE_INSENSITIVE_FS")]
+    pub case_insensitive_fs: bool,
+
+    /// Descend into symlinked directories (each real directory is scanned once, so link cycles are safe)
+    #[arg(global = true, long, value_parser = BoolishValueParser::new(), env = "HAADI_FOLLOW_SYMLINKS")]
+    pub follow_symlinks: bool,
+
+    /// List, for each reachable file, the entries that reach it (dev-only files are marked)
+    #[arg(global = true, long, value_parser = BoolishValueParser::new(), env = "HAADI_PER_ENTRY")]
+    pub per_entry: bool,
+
+    /// Match assets with sidecar license files; list unused assets that carry attribution and used assets that have none
+    #[arg(global = true, long, value_parser = BoolishValueParser::new(), env = "HAADI_AUDIT_ASSET_LICENSES")]
+    pub audit_asset_licenses: bool,
+
+    /// Compute how many files and bytes each reachable file pulls in transitively
+    #[arg(global = true, long, value_parser = BoolishValueParser::new(), env = "HAADI_IMPORT_COST")]
+    pub import_cost: bool,
+
+    /// Only report on files matched by the root tsconfig.json `files`/`include`/`exclude`
+    #[arg(global = true, long, value_parser = BoolishValueParser::new(), env = "HAADI_TSCONFIG_SCOPE")]
+    pub tsconfig_scope: bool,
+
+    /// Apply fixes to the source: `exports` removes the `export` keyword from unused exported declarations whose name appears in no other file
+    #[arg(global = true, long, value_enum, env = "HAADI_FIX")]
+    pub fix: Option<FixKind>,
+
+    /// Append run duration, parse cache hit rate, and finding counts to .haadi/stats.json (stays local)
+    #[arg(global = true, long, value_parser = BoolishValueParser::new(), env = "HAADI_STATS")]
+    pub stats: bool,
+
+    /// Record wall time per analysis phase (scan, parse, resolve, reachability, assets, exports) in the report summary and print it
+    #[arg(global = true, long, value_parser = BoolishValueParser::new(), env = "HAADI_TIMING")]
+    pub timing: bool,
+
+    /// Worker threads for parsing (0 or unset: one per core, or RAYON_NUM_THREADS)
+    #[arg(global = true, long, value_name = "N", env = "HAADI_THREADS")]
+    pub threads: Option<usize>,
+
+    /// Stop with an error once the file scan passes this many files, a guard against a --root that points at a home directory or `/` (0: no limit)
+    #[arg(global = true, long, value_name = "N", default_value_t = DEFAULT_MAX_FILES, env = "HAADI_MAX_FILES")]
+    pub max_files: usize,
+
+    /// Analyze a root that looks like a home directory, the filesystem root, or a folder of many projects
+    #[arg(global = true, long, value_parser = BoolishValueParser::new(), env = "HAADI_FORCE")]
+    pub force: bool,
+
+    /// Re-analyze as if the unused files and assets were deleted, repeating until nothing new turns up, and report each wave of new findings
+    #[arg(global = true, long, value_parser = BoolishValueParser::new(), env = "HAADI_CASCADE")]
+    pub cascade: bool,
+
+    /// Read the stages before this one from the checkpoints `--stop-after` saved in .haadi/checkpoints instead of running them
+    #[arg(
+        global = true,
+        long,
+        value_enum,
+        value_name = "STAGE",
+        env = "HAADI_RESUME_FROM"
+    )]
+    pub resume_from: Option<Stage>,
+
+    /// Save the resolved module graph here after analyzing (`graph --save`)
+    #[arg(skip)]
+    pub save_graph: Option<PathBuf>,
+
+    /// Show a progress bar on stderr while analyzing (set by the CLI for human output on a
+    /// terminal)
+    #[arg(skip)]
+    pub progress: bool,
+}
+
+impl Default for AnalysisOptions {
+    fn default() -> Self {
+        Self {
+            root: PathBuf::from("."),
+            entries: Vec::new(),
+            include_non_prod_deps: false,
+            include_low_confidence: false,
+            asset_roots: Vec::new(),
+            platforms: Vec::new(),
+            resolve_extensions: Vec::new(),
+            dist_mappings: Vec::new(),
+            stories: Vec::new(),
+            ignore_newer_than: None,
+            sample: None,
+            find_duplicate_assets: false,
+            workspaces: false,
+            check_env: false,
+            orphaned_types: false,
+            commented_code: false,
+            unused_scripts: false,
+            case_insensitive_fs: false,
+            follow_symlinks: false,
+            per_entry: false,
+            audit_asset_licenses: false,
+            import_cost: false,
+            tsconfig_scope: false,
+            fix: None,
+            stats: false,
+            timing: false,
+            threads: None,
+            max_files: DEFAULT_MAX_FILES,
+            force: false,
+            cascade: false,
+            resume_from: None,
+            save_graph: None,
+            progress: false,
+        }
+    }
+}
+
+#[derive(Parser, Debug, Clone)]
+#[command(name = "haadi")]
+#[command(about = "Find high-confidence unused files, dependencies, and exports in JS/TS projects")]
+struct Cli {
+    #[command(flatten)]
+    analysis: AnalysisOptions,
+
+    /// With --fix, only report the planned edits and their safety checks
+    #[arg(global = true, long, value_parser = BoolishValueParser::new(), env = "HAADI_DRY_RUN")]
+    dry_run: bool,
+
+    /// Compare findings with a previously saved `--json` report (new, fixed, and renamed findings)
+    #[arg(global = true, long, env = "HAADI_COMPARE")]
+    compare: Option<PathBuf>,
+
+    /// List every finding in human output instead of the first 20 per section
+    #[arg(global = true, long, value_parser = BoolishValueParser::new(), env = "HAADI_VERBOSE_FINDINGS")]
+    verbose_findings: bool,
 
     /// Emit JSON output
-    #[arg(long)]
+    #[arg(global = true, long, value_parser = BoolishValueParser::new(), env = "HAADI_JSON")]
     json: bool,
 
+    /// Log what the analyzer does to stderr: -v for progress, -vv for per-file details, -vvv for everything
+    #[arg(global = true, short, long, action = clap::ArgAction::Count, env = "HAADI_VERBOSE")]
+    verbose: u8,
+
+    /// Write log lines as JSON objects
+    #[arg(global = true, long, value_parser = BoolishValueParser::new(), env = "HAADI_LOG_JSON")]
+    log_json: bool,
+
+    /// Print one `file:line:col` line per finding: `vimgrep` for Vim's quickfix list, `compile` for Emacs compilation-mode
+    #[arg(global = true, long, value_enum, env = "HAADI_FORMAT")]
+    format: Option<OutputFormat>,
+
     /// Render an interactive terminal dashboard (press q to quit)
-    #[arg(long)]
+    #[arg(global = true, long, value_parser = BoolishValueParser::new(), env = "HAADI_TUI")]
     tui: bool,
+
+    /// Serve an interactive HTML report (sortable findings, module graph, search) on localhost
+    #[arg(global = true, long, value_parser = BoolishValueParser::new(), env = "HAADI_SERVE_REPORT")]
+    serve_report: bool,
+
+    /// Port for --serve-report
+    #[arg(global = true, long, default_value_t = 7676, env = "HAADI_PORT")]
+    port: u16,
+
+    /// Re-run the analysis whenever project files change (works with --tui and --json)
+    #[arg(global = true, long, value_parser = BoolishValueParser::new(), env = "HAADI_WATCH")]
+    watch: bool,
+
+    /// When a restored file's original path exists again: skip it (it stays in the trash), overwrite the existing file, or restore alongside as `name.restored.ext`
+    #[arg(global = true, long, value_enum, default_value_t = OnConflict::Skip, env = "HAADI_ON_CONFLICT")]
+    on_conflict: OnConflict,
+
+    /// Where the delete page puts deleted files: repo (.haadi_trash), system (the desktop trash), or none (removed for good). Default: none when the CI environment variable is set, else repo; always repo for mcp
+    #[arg(global = true, long, value_enum, env = "HAADI_TRASH")]
+    trash: Option<TrashMode>,
+
+    /// Before moving files into .haadi_trash, empty the sessions trashed longer ago than this (e.g. 7d, 12h)
+    #[arg(global = true, long, value_name = "AGE", value_parser = parse_age, env = "HAADI_TRASH_MAX_AGE")]
+    trash_max_age: Option<std::time::Duration>,
+
+    /// Show paths relative to the root in reports, logs, and the delete log, so they can be shared without revealing usernames or folder layouts
+    #[arg(global = true, long, value_parser = BoolishValueParser::new(), env = "HAADI_REDACT_PATHS")]
+    redact_paths: bool,
+
+    /// Stop after this analysis stage, print its artifact as JSON, and save a checkpoint of each stage that ran for --resume-from
+    #[arg(
+        global = true,
+        long,
+        value_enum,
+        value_name = "STAGE",
+        env = "HAADI_STOP_AFTER"
+    )]
+    stop_after: Option<Stage>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
 }
 
-#[derive(Debug, Default)]
+/// Flags that cannot be combined with the listed ones. Checked after parsing by
+/// [`Cli::check_conflicts`], since clap's `conflicts_with` also counts `HAADI_TUI=false` and
+/// other false environment values as given.
+const FLAG_CONFLICTS: &[(&str, &[&str])] = &[
+    ("--format", &["--json", "--tui"]),
+    (
+        "--stop-after",
+        &["--tui", "--format", "--serve-report", "--watch"],
+    ),
+    ("--resume-from", &["--watch", "--serve-report"]),
+];
+
+impl Cli {
+    /// Whether `flag` from [`FLAG_CONFLICTS`] is on.
+    fn flag_given(&self, flag: &str) -> bool {
+        match flag {
+            "--format" => self.format.is_some(),
+            "--json" => self.json,
+            "--tui" => self.tui,
+            "--serve-report" => self.serve_report,
+            "--watch" => self.watch,
+            "--stop-after" => self.stop_after.is_some(),
+            "--resume-from" => self.analysis.resume_from.is_some(),
+            _ => unreachable!("unknown flag in FLAG_CONFLICTS: {flag}"),
+        }
+    }
+
+    /// Rejects the [`FLAG_CONFLICTS`] pairs that are both on.
+    fn check_conflicts(&self) -> std::result::Result<(), clap::Error> {
+        for (flag, others) in FLAG_CONFLICTS {
+            if !self.flag_given(flag) {
+                continue;
+            }
+            if let Some(other) = others.iter().find(|other| self.flag_given(other)) {
+                return Err(Cli::command().error(
+                    clap::error::ErrorKind::ArgumentConflict,
+                    format!("the argument '{flag}' cannot be used with '{other}'"),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn trash_options(&self) -> TrashOptions {
+        TrashOptions {
+            mode: TrashMode::resolve(self.trash),
+            max_age: self.trash_max_age,
+        }
+    }
+
+    /// [`Cli::trash_options`] for `haadi mcp`, which trashes into `repo` unless `--trash` (or
+    /// `HAADI_TRASH`, or a config file) picks a mode: agents rely on `trash_restore`, and an MCP
+    /// server started from a shell that has `CI` set should not delete for good.
+    fn mcp_trash_options(&self) -> TrashOptions {
+        TrashOptions {
+            mode: self.trash.unwrap_or(TrashMode::Repo),
+            max_age: self.trash_max_age,
+        }
+    }
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum Command {
+    /// Save the resolved module graph to a file, or render a previously saved one
+    Graph(GraphArgs),
+    /// Run a language server on stdio that publishes findings as editor diagnostics
+    Lsp,
+    /// Run a Model Context Protocol server on stdio exposing the analysis as tools
+    Mcp,
+    /// Restore a trashed file, or every trashed file under a folder, to its original path
+    Restore(RestoreArgs),
+    /// List how each dependency is used, or every import of one with --why
+    Deps(DepsArgs),
+    /// Inspect configuration files and the settings they resolve to
+    Config(ConfigArgs),
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+enum OutputFormat {
+    Vimgrep,
+    Compile,
+}
+
+/// Source edits `--fix` can make.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixKind {
+    Exports,
+}
+
+#[derive(Args, Debug, Clone)]
+#[group(required = true, multiple = false)]
+struct GraphArgs {
+    /// Analyze the project and write its module graph and report to this file as JSON, e.g. graph.json
+    #[arg(long)]
+    save: Option<PathBuf>,
+
+    /// Render the report stored in a saved graph instead of analyzing the project
+    #[arg(long)]
+    load: Option<PathBuf>,
+}
+
+#[derive(Args, Debug, Clone)]
+struct DepsArgs {
+    /// Show every import of this package, with the statement, and whether an entry reaches
+    /// the importing file
+    #[arg(long, value_name = "PACKAGE")]
+    why: Option<String>,
+}
+
+#[derive(Args, Debug, Clone)]
+struct RestoreArgs {
+    /// File or folder path relative to the project root
+    path: String,
+}
+
+#[derive(Args, Debug, Clone)]
+struct ConfigArgs {
+    #[command(subcommand)]
+    action: ConfigAction,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+enum ConfigAction {
+    /// Print the settings each layer sets (command line, project config, user config)
+    Show {
+        /// Print the effective value of every setting and the layer it came from
+        #[arg(long)]
+        resolved: bool,
+    },
+}
+
+/// What restoring from `.haadi_trash` does when the original path is occupied again.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum OnConflict {
+    Skip,
+    Overwrite,
+    Alongside,
+}
+
+impl OnConflict {
+    fn next(self) -> Self {
+        match self {
+            OnConflict::Skip => OnConflict::Overwrite,
+            OnConflict::Overwrite => OnConflict::Alongside,
+            OnConflict::Alongside => OnConflict::Skip,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            OnConflict::Skip => "skip",
+            OnConflict::Overwrite => "overwrite",
+            OnConflict::Alongside => "alongside",
+        }
+    }
+}
+
+/// Where the delete page puts deleted files.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum TrashMode {
+    /// `.haadi_trash` in the project, where undo and restore find them
+    Repo,
+    /// The desktop trash: the freedesktop `Trash` folder, or `~/.Trash` on macOS
+    System,
+    /// Remove them for good
+    None,
+}
+
+impl TrashMode {
+    /// `choice`, else `none` on CI and `repo` elsewhere: CI workspaces are thrown away after
+    /// the run, so a trash there only takes up space. CI services set `CI`.
+    fn resolve(choice: Option<Self>) -> Self {
+        choice.unwrap_or_else(|| {
+            let on_ci = std::env::var("CI")
+                .is_ok_and(|value| !matches!(value.as_str(), "" | "0" | "false"));
+            if on_ci {
+                TrashMode::None
+            } else {
+                TrashMode::Repo
+            }
+        })
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            TrashMode::Repo => "repo",
+            TrashMode::System => "system",
+            TrashMode::None => "none",
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 struct ImportRecord {
     specifier: String,
+    /// 1-based line of the statement or call.
+    line: usize,
     uses_default: bool,
     uses_namespace: bool,
     names: HashSet<String>,
     side_effect_only: bool,
     is_reexport: bool,
+    /// `import type ... from`, which is erased at build time.
+    type_only: bool,
+    /// Local name -> imported name (`default` for a default import) of each binding. For
+    /// re-exports the key is the exported name, and `export * as ns` maps `ns` to `*`.
+    bindings: HashMap<String, String>,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
 struct ModuleInfo {
     imports: Vec<ImportRecord>,
     exports: HashSet<String>,
+    /// Line and column of each export name (`default` for the default export).
+    export_positions: HashMap<String, (usize, usize)>,
     has_default_export: bool,
     has_export_all: bool,
+    /// Root names of capitalized JSX elements (`Button` for `<Button>`, `UI` for `<UI.Button>`).
+    jsx_elements: HashSet<String>,
+    /// Import bindings referenced outside the import statements, JSX elements included.
+    referenced_bindings: HashSet<String>,
+    /// Specifiers imported only inside comments, with their line.
+    commented_imports: Vec<(String, usize)>,
+    /// Commented-out code blocks: first line, last line, and the first import/export in them.
+    commented_code: Vec<(usize, usize, String)>,
 }
 
 #[derive(Debug, Default, Clone)]
 struct ExportUsage {
     all: bool,
+    /// A reachable file re-exports from the module.
+    reexported: bool,
     default_used: bool,
     names: HashSet<String>,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 enum DepKind {
     Prod,
     Dev,
@@ -158,40 +812,468 @@ enum DepKind {
     Optional,
 }
 
-#[derive(Debug, Serialize)]
-struct UnusedExport {
-    file: String,
-    export: String,
-}
-
-#[derive(Debug, Serialize)]
-struct Report {
-    root: String,
-    summary: ReportSummary,
-    entries: Vec<String>,
-    warnings: Vec<String>,
-    unused_files: Vec<String>,
-    used_assets: Vec<String>,
-    unused_assets: Vec<String>,
-    unused_dependencies: Vec<String>,
-    unused_exports: Vec<UnusedExport>,
-}
-
-#[derive(Debug, Serialize)]
-struct ReportSummary {
-    total_source_files: usize,
-    total_asset_files: usize,
-    total_reachable_files: usize,
-    total_entries: usize,
-    unresolved_local_imports: usize,
-    high_confidence_graph: bool,
-    omitted_risky_findings: bool,
-    unused_files_count: usize,
-    used_assets_count: usize,
-    unused_assets_count: usize,
-    asset_usage_coverage_pct: f64,
-    unused_dependencies_count: usize,
-    unused_exports_count: usize,
+/// An exported symbol that no reachable file imports.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnusedExport {
+    pub file: String,
+    pub export: String,
+    /// 1-based position of the export name; 0 in reports written before positions existed.
+    #[serde(default)]
+    pub line: usize,
+    #[serde(default)]
+    pub column: usize,
+}
+
+/// An entry file reached through `package.json` `exports`. `conditions` is the chain of
+/// condition keys leading to the target (e.g. `["node", "import"]`), empty for a plain string.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportEntry {
+    pub file: String,
+    pub subpath: String,
+    pub conditions: Vec<String>,
+}
+
+/// A local source import whose target file does not exist (typo, deleted or moved file).
+/// `suggestion` is the closest existing source file, if any is close enough.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrokenImport {
+    pub file: String,
+    pub specifier: String,
+    pub suggestion: Option<String>,
+}
+
+/// An `import` or `require` that only exists inside a comment. Informational: it is not part
+/// of `findings`, but it explains a package or file that looks used at a glance.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommentedOutImport {
+    pub file: String,
+    /// 1-based line of the specifier.
+    pub line: usize,
+    pub specifier: String,
+}
+
+/// A dependency listed in `keepDependencies`, so it is never reported unused.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KeptDependency {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// The file listing it, relative to the root.
+    pub source: String,
+    /// Whether it would have been reported unused in this run.
+    pub suppressed: bool,
+}
+
+/// Unused files and assets inside one route subtree (`app/marketing`), so a whole route's
+/// dead code can be reviewed at once.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RouteGroup {
+    /// The route directory, relative to the root.
+    pub route: String,
+    pub unused_files: Vec<String>,
+    pub unused_assets: Vec<String>,
+    /// Source files in the subtree, used or not.
+    pub total_files: usize,
+    pub total_assets: usize,
+}
+
+/// A `package.json` script that nothing runs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnusedScript {
+    /// The `package.json` declaring it, relative to the root.
+    pub package_json: String,
+    pub name: String,
+    pub command: String,
+}
+
+/// A block of commented-out code with an `import` or `export` statement in it, left behind
+/// when code was disabled instead of deleted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommentedCode {
+    pub file: String,
+    /// 1-based first and last line of the block.
+    pub start_line: usize,
+    pub end_line: usize,
+    /// The first commented-out `import` / `export` statement, which also keys the finding.
+    pub statement: String,
+}
+
+/// An import of an asset file that does not exist, e.g. `import logo from './logo.png'` after
+/// the image was deleted.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BrokenAssetReference {
+    pub file: String,
+    pub specifier: String,
+}
+
+/// An unused asset covered by license or attribution files (`--audit-asset-licenses`).
+/// Deleting it may also end the obligations those files record.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LicensedAsset {
+    pub file: String,
+    pub license_files: Vec<String>,
+}
+
+/// A project asset whose content is identical to a file inside a declared dependency.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DuplicateAsset {
+    pub file: String,
+    pub package: String,
+    pub package_file: String,
+}
+
+/// A variable declared in a `.env*` file that no source file reads.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnusedEnvVar {
+    pub name: String,
+    pub env_files: Vec<String>,
+}
+
+/// An env variable read from source but declared in no `.env*` file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UndeclaredEnvVar {
+    pub name: String,
+    pub files: Vec<String>,
+}
+
+/// A package imported by reachable code but declared in no `package.json` above the importing
+/// files. It only resolves through hoisting or a parent `node_modules`, and breaks on a clean
+/// install.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MissingDependency {
+    pub name: String,
+    pub files: Vec<String>,
+}
+
+/// How a declared dependency is used: the reachable files importing it and any other use.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DependencyUsage {
+    pub name: String,
+    pub used: bool,
+    /// Reachable files importing the package, relative to the root.
+    pub files: Vec<String>,
+    /// Uses other than imports: `package.json script`, `tool config`, `declare module`, and
+    /// `kept by <file>` for a `keepDependencies` entry.
+    #[serde(default)]
+    pub other_uses: Vec<String>,
+    /// Only for unused dependencies: what the verdict rests on, e.g. files outside the graph
+    /// that still import the package.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+    /// Every import of the package, reachable or not, sorted by file and line.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub imports: Vec<DependencyImport>,
+}
+
+/// One import of a declared dependency, shown by `haadi deps --why`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependencyImport {
+    /// The importing file, relative to the root.
+    pub file: String,
+    /// 1-based line of the import statement or `require` call.
+    pub line: usize,
+    pub specifier: String,
+    /// Whether an entry reaches the importing file. Imports from files no entry reaches do
+    /// not keep the package used, and deleting those files changes nothing.
+    pub reachable: bool,
+}
+
+/// A declared dependency in the wrong section of its `package.json`: a `dependencies` entry
+/// imported only by tests, stories, tooling scripts, or config files.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MisplacedDependency {
+    pub name: String,
+    /// The `package.json` declaring it, relative to the root.
+    pub package_json: String,
+    /// The section it is declared in (`dependencies`).
+    pub declared_in: String,
+    /// The section it belongs in (`devDependencies`).
+    pub move_to: String,
+    /// The dev files importing it.
+    pub files: Vec<String>,
+}
+
+/// A package declared only under `devDependencies` that production code imports at runtime.
+/// A production install (`npm ci --omit=dev`) leaves it out, so the import fails once
+/// deployed.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DevDependencyInProduction {
+    pub name: String,
+    /// The `package.json` declaring it, relative to the root.
+    pub package_json: String,
+    /// The production files importing it.
+    pub files: Vec<String>,
+}
+
+/// Transitive local import footprint of a reachable file (`--import-cost`).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ImportCost {
+    pub file: String,
+    pub direct_imports: usize,
+    pub transitive_files: usize,
+    pub transitive_bytes: u64,
+}
+
+/// Which entries reach a source file (`--per-entry`). `dev_only` is set when all of them are
+/// dev entries (tests, stories, CI or git hook scripts).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EntryReachability {
+    pub file: String,
+    pub entries: Vec<String>,
+    pub dev_only: bool,
+}
+
+/// A planned `--fix exports` edit. `outside_occurrences` counts the other scanned files that
+/// mention the export name; the fix is only `safe` when it is zero and the export is a plain
+/// declaration. `reason` explains refused fixes.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportFix {
+    pub file: String,
+    pub export: String,
+    pub safe: bool,
+    pub outside_occurrences: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    pub applied: bool,
+}
+
+/// One finding with its stable [`finding_id`]. `subject` is the file path, dependency name, or
+/// env variable name; `export` holds the export name for unused exports and the import
+/// specifier for broken asset references.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Finding {
+    pub id: String,
+    pub category: String,
+    pub subject: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub export: Option<String>,
+    /// Hash of the file's contents for file-based findings, used for rename detection.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
+}
+
+/// A finding that moved to a new path between two runs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RenamedFinding {
+    pub from: Finding,
+    pub to: Finding,
+}
+
+/// Result of `--compare`: how the current findings differ from a saved report.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FindingComparison {
+    pub baseline: String,
+    pub added: Vec<Finding>,
+    pub fixed: Vec<Finding>,
+    pub renamed: Vec<RenamedFinding>,
+    pub unchanged_count: usize,
+}
+
+/// Full analysis result, as emitted by `--json`.
+///
+/// Saved reports can be read back with `serde_json::from_str::<Report>(..)`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Report {
+    pub root: String,
+    pub summary: ReportSummary,
+    pub entries: Vec<String>,
+    #[serde(default)]
+    pub export_entries: Vec<ExportEntry>,
+    pub warnings: Vec<String>,
+    pub unused_files: Vec<String>,
+    /// Source files reachable only from tests, `package.json` scripts, or CI / git hook scripts.
+    #[serde(default)]
+    pub tooling_only_files: Vec<String>,
+    /// Source files reachable only from Storybook stories.
+    #[serde(default)]
+    pub storybook_only_files: Vec<String>,
+    pub used_assets: Vec<String>,
+    pub unused_assets: Vec<String>,
+    pub unused_dependencies: Vec<String>,
+    #[serde(default)]
+    pub missing_dependencies: Vec<MissingDependency>,
+    #[serde(default)]
+    pub misplaced_dependencies: Vec<MisplacedDependency>,
+    #[serde(default)]
+    pub dev_dependencies_in_production: Vec<DevDependencyInProduction>,
+    /// Dependencies kept on purpose through `keepDependencies`.
+    #[serde(default)]
+    pub kept_dependencies: Vec<KeptDependency>,
+    /// Every dependency declared in the root `package.json` but `@types/*`, sorted by name.
+    #[serde(default)]
+    pub dependency_usage: Vec<DependencyUsage>,
+    /// `@types/*` packages whose runtime package is neither declared nor imported; empty
+    /// unless `--orphaned-types` is set.
+    #[serde(default)]
+    pub orphaned_types_packages: Vec<String>,
+    pub unused_exports: Vec<UnusedExport>,
+    /// Exports that only Storybook stories import.
+    #[serde(default)]
+    pub storybook_only_exports: Vec<UnusedExport>,
+    /// Unused files and assets grouped by Next.js / React Router route subtree.
+    #[serde(default)]
+    pub route_groups: Vec<RouteGroup>,
+    #[serde(default)]
+    pub duplicate_assets: Vec<DuplicateAsset>,
+    /// Empty unless `--audit-asset-licenses` is set.
+    #[serde(default)]
+    pub unused_licensed_assets: Vec<LicensedAsset>,
+    /// Used assets with no sidecar or directory license; empty unless
+    /// `--audit-asset-licenses` is set.
+    #[serde(default)]
+    pub unlicensed_used_assets: Vec<String>,
+    #[serde(default)]
+    pub packages: Vec<PackageReport>,
+    #[serde(default)]
+    pub unused_env_vars: Vec<UnusedEnvVar>,
+    #[serde(default)]
+    pub undeclared_env_vars: Vec<UndeclaredEnvVar>,
+    #[serde(default)]
+    pub broken_imports: Vec<BrokenImport>,
+    #[serde(default)]
+    pub broken_asset_references: Vec<BrokenAssetReference>,
+    #[serde(default)]
+    pub commented_out_imports: Vec<CommentedOutImport>,
+    /// Empty unless `--commented-code` is set.
+    #[serde(default)]
+    pub commented_code: Vec<CommentedCode>,
+    /// Empty unless `--unused-scripts` is set.
+    #[serde(default)]
+    pub unused_scripts: Vec<UnusedScript>,
+    /// Heaviest importers first; empty unless `--import-cost` is set.
+    #[serde(default)]
+    pub import_costs: Vec<ImportCost>,
+    /// Empty unless `--per-entry` is set.
+    #[serde(default)]
+    pub entry_reachability: Vec<EntryReachability>,
+    /// Empty unless `--fix exports` is set; `applied` stays false with `--dry-run`.
+    #[serde(default)]
+    pub export_fixes: Vec<ExportFix>,
+    /// Every finding above with its stable ID.
+    #[serde(default)]
+    pub findings: Vec<Finding>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comparison: Option<FindingComparison>,
+    /// Findings that appear once earlier waves are deleted; empty unless `--cascade` is set.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cascade_waves: Vec<CascadeWave>,
+}
+
+/// What becomes unused once the unused files and assets of the report and of every earlier
+/// wave are deleted. The report itself is wave 1, so the first wave listed is 2.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CascadeWave {
+    pub wave: usize,
+    pub unused_files: Vec<String>,
+    pub unused_assets: Vec<String>,
+    pub unused_exports: Vec<UnusedExport>,
+    pub unused_dependencies: Vec<String>,
+}
+
+/// Totals and confidence metrics for a [`Report`].
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReportSummary {
+    pub total_source_files: usize,
+    pub total_asset_files: usize,
+    pub total_reachable_files: usize,
+    pub total_entries: usize,
+    pub unresolved_local_imports: usize,
+    pub high_confidence_graph: bool,
+    pub omitted_risky_findings: bool,
+    pub unused_files_count: usize,
+    #[serde(default)]
+    pub tooling_only_files_count: usize,
+    #[serde(default)]
+    pub storybook_only_files_count: usize,
+    pub used_assets_count: usize,
+    pub unused_assets_count: usize,
+    pub asset_usage_coverage_pct: f64,
+    pub unused_dependencies_count: usize,
+    #[serde(default)]
+    pub missing_dependencies_count: usize,
+    #[serde(default)]
+    pub misplaced_dependencies_count: usize,
+    #[serde(default)]
+    pub dev_dependencies_in_production_count: usize,
+    #[serde(default)]
+    pub orphaned_types_packages_count: usize,
+    pub unused_exports_count: usize,
+    #[serde(default)]
+    pub storybook_only_exports_count: usize,
+    #[serde(default)]
+    pub duplicate_assets_count: usize,
+    #[serde(default)]
+    pub unused_licensed_assets_count: usize,
+    #[serde(default)]
+    pub unlicensed_used_assets_count: usize,
+    #[serde(default)]
+    pub packages: Vec<PackageSummary>,
+    #[serde(default)]
+    pub unused_env_vars_count: usize,
+    #[serde(default)]
+    pub undeclared_env_vars_count: usize,
+    #[serde(default)]
+    pub broken_imports_count: usize,
+    #[serde(default)]
+    pub broken_asset_references_count: usize,
+    #[serde(default)]
+    pub commented_out_imports_count: usize,
+    #[serde(default)]
+    pub commented_code_count: usize,
+    #[serde(default)]
+    pub unused_scripts_count: usize,
+    /// The `--sample` percentage; counts and lists only cover the sampled findings.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sample_pct: Option<f64>,
+    /// Per-phase wall times, present with `--timing`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timing: Option<PhaseTimings>,
+}
+
+/// Wall time of each analysis phase in milliseconds (`--timing`). `other_ms` covers config
+/// loading and the optional checks (dependencies, env variables, workspaces, fixes);
+/// `total_ms` is the whole run. `parsed_bytes` is the size of the files parsed fresh (not
+/// reused from the `--watch` cache), for parse throughput.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PhaseTimings {
+    pub scan_ms: f64,
+    pub parse_ms: f64,
+    pub resolve_ms: f64,
+    pub reachability_ms: f64,
+    pub assets_ms: f64,
+    pub exports_ms: f64,
+    pub other_ms: f64,
+    pub total_ms: f64,
+    #[serde(default)]
+    pub parsed_bytes: u64,
+}
+
+/// Per-package totals emitted in `--workspaces` mode.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackageSummary {
+    pub name: String,
+    pub dir: String,
+    pub total_source_files: usize,
+    pub total_reachable_files: usize,
+    pub total_entries: usize,
+    pub unused_files_count: usize,
+    pub unused_assets_count: usize,
+    pub unused_dependencies_count: usize,
+    pub unused_exports_count: usize,
+}
+
+/// Findings for a single workspace package. Dependency findings use the package's own
+/// `package.json`; imports from other packages count toward export usage.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PackageReport {
+    pub name: String,
+    pub dir: String,
+    pub summary: PackageSummary,
+    pub entries: Vec<String>,
+    pub unused_files: Vec<String>,
+    pub unused_assets: Vec<String>,
+    pub unused_dependencies: Vec<String>,
+    pub unused_exports: Vec<UnusedExport>,
 }
 
 #[derive(Debug, Default)]
@@ -200,6 +1282,22 @@ struct Resolver {
     root: PathBuf,
     base_dirs: Vec<PathBuf>,
     alias_rules: Vec<AliasRule>,
+    pattern_alias_rules: Vec<PatternAliasRule>,
+    extensions: Vec<String>,
+    platforms: Vec<String>,
+    linked_files: HashMap<PathBuf, Vec<PathBuf>>,
+    browser_module_shims: HashMap<String, PathBuf>,
+    workspace_packages: Vec<WorkspacePackage>,
+    config_warnings: Vec<String>,
+    /// Lower-cased path -> scanned file, set in case-insensitive mode so `./Button` finds
+    /// `button.tsx` under the same path the scanner recorded.
+    case_index: Option<HashMap<String, PathBuf>>,
+    /// Names from `declare module '...'` in `.d.ts` files, e.g. `vue`, `virtual:icons`, `*.svg`.
+    ambient_modules: Vec<String>,
+    /// Type packages listed in tsconfig `compilerOptions.types`, e.g. `jest` for `@types/jest`.
+    tsconfig_types: HashSet<String>,
+    resolve_cache: RefCell<HashMap<(PathBuf, String), Option<PathBuf>>>,
+    path_index: PathIndex,
 }
 
 #[derive(Debug, Clone)]
@@ -209,293 +1307,626 @@ struct AliasRule {
     base_dir: PathBuf,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+/// Regex-keyed alias (jest `moduleNameMapper`), e.g. `^@components/(.*)$` ->
+/// `<rootDir>/src/components/$1`. Only applied to non-relative specifiers.
+#[derive(Debug, Clone)]
+struct PatternAliasRule {
+    pattern: Regex,
+    replacement: String,
+    base_dir: PathBuf,
+}
+
+impl Resolver {
+    /// A resolver for relative, root-absolute, and `src/`-based imports only. Aliases,
+    /// workspace packages, and other project config are applied by [`build_resolver`].
+    fn new(
+        root: &Path,
+        files: &HashSet<PathBuf>,
+        extensions: &[String],
+        platforms: &[String],
+        case_insensitive: bool,
+        path_index: PathIndex,
+    ) -> Self {
+        Resolver {
+            files: files.clone(),
+            root: root.to_path_buf(),
+            base_dirs: vec![root.to_path_buf(), root.join("src")],
+            alias_rules: Vec::new(),
+            pattern_alias_rules: Vec::new(),
+            extensions: normalize_suffix_list(extensions, JS_TS_EXTENSIONS),
+            platforms: normalize_suffix_list(platforms, DEFAULT_PLATFORM_SUFFIXES),
+            linked_files: HashMap::new(),
+            browser_module_shims: HashMap::new(),
+            workspace_packages: Vec::new(),
+            config_warnings: Vec::new(),
+            case_index: case_insensitive.then(|| {
+                files
+                    .iter()
+                    .map(|file| (case_fold_key(file), file.clone()))
+                    .collect()
+            }),
+            ambient_modules: Vec::new(),
+            tsconfig_types: HashSet::new(),
+            resolve_cache: RefCell::new(HashMap::new()),
+            path_index,
+        }
+    }
+}
+
+impl PatternAliasRule {
+    fn target_path(&self, specifier: &str) -> Option<PathBuf> {
+        let caps = self.pattern.captures(specifier)?;
+        let mut target = String::new();
+        caps.expand(&self.replacement, &mut target);
+        Some(self.base_dir.join(target.trim_start_matches('/')))
+    }
+}
+
+impl AliasRule {
+    fn target_path(&self, wildcard: &str) -> PathBuf {
+        let target = apply_alias_target(&self.target, wildcard);
+        if target.is_empty() {
+            // Joining "" would add a trailing separator and break file targets.
+            self.base_dir.clone()
+        } else {
+            self.base_dir.join(target)
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 struct UnresolvedImport {
     from_file: PathBuf,
     specifier: String,
 }
 
 pub fn run() -> Result<()> {
-    let cli = Cli::parse();
-    let root = fs::canonicalize(&cli.root)
-        .with_context(|| format!("Failed to access root: {}", cli.root.display()))?;
-
-    let files = collect_source_files(&root)?;
-    let all_assets = collect_asset_files(&root)?;
-    let assets = filter_assets_by_roots(&root, &all_assets, &cli.asset_roots);
-    let resolver = build_resolver(&root, &files)?;
-
-    let mut warnings =
-        vec!["Analysis is conservative by default to minimize false positives.".to_string()];
-    if !cli.asset_roots.is_empty() && assets.is_empty() {
-        warnings.push(
-            "No assets matched --asset-roots filter; asset findings may be empty.".to_string(),
+    let matches = Cli::command().get_matches();
+    let mut cli = Cli::from_arg_matches(&matches).unwrap_or_else(|err| err.exit());
+    cli.check_conflicts().unwrap_or_else(|err| err.exit());
+    init_logging(cli.verbose, cli.log_json);
+    // A root that cannot be accessed is still hidden in the error that says so.
+    if cli.redact_paths
+        && let Ok(root) = fs::canonicalize(&cli.analysis.root)
+            .or_else(|_| std::path::absolute(&cli.analysis.root))
+    {
+        redact_paths_under(&root);
+    }
+    apply_global_config(&mut cli, &matches)?;
+    if let Some(Command::Config(ConfigArgs {
+        action: ConfigAction::Show { resolved },
+    })) = &cli.command
+    {
+        return show_config(&cli, &matches, *resolved);
+    }
+    install_interrupt_handler();
+    let result = run_cli(cli);
+    if interrupted() {
+        use std::io::Write;
+        let _ = std::io::stdout().flush();
+        eprintln!("Interrupted.");
+        std::process::exit(130);
+    }
+    result
+}
+
+fn run_cli(mut cli: Cli) -> Result<()> {
+    fill_save_graph(&mut cli);
+    cli.analysis.progress = !cli.json
+        && !cli.tui
+        && cli.format.is_none()
+        && cli.command.is_none()
+        && std::io::IsTerminal::is_terminal(&std::io::stdout());
+    if cli.serve_report {
+        let mut graph = match &cli.command {
+            Some(Command::Graph(GraphArgs {
+                load: Some(path), ..
+            })) => load_graph(path)?,
+            _ => analyze_with_graph(&cli.analysis, &mut ParseCache::default(), true)?
+                .1
+                .context("Failed to build module graph")?,
+        };
+        add_comparison(&mut graph.report, &cli)?;
+        redact_report(&mut graph.report);
+        graph.root = graph.report.root.clone();
+        return serve_report(&graph, cli.port);
+    }
+    if let Some(Command::Graph(GraphArgs {
+        load: Some(path), ..
+    })) = &cli.command
+    {
+        return render_report(load_graph(path)?.report, &cli);
+    }
+    if let Some(Command::Lsp) = &cli.command {
+        return lsp::serve(&cli.analysis);
+    }
+    if let Some(Command::Mcp) = &cli.command {
+        return mcp::serve(&cli.analysis, cli.mcp_trash_options());
+    }
+    if let Some(Command::Restore(args)) = &cli.command {
+        let root = fs::canonicalize(&cli.analysis.root).with_context(|| {
+            format!("Failed to access root: {}", shown_path(&cli.analysis.root))
+        })?;
+        println!(
+            "{}",
+            restore_from_trash(&root, &args.path, cli.on_conflict)?
         );
+        return Ok(());
+    }
+    if let Some(Command::Deps(args)) = &cli.command {
+        let report = analyze_cached(&cli.analysis, &mut ParseCache::default())?;
+        return print_dependency_why(&report, args.why.as_deref(), cli.json);
+    }
+    if let Some(stage) = cli.stop_after {
+        let PipelineOutcome::Stopped(mut artifact) = run_pipeline(
+            &cli.analysis,
+            &mut ParseCache::default(),
+            false,
+            &HashSet::new(),
+            Some(stage),
+        )?
+        else {
+            unreachable!("the pipeline stops after {stage:?}");
+        };
+        redact_json(&mut artifact);
+        println!("{}", serde_json::to_string_pretty(&artifact)?);
+        return Ok(());
+    }
+    if cli.watch {
+        return watch(&cli);
     }
 
-    let mut modules: HashMap<PathBuf, ModuleInfo> = HashMap::new();
-    for file in &files {
-        modules.insert(file.clone(), parse_module(file)?);
+    let mut report = analyze_cached(&cli.analysis, &mut ParseCache::default())?;
+    if !cli.dry_run && !report.export_fixes.is_empty() {
+        apply_export_fixes(Path::new(&report.root), &mut report.export_fixes)?;
     }
+    render_report(report, &cli)
+}
 
-    let entries = discover_entries(&root, &files, &cli.entries)?;
-    if entries.is_empty() {
-        warnings.push(
-            "No entry files discovered. Pass --entry to improve unused file accuracy.".to_string(),
-        );
+/// Runs the analysis configured by command-line flags (without the program name), e.g.
+/// `["--root", "app", "--include-low-confidence"]`, and returns the report, including the
+/// `--compare` diff. `--fix` edits are not applied, and `HAADI_*` environment variables are
+/// ignored; prefer [`analyze`] when the options are built in code.
+pub fn analyze_args<I, T>(args: I) -> Result<Report>
+where
+    I: IntoIterator<Item = T>,
+    T: Into<std::ffi::OsString> + Clone,
+{
+    let matches = Cli::command()
+        .mut_args(|arg| arg.env(None))
+        .try_get_matches_from(
+            std::iter::once(std::ffi::OsString::from("haadi"))
+                .chain(args.into_iter().map(Into::into)),
+        )?;
+    let mut cli = Cli::from_arg_matches(&matches)?;
+    cli.check_conflicts()?;
+    fill_save_graph(&mut cli);
+    let mut report = analyze_cached(&cli.analysis, &mut ParseCache::default())?;
+    add_comparison(&mut report, &cli)?;
+    Ok(report)
+}
+
+/// Carries the `graph --save` path into the analysis options.
+fn fill_save_graph(cli: &mut Cli) {
+    if let Some(Command::Graph(GraphArgs {
+        save: Some(path), ..
+    })) = &cli.command
+    {
+        cli.analysis.save_graph = Some(path.clone());
     }
+}
 
-    let reachable = reachable_files(&entries, &modules, &resolver)?;
+/// Runs the full analysis of `options.root` and returns its report. This is the library entry
+/// point behind the `haadi` command; planned `--fix` edits are listed in
+/// [`Report::export_fixes`] but not applied.
+pub fn analyze(options: &AnalysisOptions) -> Result<Report> {
+    analyze_cached(options, &mut ParseCache::default())
+}
 
-    let unresolved = collect_unresolved_local_imports(&reachable, &modules, &resolver)?;
-    let maybe_used_from_unresolved =
-        infer_potentially_used_files_from_unresolved(&files, &unresolved, &root);
-    let high_confidence_graph = unresolved.is_empty();
-    if !unresolved.is_empty() {
-        warnings.push(format!(
-            "Skipped high-risk findings because {} local/alias imports could not be resolved.",
-            unresolved.len()
-        ));
-        if !maybe_used_from_unresolved.is_empty() {
-            warnings.push(format!(
-                "Suppressed unused-export findings for {} files potentially referenced by unresolved imports.",
-                maybe_used_from_unresolved.len()
-            ));
-        }
+/// [`analyze`], reusing parsed modules from earlier runs (watch mode and the servers).
+fn analyze_cached(options: &AnalysisOptions, parse_cache: &mut ParseCache) -> Result<Report> {
+    Ok(analyze_with_graph(options, parse_cache, false)?.0)
+}
+
+/// [`analyze`], also returning the resolved module graph when `with_graph` is set.
+fn analyze_with_graph(
+    options: &AnalysisOptions,
+    parse_cache: &mut ParseCache,
+    with_graph: bool,
+) -> Result<(Report, Option<ModuleGraph>)> {
+    analyze_excluding(options, parse_cache, with_graph, &HashSet::new())
+}
+
+/// [`analyze_with_graph`] as if the source files and assets in `deleted` did not exist.
+fn analyze_excluding(
+    options: &AnalysisOptions,
+    parse_cache: &mut ParseCache,
+    with_graph: bool,
+    deleted: &HashSet<PathBuf>,
+) -> Result<(Report, Option<ModuleGraph>)> {
+    match run_pipeline(options, parse_cache, with_graph, deleted, None)? {
+        PipelineOutcome::Finished(outcome) => Ok(*outcome),
+        PipelineOutcome::Stopped(_) => unreachable!("no stage to stop after"),
     }
+}
 
-    let used_packages = collect_used_packages(&reachable, &modules, &resolver)?;
-    let declared_deps = collect_declared_dependencies(&root)?;
-    let mut unused_dependencies: Vec<String> = declared_deps
+/// Each package directory (the root and workspace packages), deepest first, with whether its
+/// `package.json` declares `"sideEffects": false`. Only the boolean form counts; a list of
+/// side-effectful globs leaves the package treated as having side effects.
+fn package_side_effects(
+    root: &Path,
+    root_manifest: Option<&serde_json::Value>,
+    resolver: &Resolver,
+) -> Vec<(PathBuf, bool)> {
+    let declares_none =
+        |manifest: &serde_json::Value| manifest.get("sideEffects") == Some(&false.into());
+    let mut dirs: Vec<(PathBuf, bool)> = resolver
+        .workspace_packages
         .iter()
-        .filter(|(name, kind)| {
-            if name.starts_with("@types/") {
-                return false;
-            }
+        .map(|package| (package.dir.clone(), declares_none(&package.manifest)))
+        .collect();
+    if let Some(manifest) = root_manifest {
+        dirs.push((root.to_path_buf(), declares_none(manifest)));
+    }
+    dirs.sort_by_key(|(dir, _)| std::cmp::Reverse(dir.components().count()));
+    for (dir, _) in dirs.iter().filter(|(_, free)| *free) {
+        debug!(package = %shown_path(dir), "sideEffects: false, tracing re-exports");
+    }
+    dirs
+}
 
-            if !cli.include_non_prod_deps {
-                return **kind == DepKind::Prod;
-            }
+/// Whether the package owning `file` (the deepest directory containing it) is free of side
+/// effects.
+fn is_side_effect_free(file: &Path, side_effects: &[(PathBuf, bool)]) -> bool {
+    side_effects
+        .iter()
+        .find(|(dir, _)| file.starts_with(dir))
+        .is_some_and(|(_, free)| *free)
+}
 
-            true
-        })
-        .map(|(name, _)| name)
-        .filter(|name| !used_packages.contains(*name))
-        .cloned()
+/// Exports of reachable, non-entry source files that no reachable file imports. An export
+/// whose name appears as an identifier in another file is kept, since regex parsing cannot see
+/// every use; `skip` lists files that unresolved imports may reach. A reachable re-export
+/// keeps every export of its source, unless the package owning the source declares
+/// `"sideEffects": false` (see [`package_side_effects`]): then only the names importers pull
+/// through the barrel count. Warnings about `export *` and suppressed findings are added to
+/// `warnings`.
+///
+/// With `non_story_reachable`, the second list holds the exports only stories use: unused once
+/// the files only stories reach are taken out, used otherwise. Both lists come from the same
+/// pass; each import and name token is also credited to the non-story view unless its file is
+/// story-only.
+#[allow(clippy::too_many_arguments)]
+fn find_unused_exports(
+    root: &Path,
+    files: &HashSet<PathBuf>,
+    reachable: &HashSet<PathBuf>,
+    entries: &[PathBuf],
+    modules: &HashMap<PathBuf, ModuleInfo>,
+    resolver: &Resolver,
+    skip: &HashSet<PathBuf>,
+    side_effects: &[(PathBuf, bool)],
+    non_story_reachable: Option<&HashSet<PathBuf>>,
+    read_source: impl Fn(&Path) -> String,
+    warnings: &mut Vec<String>,
+) -> Result<(Vec<UnusedExport>, Vec<UnusedExport>)> {
+    let entry_set: HashSet<&PathBuf> = entries.iter().collect();
+    let story_only: HashSet<PathBuf> = non_story_reachable
+        .map(|non_story| reachable.difference(non_story).cloned().collect())
+        .unwrap_or_default();
+    let mut unused_exports = Vec::new();
+    let mut storybook_only_exports = Vec::new();
+    let mut usage: HashMap<PathBuf, ExportUsage> = HashMap::new();
+    let mut non_story_usage: HashMap<PathBuf, ExportUsage> = HashMap::new();
+    let export_names: HashSet<&str> = modules
+        .iter()
+        .filter(|(file, _)| reachable.contains(*file) && files.contains(*file))
+        .flat_map(|(_, module)| module.exports.iter().map(String::as_str))
         .collect();
-    unused_dependencies.sort();
+    let export_name_counts =
+        count_export_name_files(files, reachable, &story_only, &export_names, read_source);
+    let mut suppressed_by_symbol_ref = 0usize;
 
-    let mut unused_files = Vec::new();
-    let mut used_assets = Vec::new();
-    let mut unused_assets = Vec::new();
-    let mut unused_exports = Vec::new();
+    // High-confidence: usage only comes from reachable files.
+    for file in reachable {
+        let Some(module) = modules.get(file) else {
+            continue;
+        };
+        let from_story = story_only.contains(file);
 
-    if high_confidence_graph || cli.include_low_confidence {
-        unused_files = files
-            .difference(&reachable)
-            .filter(|path| {
-                !is_test_like_file(path)
-                    && !is_declaration_file(path)
-                    && !is_common_config_file(path)
-            })
-            .map(|path| relative_display(&root, path))
-            .collect();
-        unused_files.sort();
-        let used_asset_paths = collect_used_assets(&root, &files, &assets)?;
-        used_assets = used_asset_paths
-            .iter()
-            .map(|path| relative_display(&root, path))
-            .collect();
-        used_assets.sort();
-        unused_assets = assets
-            .difference(&used_asset_paths)
-            .filter(|path| !is_public_asset(path))
-            .map(|path| relative_display(&root, path))
-            .collect();
-        unused_assets.sort();
-
-        let entry_set: HashSet<PathBuf> = entries.iter().cloned().collect();
-        let mut usage: HashMap<PathBuf, ExportUsage> = HashMap::new();
-        let token_cache = build_file_token_cache(&files)?;
-        let token_file_counts = count_tokens_in_scope(&reachable, &token_cache);
-        let global_token_file_counts = count_tokens_in_scope(&files, &token_cache);
-        let mut suppressed_by_symbol_ref = 0usize;
-
-        // High-confidence: usage only comes from reachable files.
-        for file in &reachable {
-            let Some(module) = modules.get(file) else {
+        for import in &module.imports {
+            if import.side_effect_only || import.is_reexport {
                 continue;
-            };
+            }
 
-            for import in &module.imports {
-                if import.side_effect_only || import.is_reexport {
-                    continue;
+            let credit = |slot: &mut ExportUsage| {
+                if import.uses_namespace {
+                    slot.all = true;
                 }
-
-                if let Some(resolved) = resolver.resolve_specifier(file, &import.specifier)? {
-                    let slot = usage.entry(resolved).or_default();
-                    if import.uses_namespace {
-                        slot.all = true;
-                    }
-                    if import.uses_default {
-                        slot.default_used = true;
-                    }
-                    slot.names.extend(import.names.iter().cloned());
+                if import.uses_default {
+                    slot.default_used = true;
                 }
+                slot.names.extend(import.names.iter().cloned());
+            };
+            for resolved in resolver.resolve_specifier_all(file, &import.specifier)? {
+                if non_story_reachable.is_some() && !from_story {
+                    credit(non_story_usage.entry(resolved.clone()).or_default());
+                }
+                credit(usage.entry(resolved).or_default());
             }
         }
+    }
 
-        // Conservative re-export handling: any reachable re-export marks source module as used.
-        for file in &reachable {
-            let Some(module) = modules.get(file) else {
+    // Conservative re-export handling: any reachable re-export marks source module as used.
+    for file in reachable {
+        let Some(module) = modules.get(file) else {
+            continue;
+        };
+        let from_story = story_only.contains(file);
+
+        for import in &module.imports {
+            if !import.is_reexport {
                 continue;
-            };
+            }
 
-            for import in &module.imports {
-                if !import.is_reexport {
-                    continue;
+            for resolved in resolver.resolve_specifier_all(file, &import.specifier)? {
+                if non_story_reachable.is_some() && !from_story {
+                    non_story_usage
+                        .entry(resolved.clone())
+                        .or_default()
+                        .reexported = true;
                 }
+                usage.entry(resolved).or_default().reexported = true;
+            }
+        }
+    }
 
-                if let Some(resolved) = resolver.resolve_specifier(file, &import.specifier)? {
-                    let slot = usage.entry(resolved).or_default();
-                    slot.all = true;
+    // Story-only files are reachable, so they leave both name counts.
+    let non_story_files: HashSet<PathBuf> = files.difference(&story_only).cloned().collect();
+    let non_story = match non_story_reachable {
+        Some(non_story_reachable) => {
+            let non_story_entries: Vec<PathBuf> = entries
+                .iter()
+                .filter(|entry| !story_only.contains(*entry))
+                .cloned()
+                .collect();
+            let without_stories = |counts: &HashMap<String, usize>| {
+                counts
+                    .iter()
+                    .map(|(name, count)| {
+                        let in_stories = export_name_counts.excluded.get(name).copied();
+                        (name.clone(), count - in_stories.unwrap_or(0))
+                    })
+                    .collect()
+            };
+            Some(ExportEvidence {
+                files: &non_story_files,
+                reachable: non_story_reachable,
+                usage: non_story_usage,
+                component_usage: trace_component_usage(
+                    non_story_reachable,
+                    &non_story_entries,
+                    modules,
+                    resolver,
+                )?,
+                reachable_counts: without_stories(&export_name_counts.reachable),
+                project_counts: without_stories(&export_name_counts.project),
+            })
+        }
+        None => None,
+    };
+
+    let all_files = ExportEvidence {
+        files,
+        reachable,
+        usage,
+        component_usage: trace_component_usage(reachable, entries, modules, resolver)?,
+        reachable_counts: export_name_counts.reachable,
+        project_counts: export_name_counts.project,
+    };
+
+    for (file, module) in modules {
+        if !reachable.contains(file) || !files.contains(file) {
+            continue;
+        }
+        if skip.contains(file) {
+            continue;
+        }
+        if entry_set.contains(file) || is_test_like_file(file) || is_declaration_file(file) {
+            continue;
+        }
+
+        let unused = all_files.unused_exports(file, module, side_effects);
+        suppressed_by_symbol_ref += unused.suppressed;
+        if let Some(non_story) = &non_story
+            && non_story.reachable.contains(file)
+        {
+            let without_stories = non_story.unused_exports(file, module, side_effects);
+            for name in without_stories.names {
+                if !unused.names.contains(&name) {
+                    storybook_only_exports.push(unused_export(root, file, module, name));
                 }
             }
         }
 
-        for (file, module) in &modules {
-            if !reachable.contains(file) {
-                continue;
-            }
-            if maybe_used_from_unresolved.contains(file) {
-                continue;
-            }
-            if entry_set.contains(file) || is_test_like_file(file) || is_declaration_file(file) {
-                continue;
-            }
+        if module.has_export_all && !unused.all_used {
+            warnings.push(format!(
+                "{} re-exports '*' and may need manual verification.",
+                relative_display(root, file)
+            ));
+        }
+        for name in unused.names {
+            unused_exports.push(unused_export(root, file, module, name));
+        }
+    }
 
-            let used = usage.get(file).cloned().unwrap_or_default();
-
-            if !used.all {
-                for export_name in &module.exports {
-                    if export_appears_in_other_reachable_files(
-                        &token_file_counts,
-                        export_name,
-                        &reachable,
-                        file,
-                    ) {
-                        suppressed_by_symbol_ref += 1;
-                        continue;
-                    }
-                    if export_appears_in_other_project_files(
-                        &global_token_file_counts,
-                        export_name,
-                        &files,
-                        file,
-                    ) {
-                        suppressed_by_symbol_ref += 1;
-                        continue;
-                    }
+    for list in [&mut unused_exports, &mut storybook_only_exports] {
+        list.sort_by(|a, b| a.file.cmp(&b.file).then_with(|| a.export.cmp(&b.export)));
+        list.dedup_by(|a, b| a.file == b.file && a.export == b.export);
+    }
+    if suppressed_by_symbol_ref > 0 {
+        warnings.push(format!(
+            "Suppressed {} unused-export findings because the symbol appears in other reachable files.",
+            suppressed_by_symbol_ref
+        ));
+    }
+
+    Ok((unused_exports, storybook_only_exports))
+}
+
+/// What [`find_unused_exports`] judges exports by, for one set of reachable files.
+struct ExportEvidence<'a> {
+    files: &'a HashSet<PathBuf>,
+    reachable: &'a HashSet<PathBuf>,
+    usage: HashMap<PathBuf, ExportUsage>,
+    component_usage: ComponentUsage,
+    /// Export name -> how many reachable files, and how many of `files`, mention it.
+    reachable_counts: HashMap<String, usize>,
+    project_counts: HashMap<String, usize>,
+}
 
-                    if !used.names.contains(export_name) {
-                        unused_exports.push(UnusedExport {
-                            file: relative_display(&root, file),
-                            export: export_name.clone(),
-                        });
-                    }
-                }
+/// The exports of one module that nothing uses.
+struct UnusedInModule {
+    names: Vec<String>,
+    /// Exports kept only because their name appears in another file.
+    suppressed: usize,
+    /// A namespace import or untraced re-export uses every export.
+    all_used: bool,
+}
 
-                if module.has_default_export && !used.default_used {
-                    unused_exports.push(UnusedExport {
-                        file: relative_display(&root, file),
-                        export: "default".to_string(),
-                    });
-                }
-            }
+impl ExportEvidence<'_> {
+    fn unused_exports(
+        &self,
+        file: &Path,
+        module: &ModuleInfo,
+        side_effects: &[(PathBuf, bool)],
+    ) -> UnusedInModule {
+        let used = self.usage.get(file).cloned().unwrap_or_default();
+        // With `"sideEffects": false` a bundler drops whatever the barrel re-exports but nobody
+        // imports, so re-exported names are traced like components instead.
+        let trace_reexports = used.reexported && is_side_effect_free(file, side_effects);
+        let all_used = used.all || (used.reexported && !trace_reexports);
+        let mut unused = UnusedInModule {
+            names: Vec::new(),
+            suppressed: 0,
+            all_used,
+        };
 
-            if module.has_export_all && !used.all {
-                warnings.push(format!(
-                    "{} re-exports '*' and may need manual verification.",
-                    relative_display(&root, file)
-                ));
+        for export_name in &module.exports {
+            // Components are attributed through the import graph; the other exports of a
+            // namespace-imported or re-exported module all count as used.
+            if is_component_export(file, export_name) {
+                if self.component_usage.is_used(file, export_name) {
+                    continue;
+                }
+            } else if all_used
+                || (trace_reexports && self.component_usage.is_used(file, export_name))
+            {
+                continue;
+            } else if export_appears_in_other_reachable_files(
+                &self.reachable_counts,
+                export_name,
+                self.reachable,
+                file,
+            ) || export_appears_in_other_project_files(
+                &self.project_counts,
+                export_name,
+                self.files,
+                file,
+            ) {
+                unused.suppressed += 1;
+                continue;
+            } else if used.names.contains(export_name) {
+                continue;
             }
+            unused.names.push(export_name.clone());
         }
 
-        unused_exports.sort_by(|a, b| a.file.cmp(&b.file).then_with(|| a.export.cmp(&b.export)));
-        unused_exports.dedup_by(|a, b| a.file == b.file && a.export == b.export);
-        if suppressed_by_symbol_ref > 0 {
-            warnings.push(format!(
-                "Suppressed {} unused-export findings because the symbol appears in other reachable files.",
-                suppressed_by_symbol_ref
-            ));
+        let default_traced = trace_reexports && self.component_usage.is_used(file, "default");
+        if !all_used && module.has_default_export && !used.default_used && !default_traced {
+            unused.names.push("default".to_string());
         }
-    } else {
-        warnings.push(
-            "unused_files and unused_exports omitted (use --include-low-confidence to force)."
-                .to_string(),
-        );
-        warnings.push(
-            "unused_assets omitted because graph confidence is low (use --include-low-confidence to force)."
-                .to_string(),
-        );
+        unused
     }
-    let total_asset_files = assets.len();
-    let unused_assets_count = unused_assets.len();
-    let used_assets_count = total_asset_files.saturating_sub(unused_assets_count);
-
-    let summary = ReportSummary {
-        total_source_files: files.len(),
-        total_asset_files,
-        total_reachable_files: reachable.len(),
-        total_entries: entries.len(),
-        unresolved_local_imports: unresolved.len(),
-        high_confidence_graph,
-        omitted_risky_findings: !(high_confidence_graph || cli.include_low_confidence),
-        unused_files_count: unused_files.len(),
-        used_assets_count,
-        unused_assets_count,
-        asset_usage_coverage_pct: if total_asset_files == 0 {
-            0.0
-        } else {
-            (used_assets_count as f64 * 100.0) / total_asset_files as f64
-        },
-        unused_dependencies_count: unused_dependencies.len(),
-        unused_exports_count: unused_exports.len(),
-    };
+}
 
-    let report = Report {
-        root: root.display().to_string(),
-        summary,
-        entries: entries
-            .iter()
-            .map(|entry| relative_display(&root, entry))
-            .collect(),
-        warnings,
-        unused_files,
-        used_assets,
-        unused_assets,
-        unused_dependencies,
-        unused_exports,
-    };
+fn unused_export(root: &Path, file: &Path, module: &ModuleInfo, export: String) -> UnusedExport {
+    let (line, column) = module
+        .export_positions
+        .get(&export)
+        .copied()
+        .unwrap_or((1, 1));
+    UnusedExport {
+        file: relative_display(root, file),
+        export,
+        line,
+        column,
+    }
+}
+
+#[cfg(not(feature = "watch"))]
+fn watch(_cli: &Cli) -> Result<()> {
+    anyhow::bail!("--watch is not available: haadi was built without the `watch` feature")
+}
+
+/// Adds the `--compare` diff and prints the report in the requested format.
+fn render_report(mut report: Report, cli: &Cli) -> Result<()> {
+    add_comparison(&mut report, cli)?;
 
-    if cli.json {
+    if let Some(format) = cli.format {
+        print_quickfix_report(&report, format);
+    } else if cli.json {
+        redact_report(&mut report);
         println!("{}", serde_json::to_string_pretty(&report)?);
     } else if cli.tui {
-        print_tui_report(&report)?;
+        print_tui_report(&report, None, cli.on_conflict, cli.trash_options())?;
     } else {
-        print_human_report(&report);
+        redact_report(&mut report);
+        print_human_report(&report, cli.verbose_findings);
     }
 
     Ok(())
 }
 
-fn build_resolver(root: &Path, files: &HashSet<PathBuf>) -> Result<Resolver> {
-    let mut resolver = Resolver {
-        files: files.clone(),
-        root: root.to_path_buf(),
-        base_dirs: vec![root.to_path_buf(), root.join("src")],
-        alias_rules: Vec::new(),
-    };
+/// Sets `report.comparison` from the `--compare` baseline, if one was given.
+fn add_comparison(report: &mut Report, cli: &Cli) -> Result<()> {
+    if let Some(baseline) = &cli.compare {
+        let raw = fs::read_to_string(baseline)
+            .with_context(|| format!("Failed to read report: {}", shown_path(baseline)))?;
+        let mut previous: Report = serde_json::from_str(&raw)
+            .with_context(|| format!("Failed to parse report: {}", shown_path(baseline)))?;
+        // Findings outside the current sample are hidden, not fixed.
+        if let Some(percent) = report.summary.sample_pct {
+            previous
+                .findings
+                .retain(|finding| in_sample(&finding.subject, percent));
+        }
+        report.comparison = Some(compare_findings(
+            &baseline.display().to_string(),
+            &previous.findings,
+            &report.findings,
+        ));
+    }
+    Ok(())
+}
+
+fn build_resolver(
+    root: &Path,
+    files: &HashSet<PathBuf>,
+    extensions: &[String],
+    platforms: &[String],
+    case_insensitive: bool,
+    path_index: PathIndex,
+) -> Result<Resolver> {
+    let mut resolver = Resolver::new(
+        root,
+        files,
+        extensions,
+        platforms,
+        case_insensitive,
+        path_index,
+    );
+    resolver.workspace_packages = discover_workspace_packages(root)?;
 
     let mut config_paths = BTreeSet::new();
     for seed_name in [
@@ -511,10 +1942,27 @@ fn build_resolver(root: &Path, files: &HashSet<PathBuf>) -> Result<Resolver> {
     }
 
     for config_path in config_paths {
+        debug!(config = %relative_display(root, &config_path), "reading tsconfig");
         apply_compiler_options_from_config(&config_path, &mut resolver, root)?;
     }
 
+    apply_package_imports(root, &mut resolver)?;
+    apply_browser_field(root, &mut resolver)?;
+    apply_webpack_aliases(root, &mut resolver)?;
+    apply_cra_override_aliases(root, &mut resolver)?;
+    apply_vite_aliases(root, &mut resolver)?;
+    apply_nuxt_aliases(root, &mut resolver)?;
+    apply_sveltekit_aliases(root, &mut resolver)?;
+    apply_jest_module_name_mapper(root, &mut resolver)?;
+    apply_babel_module_resolver(root, &mut resolver)?;
+
     resolver.base_dirs = dedup_paths(resolver.base_dirs);
+    info!(
+        alias_rules = resolver.alias_rules.len(),
+        base_dirs = resolver.base_dirs.len(),
+        workspace_packages = resolver.workspace_packages.len(),
+        "configured resolver"
+    );
 
     Ok(resolver)
 }
@@ -540,10 +1988,10 @@ fn discover_related_tsconfigs(
 
     let config_dir = canonical.parent().unwrap_or(Path::new("."));
 
-    if let Some(extends) = value.get("extends").and_then(|v| v.as_str()) {
-        if let Some(path) = resolve_tsconfig_reference_path(config_dir, extends) {
-            discover_related_tsconfigs(&path, out, visiting)?;
-        }
+    if let Some(extends) = value.get("extends").and_then(|v| v.as_str())
+        && let Some(path) = resolve_tsconfig_reference_path(config_dir, extends)
+    {
+        discover_related_tsconfigs(&path, out, visiting)?;
     }
 
     if let Some(refs) = value.get("references").and_then(|v| v.as_array()) {
@@ -598,7 +2046,10 @@ fn apply_compiler_options_from_config(
     let sanitized = sanitize_jsonc(&raw);
     let value: serde_json::Value = match serde_json::from_str(&sanitized) {
         Ok(v) => v,
-        Err(_) => return Ok(()),
+        Err(err) => {
+            debug!(config = %relative_display(root, config_path), %err, "skipped unparsable tsconfig");
+            return Ok(());
+        }
     };
 
     let config_dir = config_path.parent().unwrap_or(root);
@@ -608,6 +2059,12 @@ fn apply_compiler_options_from_config(
         .cloned()
         .unwrap_or_default();
 
+    if let Some(types) = compiler.get("types").and_then(|v| v.as_array()) {
+        resolver
+            .tsconfig_types
+            .extend(types.iter().filter_map(|v| v.as_str()).map(str::to_string));
+    }
+
     if let Some(base_url) = compiler.get("baseUrl").and_then(|v| v.as_str()) {
         resolver.base_dirs.push(config_dir.join(base_url));
     }
@@ -619,6 +2076,7 @@ fn apply_compiler_options_from_config(
             };
 
             for target in arr.iter().filter_map(|v| v.as_str()) {
+                debug!(alias = %key, %target, "tsconfig path alias");
                 resolver.alias_rules.push(AliasRule {
                     key: key.to_string(),
                     target: target.to_string(),
@@ -631,6 +2089,111 @@ fn apply_compiler_options_from_config(
     Ok(())
 }
 
+fn read_root_package_json(root: &Path) -> Result<Option<serde_json::Value>> {
+    let package_json = root.join("package.json");
+    if !package_json.exists() {
+        return Ok(None);
+    }
+
+    let raw = fs::read_to_string(package_json)?;
+    Ok(serde_json::from_str(&raw).ok())
+}
+
+/// Registers Node subpath imports (`"imports": { "#utils/*": "./src/utils/*.js" }`) as alias
+/// rules. Conditional targets contribute every local path they mention.
+fn apply_package_imports(root: &Path, resolver: &mut Resolver) -> Result<()> {
+    let Some(value) = read_root_package_json(root)? else {
+        return Ok(());
+    };
+
+    let Some(imports) = value.get("imports").and_then(|v| v.as_object()) else {
+        return Ok(());
+    };
+
+    for (key, targets) in imports {
+        if !key.starts_with('#') {
+            continue;
+        }
+
+        let mut raw_targets = Vec::new();
+        collect_strings(targets, &mut raw_targets);
+        for target in raw_targets {
+            resolver.alias_rules.push(AliasRule {
+                key: key.to_string(),
+                target,
+                base_dir: root.to_path_buf(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Connects both sides of `"browser": { "./a.js": "./b.js" }` remappings so either file keeps
+/// the other reachable, and records shims for bare modules (`"fs": "./shims/fs.js"`).
+/// `false` targets (module ignored in the browser) are skipped.
+fn apply_browser_field(root: &Path, resolver: &mut Resolver) -> Result<()> {
+    let Some(value) = read_root_package_json(root)? else {
+        return Ok(());
+    };
+
+    let Some(browser) = value.get("browser").and_then(|v| v.as_object()) else {
+        return Ok(());
+    };
+
+    for (from, to) in browser {
+        let Some(to) = to.as_str() else {
+            continue;
+        };
+        let Some(to_path) = resolver.resolve_path(&root.join(to))? else {
+            continue;
+        };
+
+        if !is_relative_specifier(from) && looks_like_package_specifier(from) {
+            resolver
+                .browser_module_shims
+                .insert(from.to_string(), to_path);
+            continue;
+        }
+
+        let Some(from_path) = resolver.resolve_path(&root.join(from))? else {
+            continue;
+        };
+        if from_path == to_path {
+            continue;
+        }
+
+        resolver
+            .linked_files
+            .entry(from_path.clone())
+            .or_default()
+            .push(to_path.clone());
+        resolver
+            .linked_files
+            .entry(to_path)
+            .or_default()
+            .push(from_path);
+    }
+
+    Ok(())
+}
+
+fn normalize_suffix_list(values: &[String], defaults: &[&str]) -> Vec<String> {
+    let mut out = Vec::new();
+    for value in values {
+        let value = value.trim().trim_start_matches('.').to_string();
+        if !value.is_empty() && !out.contains(&value) {
+            out.push(value);
+        }
+    }
+
+    if out.is_empty() {
+        return defaults.iter().map(|v| v.to_string()).collect();
+    }
+
+    out
+}
+
 fn dedup_paths(paths: Vec<PathBuf>) -> Vec<PathBuf> {
     let mut seen = HashSet::new();
     let mut out = Vec::new();
@@ -659,7 +2222,113 @@ fn sanitize_jsonc(input: &str) -> String {
 }
 
 impl Resolver {
+    fn resolve_path(&self, raw_candidate: &Path) -> Result<Option<PathBuf>> {
+        resolve_candidate_path(
+            raw_candidate,
+            &self.files,
+            &self.extensions,
+            &self.platforms,
+            self.case_index.as_ref(),
+            &self.path_index,
+        )
+    }
+
+    /// Resolves a specifier plus every platform variant and browser-field counterpart of the
+    /// resolved file, so `./Button` keeps `Button.ios.tsx` and `Button.android.tsx` reachable
+    /// together.
+    fn resolve_specifier_all(&self, from_file: &Path, specifier: &str) -> Result<Vec<PathBuf>> {
+        let Some(resolved) = self.resolve_specifier(from_file, specifier)? else {
+            return Ok(self
+                .browser_module_shims
+                .get(&normalize_specifier(specifier))
+                .cloned()
+                .into_iter()
+                .collect());
+        };
+
+        let mut out = vec![resolved.clone()];
+        out.extend(self.platform_variants(&resolved));
+        out.extend(self.linked_files(&resolved).iter().cloned());
+        Ok(out)
+    }
+
+    fn linked_files(&self, file: &Path) -> &[PathBuf] {
+        self.linked_files
+            .get(file)
+            .map(|v| v.as_slice())
+            .unwrap_or_default()
+    }
+
+    fn platform_variants(&self, resolved: &Path) -> Vec<PathBuf> {
+        if self.platforms.is_empty() {
+            return Vec::new();
+        }
+
+        let Some(parent) = resolved.parent() else {
+            return Vec::new();
+        };
+        let Some(file_name) = resolved.file_name().and_then(|n| n.to_str()) else {
+            return Vec::new();
+        };
+        let Some(stem) = self
+            .extensions
+            .iter()
+            .find_map(|ext| file_name.strip_suffix(&format!(".{ext}")))
+        else {
+            return Vec::new();
+        };
+        let base = self
+            .platforms
+            .iter()
+            .find_map(|platform| stem.strip_suffix(&format!(".{platform}")))
+            .unwrap_or(stem);
+
+        let mut out = Vec::new();
+        for ext in &self.extensions {
+            let mut names = vec![format!("{base}.{ext}")];
+            for platform in &self.platforms {
+                names.push(format!("{base}.{platform}.{ext}"));
+            }
+            for name in names {
+                let candidate = parent.join(name);
+                if candidate != resolved && self.files.contains(&candidate) {
+                    out.push(candidate);
+                }
+            }
+        }
+
+        out
+    }
+
+    /// Memoized: the same specifier is resolved by the reachability, unresolved, usage, and
+    /// package passes. Only relative specifiers depend on the importing file's directory.
     fn resolve_specifier(&self, from_file: &Path, specifier: &str) -> Result<Option<PathBuf>> {
+        let normalized = normalize_specifier(specifier);
+        let dir = if is_relative_specifier(&normalized) {
+            from_file
+                .parent()
+                .map(Path::to_path_buf)
+                .unwrap_or_default()
+        } else {
+            PathBuf::new()
+        };
+        let key = (dir, normalized);
+
+        if let Some(cached) = self.resolve_cache.borrow().get(&key) {
+            return Ok(cached.clone());
+        }
+        let resolved = self.resolve_specifier_uncached(from_file, &key.1)?;
+        self.resolve_cache
+            .borrow_mut()
+            .insert(key, resolved.clone());
+        Ok(resolved)
+    }
+
+    fn resolve_specifier_uncached(
+        &self,
+        from_file: &Path,
+        specifier: &str,
+    ) -> Result<Option<PathBuf>> {
         let normalized = normalize_specifier(specifier);
         if normalized.is_empty() {
             return Ok(None);
@@ -669,28 +2338,40 @@ impl Resolver {
             let Some(parent) = from_file.parent() else {
                 return Ok(None);
             };
-            return resolve_candidate_path(&parent.join(&normalized), &self.files);
+            return self.resolve_path(&parent.join(&normalized));
         }
 
         if let Some(trimmed) = normalized.strip_prefix('/') {
-            return resolve_candidate_path(&self.root.join(trimmed), &self.files);
+            return self.resolve_path(&self.root.join(trimmed));
         }
 
         for rule in &self.alias_rules {
-            if let Some(star) = match_alias(&rule.key, &normalized) {
-                let target = apply_alias_target(&rule.target, &star);
-                if let Some(path) =
-                    resolve_candidate_path(&rule.base_dir.join(target), &self.files)?
-                {
-                    return Ok(Some(path));
-                }
+            if let Some(star) = match_alias(&rule.key, &normalized)
+                && let Some(path) = self.resolve_path(&rule.target_path(&star))?
+            {
+                return Ok(Some(path));
+            }
+        }
+
+        for rule in &self.pattern_alias_rules {
+            if let Some(target) = rule.target_path(&normalized)
+                && let Some(path) = self.resolve_path(&target)?
+            {
+                return Ok(Some(path));
             }
         }
 
+        if let Some((package, subpath)) =
+            match_workspace_package(&self.workspace_packages, &normalized)
+            && let Some(path) = resolve_workspace_subpath(self, package, &subpath)?
+        {
+            return Ok(Some(path));
+        }
+
         // Absolute-style imports through baseUrl (e.g., import x from "utils/foo").
         if !looks_like_package_specifier(&normalized) {
             for base in &self.base_dirs {
-                if let Some(path) = resolve_candidate_path(&base.join(&normalized), &self.files)? {
+                if let Some(path) = self.resolve_path(&base.join(&normalized))? {
                     return Ok(Some(path));
                 }
             }
@@ -699,12 +2380,43 @@ impl Resolver {
         Ok(None)
     }
 
+    /// Whether a bare specifier is covered by a `declare module` in a `.d.ts` file, including
+    /// wildcard declarations such as `*.svg` or `virtual:*`.
+    fn is_ambient_module(&self, specifier: &str) -> bool {
+        self.ambient_modules.iter().any(|pattern| {
+            match_alias(pattern, specifier).is_some()
+                || match_alias(pattern, &normalize_specifier(specifier)).is_some()
+        })
+    }
+
+    /// Returns the package behind a `#` subpath import that maps to a bare package
+    /// specifier (e.g. `"#fetch": "node-fetch"`). Bundler aliases such as Nuxt's `#shared`,
+    /// whose `*` target stands for a path in their folder, map to no package.
+    fn package_import_target(&self, specifier: &str) -> Option<String> {
+        if !specifier.starts_with('#') {
+            return None;
+        }
+
+        self.alias_rules.iter().find_map(|rule| {
+            if rule.target.is_empty() || rule.target == "*" {
+                return None;
+            }
+            let star = match_alias(&rule.key, specifier)?;
+            let target = apply_alias_target(&rule.target, &star);
+            looks_like_package_specifier(&target).then(|| package_name(&target))
+        })
+    }
+
     fn is_likely_local_specifier(&self, specifier: &str) -> bool {
         let normalized = normalize_specifier(specifier);
         if normalized.is_empty() {
             return false;
         }
 
+        if self.package_import_target(&normalized).is_some() {
+            return false;
+        }
+
         if is_relative_specifier(&normalized) || normalized.starts_with('/') {
             return true;
         }
@@ -713,10 +2425,20 @@ impl Resolver {
             .alias_rules
             .iter()
             .any(|rule| match_alias(&rule.key, &normalized).is_some())
+            || self
+                .pattern_alias_rules
+                .iter()
+                .any(|rule| rule.pattern.is_match(&normalized))
         {
             return true;
         }
 
+        // Typed ambiently (`declare module 'virtual:routes'`, `declare module '*.svg'`), so a
+        // failed lookup is not evidence of a broken local import.
+        if self.is_ambient_module(specifier) {
+            return false;
+        }
+
         if !looks_like_package_specifier(&normalized) {
             return true;
         }
@@ -724,6 +2446,27 @@ impl Resolver {
         false
     }
 
+    /// The path a relative, root-absolute, or aliased specifier points at, whether or not
+    /// anything exists there. `None` for specifiers without an explicit local mapping.
+    fn local_target_path(&self, from_file: &Path, specifier: &str) -> Option<PathBuf> {
+        let normalized = normalize_specifier(specifier);
+        if is_relative_specifier(&normalized) {
+            return Some(normalize_path(from_file.parent()?.join(&normalized)));
+        }
+        if let Some(trimmed) = normalized.strip_prefix('/') {
+            return Some(self.root.join(trimmed));
+        }
+        if let Some(path) = self.alias_rules.iter().find_map(|rule| {
+            match_alias(&rule.key, &normalized).map(|star| rule.target_path(&star))
+        }) {
+            return Some(normalize_path(path));
+        }
+        self.pattern_alias_rules
+            .iter()
+            .find_map(|rule| rule.target_path(&normalized))
+            .map(normalize_path)
+    }
+
     fn local_specifier_exists(&self, from_file: &Path, specifier: &str) -> Result<bool> {
         let normalized = normalize_specifier(specifier);
         if normalized.is_empty() {
@@ -734,25 +2477,32 @@ impl Resolver {
             let Some(parent) = from_file.parent() else {
                 return Ok(false);
             };
-            return local_target_exists(&parent.join(&normalized));
+            return local_target_exists(&parent.join(&normalized), &self.path_index);
         }
 
         if let Some(trimmed) = normalized.strip_prefix('/') {
-            return local_target_exists(&self.root.join(trimmed));
+            return local_target_exists(&self.root.join(trimmed), &self.path_index);
         }
 
         for rule in &self.alias_rules {
-            if let Some(star) = match_alias(&rule.key, &normalized) {
-                let target = apply_alias_target(&rule.target, &star);
-                if local_target_exists(&rule.base_dir.join(target))? {
-                    return Ok(true);
-                }
+            if let Some(star) = match_alias(&rule.key, &normalized)
+                && local_target_exists(&rule.target_path(&star), &self.path_index)?
+            {
+                return Ok(true);
+            }
+        }
+
+        for rule in &self.pattern_alias_rules {
+            if let Some(target) = rule.target_path(&normalized)
+                && local_target_exists(&target, &self.path_index)?
+            {
+                return Ok(true);
             }
         }
 
         if !looks_like_package_specifier(&normalized) {
             for base in &self.base_dirs {
-                if local_target_exists(&base.join(&normalized))? {
+                if local_target_exists(&base.join(&normalized), &self.path_index)? {
                     return Ok(true);
                 }
             }
@@ -775,18 +2525,52 @@ fn collect_used_packages(
         };
 
         for import in &module.imports {
-            let normalized = normalize_specifier(&import.specifier);
-            if resolver.resolve_specifier(file, &normalized)?.is_none()
-                && looks_like_package_specifier(&normalized)
-            {
-                used.insert(package_name(&normalized));
+            if let Some(package) = imported_package(file, &import.specifier, resolver)? {
+                used.insert(package);
             }
         }
     }
 
+    // `declare module 'vue' { ... }` augments or types a package, so it is in use.
+    for name in &resolver.ambient_modules {
+        if !name.contains('*') && looks_like_package_specifier(name) {
+            used.insert(package_name(name));
+        }
+    }
+
     Ok(used)
 }
 
+/// The package an import specifier refers to: the target of a `#` subpath import, a
+/// workspace package, or a bare specifier that resolves to no local file.
+fn imported_package(file: &Path, specifier: &str, resolver: &Resolver) -> Result<Option<String>> {
+    let normalized = normalize_specifier(specifier);
+    if let Some(package) = resolver.package_import_target(&normalized) {
+        return Ok(Some(package));
+    }
+    // Workspace packages resolve to local files but are still declared dependencies.
+    if let Some((package, _)) = match_workspace_package(&resolver.workspace_packages, &normalized) {
+        return Ok(Some(package.name.clone()));
+    }
+    if resolver.resolve_specifier(file, &normalized)?.is_none()
+        && looks_like_package_specifier(&normalized)
+    {
+        return Ok(Some(package_name(&normalized)));
+    }
+    Ok(None)
+}
+
+fn collect_ambient_modules(declaration_files: &HashSet<PathBuf>) -> Vec<String> {
+    let mut names = BTreeSet::new();
+    for file in declaration_files {
+        let source = strip_comments(&fs::read_to_string(file).unwrap_or_default());
+        for caps in DECLARE_MODULE_RE.captures_iter(&source) {
+            names.insert(caps[1].to_string());
+        }
+    }
+    names.into_iter().collect()
+}
+
 fn collect_declared_dependencies(root: &Path) -> Result<HashMap<String, DepKind>> {
     let package_json = root.join("package.json");
     if !package_json.exists() {
@@ -795,14 +2579,76 @@ fn collect_declared_dependencies(root: &Path) -> Result<HashMap<String, DepKind>
 
     let raw = fs::read_to_string(package_json)?;
     let value: serde_json::Value = serde_json::from_str(&raw)?;
+    Ok(declared_dependencies(&value))
+}
 
+fn declared_dependencies(package_json: &serde_json::Value) -> HashMap<String, DepKind> {
     let mut deps = HashMap::new();
-    insert_dep_kind(&mut deps, &value, "dependencies", DepKind::Prod);
-    insert_dep_kind(&mut deps, &value, "devDependencies", DepKind::Dev);
-    insert_dep_kind(&mut deps, &value, "peerDependencies", DepKind::Peer);
-    insert_dep_kind(&mut deps, &value, "optionalDependencies", DepKind::Optional);
+    // The first section listing a package wins, so a package that is both a peer and a
+    // devDependency (installed for local development) counts as a peer.
+    insert_dep_kind(&mut deps, package_json, "dependencies", DepKind::Prod);
+    insert_dep_kind(&mut deps, package_json, "peerDependencies", DepKind::Peer);
+    insert_dep_kind(
+        &mut deps,
+        package_json,
+        "optionalDependencies",
+        DepKind::Optional,
+    );
+    insert_dep_kind(&mut deps, package_json, "devDependencies", DepKind::Dev);
+    deps
+}
+
+fn find_unused_dependencies(
+    declared: &HashMap<String, DepKind>,
+    used: &HashSet<String>,
+    include_non_prod: bool,
+) -> Vec<String> {
+    let mut unused: Vec<String> = declared
+        .iter()
+        .filter(|(name, kind)| {
+            if name.starts_with("@types/") {
+                return false;
+            }
+
+            if !include_non_prod {
+                return **kind == DepKind::Prod;
+            }
 
-    Ok(deps)
+            true
+        })
+        .map(|(name, _)| name)
+        .filter(|name| !used.contains(*name))
+        .cloned()
+        .collect();
+    unused.sort();
+    unused
+}
+
+/// Declared `@types/*` packages whose runtime package (`lodash` for `@types/lodash`,
+/// `@scope/pkg` for `@types/scope__pkg`) is neither declared nor imported. `@types/node` and
+/// packages listed in tsconfig `compilerOptions.types` are ambient on purpose and skipped.
+fn find_orphaned_types_packages(
+    declared: &HashMap<String, DepKind>,
+    used: &HashSet<String>,
+    tsconfig_types: &HashSet<String>,
+) -> Vec<String> {
+    let mut orphaned: Vec<String> = declared
+        .keys()
+        .filter_map(|name| {
+            let types_name = name.strip_prefix("@types/")?;
+            let runtime = match types_name.split_once("__") {
+                Some((scope, package)) => format!("@{scope}/{package}"),
+                None => types_name.to_string(),
+            };
+            let orphaned = types_name != "node"
+                && !tsconfig_types.contains(types_name)
+                && !declared.contains_key(&runtime)
+                && !used.contains(&runtime);
+            orphaned.then(|| name.clone())
+        })
+        .collect();
+    orphaned.sort();
+    orphaned
 }
 
 fn insert_dep_kind(
@@ -831,9 +2677,15 @@ fn reachable_files(
             continue;
         }
 
+        for linked in resolver.linked_files(&current) {
+            if !seen.contains(linked) {
+                queue.push_back(linked.clone());
+            }
+        }
+
         if let Some(module) = modules.get(&current) {
             for import in &module.imports {
-                if let Some(next) = resolver.resolve_specifier(&current, &import.specifier)? {
+                for next in resolver.resolve_specifier_all(&current, &import.specifier)? {
                     if !seen.contains(&next) {
                         queue.push_back(next);
                     }
@@ -845,12 +2697,26 @@ fn reachable_files(
     Ok(seen)
 }
 
+/// Local imports that did not resolve, split by how much they say about the graph.
+#[derive(Debug, Default)]
+struct LocalImportIssues {
+    /// Specifiers haadi could not map to a path (unknown aliases, unsupported configs).
+    /// These lower graph confidence.
+    unresolved: Vec<UnresolvedImport>,
+    /// Relative, root-absolute, or aliased source imports whose target file does not exist.
+    broken: Vec<UnresolvedImport>,
+    /// Imports of asset files that do not exist.
+    missing_assets: Vec<UnresolvedImport>,
+}
+
 fn collect_unresolved_local_imports(
     reachable: &HashSet<PathBuf>,
     modules: &HashMap<PathBuf, ModuleInfo>,
     resolver: &Resolver,
-) -> Result<Vec<UnresolvedImport>> {
+) -> Result<LocalImportIssues> {
     let mut unresolved = BTreeSet::new();
+    let mut broken = BTreeSet::new();
+    let mut missing_assets = BTreeSet::new();
 
     for file in reachable {
         let Some(module) = modules.get(file) else {
@@ -867,15 +2733,29 @@ fn collect_unresolved_local_imports(
                 .is_none()
                 && !resolver.local_specifier_exists(file, &import.specifier)?
             {
-                unresolved.insert(UnresolvedImport {
+                let item = UnresolvedImport {
                     from_file: file.clone(),
                     specifier: import.specifier.clone(),
-                });
+                };
+                if has_asset_extension(Path::new(&normalize_specifier(&import.specifier))) {
+                    missing_assets.insert(item);
+                } else if resolver
+                    .local_target_path(file, &import.specifier)
+                    .is_some()
+                {
+                    broken.insert(item);
+                } else {
+                    unresolved.insert(item);
+                }
             }
         }
     }
 
-    Ok(unresolved.into_iter().collect())
+    Ok(LocalImportIssues {
+        unresolved: unresolved.into_iter().collect(),
+        broken: broken.into_iter().collect(),
+        missing_assets: missing_assets.into_iter().collect(),
+    })
 }
 
 fn infer_potentially_used_files_from_unresolved(
@@ -909,10 +2789,10 @@ fn infer_potentially_used_files_from_unresolved(
                 continue;
             }
 
-            if let Some(leaf_name) = &leaf {
-                if file.file_stem().and_then(|v| v.to_str()) == Some(leaf_name.as_str()) {
-                    maybe_used.insert(file.clone());
-                }
+            if let Some(leaf_name) = &leaf
+                && file.file_stem().and_then(|v| v.to_str()) == Some(leaf_name.as_str())
+            {
+                maybe_used.insert(file.clone());
             }
         }
     }
@@ -951,10 +2831,10 @@ fn unresolved_specifier_suffixes(specifier: &str) -> Vec<String> {
     if let Some(stripped) = base.strip_prefix("~/") {
         out.insert(stripped.to_string());
     }
-    if base.starts_with('@') {
-        if let Some((_, rest)) = base.split_once('/') {
-            out.insert(rest.to_string());
-        }
+    if base.starts_with('@')
+        && let Some((_, rest)) = base.split_once('/')
+    {
+        out.insert(rest.to_string());
     }
     if let Some(stripped) = base.strip_prefix("src/") {
         out.insert(stripped.to_string());
@@ -970,7 +2850,7 @@ fn unresolved_leaf_name(specifier: &str) -> Option<String> {
         .split('#')
         .next()?
         .replace('\\', "/");
-    let leaf = clean.split('/').filter(|v| !v.is_empty()).next_back()?;
+    let leaf = clean.split('/').rfind(|v| !v.is_empty())?;
     if leaf == "." || leaf == ".." {
         return None;
     }
@@ -989,6 +2869,10 @@ fn strip_file_extension(path_like: &str) -> String {
 fn resolve_candidate_path(
     raw_candidate: &Path,
     files: &HashSet<PathBuf>,
+    extensions: &[String],
+    platforms: &[String],
+    case_index: Option<&HashMap<String, PathBuf>>,
+    path_index: &PathIndex,
 ) -> Result<Option<PathBuf>> {
     let mut candidates = Vec::new();
 
@@ -996,27 +2880,77 @@ fn resolve_candidate_path(
         candidates.push(raw_candidate.to_path_buf());
     } else {
         candidates.push(raw_candidate.to_path_buf());
-        for ext in JS_TS_EXTENSIONS {
+        // Platform-specific files win over plain ones, mirroring Metro's resolution order.
+        for platform in platforms {
+            for ext in extensions {
+                candidates.push(raw_candidate.with_extension(format!("{platform}.{ext}")));
+            }
+        }
+        for ext in extensions {
             candidates.push(raw_candidate.with_extension(ext));
         }
-        for ext in JS_TS_EXTENSIONS {
+        for platform in platforms {
+            for ext in extensions {
+                candidates.push(raw_candidate.join(format!("index.{platform}.{ext}")));
+            }
+        }
+        for ext in extensions {
             candidates.push(raw_candidate.join(format!("index.{ext}")));
         }
     }
 
     for candidate in candidates {
-        if candidate.exists() {
-            let canonical = fs::canonicalize(candidate)?;
+        let indexed = path_index.is_file(&candidate);
+        if indexed == Some(true) {
+            let path = normalize_path(candidate.clone());
+            if files.contains(&path) {
+                return Ok(Some(path));
+            }
+        }
+        // Indexed files that are not scanned directly may still be symlinks to scanned ones.
+        if indexed != Some(false) && candidate.exists() {
+            let canonical = fs::canonicalize(&candidate)?;
             if files.contains(&canonical) {
                 return Ok(Some(canonical));
             }
+            if let Some(file) = case_index.and_then(|index| index.get(&case_fold_key(&canonical))) {
+                return Ok(Some(file.clone()));
+            }
+        }
+        if let Some(file) = case_index.and_then(|index| index.get(&case_fold_key(&candidate))) {
+            return Ok(Some(file.clone()));
         }
     }
 
     Ok(None)
 }
 
-fn local_target_exists(raw_candidate: &Path) -> Result<bool> {
+fn case_fold_key(path: &Path) -> String {
+    normalize_path(path.to_path_buf())
+        .to_string_lossy()
+        .to_lowercase()
+}
+
+/// The filesystem is case-insensitive when a scanned file is also reachable under its name
+/// with the letter case flipped (macOS and Windows defaults).
+fn detect_case_insensitive_fs(files: &HashSet<PathBuf>) -> bool {
+    files.iter().find_map(|file| {
+        let name = file.file_name()?.to_str()?;
+        let flipped: String = name
+            .chars()
+            .map(|c| {
+                if c.is_lowercase() {
+                    c.to_ascii_uppercase()
+                } else {
+                    c.to_ascii_lowercase()
+                }
+            })
+            .collect();
+        (flipped != name).then(|| file.with_file_name(flipped).exists())
+    }) == Some(true)
+}
+
+fn local_target_exists(raw_candidate: &Path, path_index: &PathIndex) -> Result<bool> {
     let mut candidates = Vec::new();
 
     if raw_candidate.extension().is_some() {
@@ -1031,7 +2965,9 @@ fn local_target_exists(raw_candidate: &Path) -> Result<bool> {
         }
     }
 
-    Ok(candidates.into_iter().any(|path| path.exists()))
+    Ok(candidates
+        .into_iter()
+        .any(|path| path_index.exists(&path).unwrap_or_else(|| path.exists())))
 }
 
 fn normalize_specifier(specifier: &str) -> String {
@@ -1043,8 +2979,9 @@ fn normalize_specifier(specifier: &str) -> String {
     if let Some((left, _)) = out.split_once('?') {
         out = left.to_string();
     }
-    if let Some((left, _)) = out.split_once('#') {
-        out = left.to_string();
+    // A leading `#` is a package.json subpath import, not a fragment.
+    if let Some(idx) = out.get(1..).and_then(|rest| rest.find('#')) {
+        out.truncate(idx + 1);
     }
 
     out.trim().to_string()
@@ -1119,6 +3056,7 @@ fn is_test_like_file(path: &Path) -> bool {
 
     file_name.contains(".test.")
         || file_name.contains(".spec.")
+        || file_name.contains(".cy.")
         || path_str.contains("/__tests__/")
         || path_str.contains("\\__tests__\\")
 }
@@ -1202,6 +3140,7 @@ fn is_ignored_dir(path: &Path) -> bool {
         "node_modules",
         ".git",
         ".haadi_trash",
+        ".haadi",
         "dist",
         "build",
         "coverage",
@@ -1288,3 +3227,61 @@ fn package_name(specifier: &str) -> String {
         first.to_string()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Cli {
+        Cli::try_parse_from(std::iter::once("haadi").chain(args.iter().copied()))
+            .expect("arguments parse")
+    }
+
+    #[test]
+    fn format_conflicts_only_with_enabled_output_flags() {
+        // SAFETY: no other test reads or writes this variable.
+        unsafe { std::env::set_var("HAADI_TUI", "false") };
+        assert!(parse(&["--format", "vimgrep"]).check_conflicts().is_ok());
+        assert!(
+            parse(&["--format", "vimgrep", "--tui"])
+                .check_conflicts()
+                .is_err()
+        );
+        assert!(
+            parse(&["--format", "vimgrep", "--json"])
+                .check_conflicts()
+                .is_err()
+        );
+        assert!(parse(&["--json", "--tui"]).check_conflicts().is_ok());
+    }
+
+    #[test]
+    fn checkpoint_flags_conflict_only_with_enabled_modes() {
+        // SAFETY: no other test reads or writes these variables.
+        unsafe {
+            std::env::set_var("HAADI_WATCH", "false");
+            std::env::set_var("HAADI_SERVE_REPORT", "0");
+        }
+        assert!(
+            parse(&["--resume-from", "exports"])
+                .check_conflicts()
+                .is_ok()
+        );
+        assert!(parse(&["--stop-after", "scan"]).check_conflicts().is_ok());
+        assert!(
+            parse(&["--resume-from", "exports", "--watch"])
+                .check_conflicts()
+                .is_err()
+        );
+        assert!(
+            parse(&["--resume-from", "exports", "--serve-report"])
+                .check_conflicts()
+                .is_err()
+        );
+        assert!(
+            parse(&["--stop-after", "scan", "--watch"])
+                .check_conflicts()
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,26 @@
+use std::io::IsTerminal;
+use tracing_subscriber::EnvFilter;
+
+/// Sends diagnostics about the analysis (configs read, aliases applied, entries found, ...)
+/// to stderr, so report output on stdout stays clean. `-v` shows progress, `-vv` per-file
+/// details, `-vvv` everything; `RUST_LOG` overrides the level when set.
+pub(crate) fn init_logging(verbosity: u8, json: bool) {
+    let level = match verbosity {
+        0 => "warn",
+        1 => "info",
+        2 => "debug",
+        _ => "trace",
+    };
+    let filter = EnvFilter::try_from_default_env()
+        .unwrap_or_else(|_| EnvFilter::new(format!("haadi={level}")));
+    let builder = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(std::io::stderr)
+        .with_ansi(std::io::stderr().is_terminal());
+    // Embedders may have installed their own subscriber already; keep theirs.
+    if json {
+        let _ = builder.json().try_init();
+    } else {
+        let _ = builder.try_init();
+    }
+}
@@ -0,0 +1,396 @@
+use super::*;
+use crate::findings::{UNUSED_EXPORT, UNUSED_FILE};
+use crate::fix_exports::locate_export;
+use serde_json::{Value, json};
+use std::io::{self, BufRead, Write};
+
+/// LSP `DiagnosticSeverity.Warning` and `DiagnosticTag.Unnecessary` (rendered faded).
+const SEVERITY_WARNING: u8 = 2;
+const TAG_UNNECESSARY: u8 = 1;
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+/// LSP `RequestFailed`: the request was valid but could not be carried out.
+const REQUEST_FAILED: i64 = -32803;
+
+struct LspState {
+    options: AnalysisOptions,
+    root: PathBuf,
+    report: Option<Report>,
+    parse_cache: ParseCache,
+    open_files: BTreeSet<PathBuf>,
+}
+
+/// `haadi lsp`: a minimal language server over stdio. It analyzes the workspace on startup
+/// and after every save, publishes unused-file and unused-export diagnostics for open files,
+/// and offers code actions to delete an unused file or drop an `export` keyword.
+pub(crate) fn serve(options: &AnalysisOptions) -> Result<()> {
+    let stdin = io::stdin();
+    let mut reader = stdin.lock();
+    let mut out = io::stdout().lock();
+    let mut state: Option<LspState> = None;
+
+    // An analysis stopped by Ctrl-C has answered its request; the server stops after it.
+    while !interrupted()
+        && let Some(message) = read_message(&mut reader)?
+    {
+        // A malformed message gets a parse error without an id; the session goes on.
+        let message = match message {
+            Ok(message) => message,
+            Err(err) => {
+                info!("skipping malformed LSP message: {err:#}");
+                respond_error(&mut out, Value::Null, PARSE_ERROR, format!("{err:#}"))?;
+                continue;
+            }
+        };
+        let method = message.get("method").and_then(Value::as_str).unwrap_or("");
+        let id = message.get("id").cloned();
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+        match method {
+            "initialize" => {
+                let root = params
+                    .get("rootUri")
+                    .and_then(Value::as_str)
+                    .and_then(uri_to_path)
+                    .unwrap_or_else(|| options.root.clone());
+                // Checkpoints go stale as soon as the editor changes a file.
+                let options = AnalysisOptions {
+                    root,
+                    resume_from: None,
+                    ..options.clone()
+                };
+                // A root that cannot be opened fails the request, not the server, so the
+                // client can report it.
+                let root = match fs::canonicalize(&options.root) {
+                    Ok(root) => root,
+                    Err(err) => {
+                        let message = format!(
+                            "Failed to access root: {}: {err}",
+                            shown_path(&options.root)
+                        );
+                        respond_error(
+                            &mut out,
+                            id.unwrap_or(Value::Null),
+                            REQUEST_FAILED,
+                            message,
+                        )?;
+                        continue;
+                    }
+                };
+                state = Some(LspState {
+                    options,
+                    root,
+                    report: None,
+                    parse_cache: ParseCache::default(),
+                    open_files: BTreeSet::new(),
+                });
+                let result = json!({
+                    "capabilities": {
+                        "textDocumentSync": { "openClose": true, "save": true },
+                        "codeActionProvider": true,
+                    },
+                    "serverInfo": { "name": "haadi", "version": env!("CARGO_PKG_VERSION") },
+                });
+                respond(&mut out, id, result)?;
+            }
+            "initialized" => {
+                if let Some(state) = state.as_mut() {
+                    reanalyze(&mut out, state)?;
+                }
+            }
+            "textDocument/didOpen" | "textDocument/didSave" | "textDocument/didClose" => {
+                let Some(state) = state.as_mut() else {
+                    continue;
+                };
+                let Some(path) = document_path(&params) else {
+                    continue;
+                };
+                match method {
+                    "textDocument/didOpen" => {
+                        state.open_files.insert(path.clone());
+                        publish(&mut out, state, &path)?;
+                    }
+                    "textDocument/didSave" => reanalyze(&mut out, state)?,
+                    _ => {
+                        state.open_files.remove(&path);
+                        notify(
+                            &mut out,
+                            "textDocument/publishDiagnostics",
+                            json!({ "uri": path_to_uri(&path), "diagnostics": [] }),
+                        )?;
+                    }
+                }
+            }
+            "textDocument/codeAction" => {
+                respond(&mut out, id, code_actions(&params))?;
+            }
+            "shutdown" => respond(&mut out, id, Value::Null)?,
+            "exit" => break,
+            _ => {
+                // Requests need an answer; unknown notifications are ignored.
+                if let Some(id) = id {
+                    respond_error(
+                        &mut out,
+                        id,
+                        METHOD_NOT_FOUND,
+                        format!("Unsupported method: {method}"),
+                    )?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Re-runs the analysis and republishes diagnostics for every open file. A failed run is
+/// logged to the client and the previous report is kept.
+fn reanalyze(out: &mut impl Write, state: &mut LspState) -> Result<()> {
+    match analyze_cached(&state.options, &mut state.parse_cache) {
+        Ok(report) => state.report = Some(report),
+        Err(err) => {
+            notify(
+                out,
+                "window/logMessage",
+                json!({ "type": 1, "message": format!("haadi analysis failed: {err:#}") }),
+            )?;
+        }
+    }
+    for path in state.open_files.clone() {
+        publish(out, state, &path)?;
+    }
+    Ok(())
+}
+
+fn publish(out: &mut impl Write, state: &LspState, path: &Path) -> Result<()> {
+    let diagnostics = state
+        .report
+        .as_ref()
+        .map(|report| file_diagnostics(&state.root, report, path))
+        .unwrap_or_default();
+    notify(
+        out,
+        "textDocument/publishDiagnostics",
+        json!({ "uri": path_to_uri(path), "diagnostics": diagnostics }),
+    )
+}
+
+fn file_diagnostics(root: &Path, report: &Report, path: &Path) -> Vec<Value> {
+    let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    let Ok(rel) = path.strip_prefix(root) else {
+        return Vec::new();
+    };
+    let rel = rel.display().to_string();
+    let source = fs::read_to_string(&path).unwrap_or_default();
+    let mut diagnostics = Vec::new();
+
+    if report.unused_files.contains(&rel) {
+        diagnostics.push(json!({
+            "range": lsp_range(&source, 0, 0),
+            "severity": SEVERITY_WARNING,
+            "tags": [TAG_UNNECESSARY],
+            "source": "haadi",
+            "code": finding_id(UNUSED_FILE, &rel, None),
+            "message": "File is not reachable from any entry.",
+            "data": { "category": UNUSED_FILE },
+        }));
+    }
+
+    for item in report.unused_exports.iter().filter(|item| item.file == rel) {
+        let location = locate_export(&source, &item.export);
+        let (start, end) = location.map_or((0, 0), |loc| loc.range);
+        diagnostics.push(json!({
+            "range": lsp_range(&source, start, end),
+            "severity": SEVERITY_WARNING,
+            "tags": [TAG_UNNECESSARY],
+            "source": "haadi",
+            "code": finding_id(UNUSED_EXPORT, &rel, Some(&item.export)),
+            "message": format!("Export '{}' is not imported by any reachable file.", item.export),
+            "data": {
+                "category": UNUSED_EXPORT,
+                "export": item.export,
+                "keyword": location
+                    .and_then(|loc| loc.keyword)
+                    .map(|(start, end)| lsp_range(&source, start, end)),
+            },
+        }));
+    }
+
+    diagnostics
+}
+
+fn code_actions(params: &Value) -> Value {
+    let Some(uri) = params.pointer("/textDocument/uri").and_then(Value::as_str) else {
+        return json!([]);
+    };
+    let diagnostics = params
+        .pointer("/context/diagnostics")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+
+    let mut actions = Vec::new();
+    for diagnostic in diagnostics
+        .iter()
+        .filter(|d| d.get("source").and_then(Value::as_str) == Some("haadi"))
+    {
+        let category = diagnostic.pointer("/data/category").and_then(Value::as_str);
+        match category {
+            Some(UNUSED_FILE) => actions.push(json!({
+                "title": "Delete unused file",
+                "kind": "quickfix",
+                "diagnostics": [diagnostic],
+                "edit": { "documentChanges": [{ "kind": "delete", "uri": uri }] },
+            })),
+            Some(UNUSED_EXPORT) => {
+                let Some(keyword) = diagnostic.pointer("/data/keyword").filter(|v| !v.is_null())
+                else {
+                    continue;
+                };
+                let export = diagnostic
+                    .pointer("/data/export")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                actions.push(json!({
+                    "title": format!("Remove `export` from '{export}'"),
+                    "kind": "quickfix",
+                    "diagnostics": [diagnostic],
+                    "edit": { "changes": { uri: [{ "range": keyword, "newText": "" }] } },
+                }));
+            }
+            _ => {}
+        }
+    }
+
+    Value::Array(actions)
+}
+
+/// LSP positions count UTF-16 code units within a line.
+fn lsp_range(source: &str, start: usize, end: usize) -> Value {
+    json!({ "start": lsp_position(source, start), "end": lsp_position(source, end) })
+}
+
+fn lsp_position(source: &str, offset: usize) -> Value {
+    let before = &source[..offset.min(source.len())];
+    let line = before.matches('\n').count();
+    let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+    let character: usize = before[line_start..].chars().map(char::len_utf16).sum();
+    json!({ "line": line, "character": character })
+}
+
+fn document_path(params: &Value) -> Option<PathBuf> {
+    params
+        .pointer("/textDocument/uri")
+        .and_then(Value::as_str)
+        .and_then(uri_to_path)
+}
+
+fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let encoded = uri.strip_prefix("file://")?;
+    let bytes = encoded.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+    while idx < bytes.len() {
+        if bytes[idx] == b'%'
+            && let Some(hex) = encoded.get(idx + 1..idx + 3)
+            && let Ok(byte) = u8::from_str_radix(hex, 16)
+        {
+            decoded.push(byte);
+            idx += 3;
+        } else {
+            decoded.push(bytes[idx]);
+            idx += 1;
+        }
+    }
+    let path = String::from_utf8(decoded).ok()?;
+    // Windows URIs look like `file:///C:/src/app.ts`.
+    let path = match path.as_bytes() {
+        [b'/', _, b':', ..] => path[1..].to_string(),
+        _ => path,
+    };
+    Some(PathBuf::from(path))
+}
+
+fn path_to_uri(path: &Path) -> String {
+    let raw = path.to_string_lossy().replace('\\', "/");
+    let mut uri = String::from("file://");
+    if !raw.starts_with('/') {
+        uri.push('/');
+    }
+    for byte in raw.bytes() {
+        if byte.is_ascii_alphanumeric() || b"/-_.~:".contains(&byte) {
+            uri.push(byte as char);
+        } else {
+            uri.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    uri
+}
+
+/// The next message, `None` at the end of input, or an inner error for a message whose
+/// header or body is malformed. Only I/O errors end the session.
+fn read_message(reader: &mut impl BufRead) -> Result<Option<Result<Value>>> {
+    let mut content_length = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            content_length = Some(value.trim().parse::<usize>());
+        }
+    }
+
+    let length = match content_length {
+        Some(Ok(length)) => length,
+        Some(Err(err)) => {
+            return Ok(Some(
+                Err(err).context("Failed to read LSP message: invalid Content-Length"),
+            ));
+        }
+        None => {
+            return Ok(Some(Err(anyhow::anyhow!(
+                "Failed to read LSP message: missing Content-Length"
+            ))));
+        }
+    };
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    Ok(Some(
+        serde_json::from_slice(&body).context("Failed to parse LSP message"),
+    ))
+}
+
+fn respond(out: &mut impl Write, id: Option<Value>, result: Value) -> Result<()> {
+    write_message(
+        out,
+        &json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+    )
+}
+
+fn respond_error(out: &mut impl Write, id: Value, code: i64, message: String) -> Result<()> {
+    write_message(
+        out,
+        &json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } }),
+    )
+}
+
+fn notify(out: &mut impl Write, method: &str, params: Value) -> Result<()> {
+    write_message(
+        out,
+        &json!({ "jsonrpc": "2.0", "method": method, "params": params }),
+    )
+}
+
+fn write_message(out: &mut impl Write, message: &Value) -> Result<()> {
+    let body = serde_json::to_string(message)?;
+    write!(out, "Content-Length: {}\r\n\r\n{body}", body.len())?;
+    out.flush()?;
+    Ok(())
+}
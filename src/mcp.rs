@@ -0,0 +1,262 @@
+use super::*;
+use serde_json::{Value, json};
+use std::io::{self, BufRead, Write};
+
+/// Used when the client does not name a protocol version.
+const DEFAULT_PROTOCOL_VERSION: &str = "2024-11-05";
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+struct McpState {
+    options: AnalysisOptions,
+    /// Where `trash_files` disposes of files: `--trash` and `--trash-max-age`.
+    trash: TrashOptions,
+    parse_cache: ParseCache,
+    /// Latest analysis; cleared whenever files are trashed or restored.
+    analysis: Option<(Report, ModuleGraph)>,
+}
+
+impl McpState {
+    fn analysis(&mut self) -> Result<&(Report, ModuleGraph)> {
+        if self.analysis.is_none() {
+            let (report, graph) = analyze_with_graph(&self.options, &mut self.parse_cache, true)?;
+            let graph = graph.context("Failed to build module graph")?;
+            self.analysis = Some((report, graph));
+        }
+        Ok(self.analysis.as_ref().expect("analysis was just computed"))
+    }
+}
+
+/// `haadi mcp`: a Model Context Protocol server on stdio (newline-delimited JSON-RPC) exposing
+/// the analysis as tools, so coding agents can drive cleanup with haadi's findings. Deletions
+/// follow `trash`, as TUI deletes do, and are limited to reported unused files and assets.
+pub(crate) fn serve(options: &AnalysisOptions, trash: TrashOptions) -> Result<()> {
+    let stdin = io::stdin();
+    let mut out = io::stdout().lock();
+    let mut state = McpState {
+        // Trashing files makes checkpoints stale, so the server always runs every stage.
+        options: AnalysisOptions {
+            resume_from: None,
+            ..options.clone()
+        },
+        trash,
+        parse_cache: ParseCache::default(),
+        analysis: None,
+    };
+
+    for line in stdin.lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        // A line that is not JSON gets a parse error without an id; the session goes on.
+        let message: Value = match serde_json::from_str(&line) {
+            Ok(message) => message,
+            Err(err) => {
+                let reply = json!({
+                    "jsonrpc": "2.0",
+                    "id": Value::Null,
+                    "error": { "code": PARSE_ERROR, "message": format!("Parse error: {err}") },
+                });
+                writeln!(out, "{}", serde_json::to_string(&reply)?)?;
+                out.flush()?;
+                continue;
+            }
+        };
+        let Some(id) = message.get("id").cloned() else {
+            // Notifications (`notifications/initialized`, cancellations) need no answer.
+            continue;
+        };
+        let method = message.get("method").and_then(Value::as_str).unwrap_or("");
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+        let response = match method {
+            "initialize" => Ok(json!({
+                "protocolVersion": params
+                    .get("protocolVersion")
+                    .and_then(Value::as_str)
+                    .unwrap_or(DEFAULT_PROTOCOL_VERSION),
+                "capabilities": { "tools": {} },
+                "serverInfo": { "name": "haadi", "version": env!("CARGO_PKG_VERSION") },
+            })),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": tool_definitions(state.trash.mode) })),
+            "tools/call" => call_tool(&mut state, &params),
+            _ => Err((METHOD_NOT_FOUND, format!("Unsupported method: {method}"))),
+        };
+
+        let reply = match response {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": code, "message": message },
+            }),
+        };
+        writeln!(out, "{}", serde_json::to_string(&reply)?)?;
+        out.flush()?;
+        // An analysis stopped by Ctrl-C has answered its request; the server stops after it.
+        if interrupted() {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+/// The tools, with `trash_files` described by where `trash` actually sends files.
+fn tool_definitions(trash: TrashMode) -> Value {
+    let trash_description = match trash {
+        TrashMode::Repo => {
+            "Move unused files or assets into .haadi_trash (reversible with trash_restore)."
+        }
+        TrashMode::System => {
+            "Move unused files or assets to the desktop trash. trash_restore cannot bring them back."
+        }
+        TrashMode::None => "Delete unused files or assets for good. They cannot be restored.",
+    };
+    json!([
+        {
+            "name": "analyze_project",
+            "description": "Re-run the dead-code analysis and return the full report (summary, unused files, assets, dependencies, exports, warnings, and findings with stable IDs).",
+            "inputSchema": { "type": "object", "properties": {} },
+        },
+        {
+            "name": "why_is_file_used",
+            "description": "Explain why a source file is considered used: the shortest import chain from an entry to it.",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "file": { "type": "string", "description": "Path relative to the project root" } },
+                "required": ["file"],
+            },
+        },
+        {
+            "name": "list_unused_exports",
+            "description": "List exports that no reachable file imports, optionally for a single file.",
+            "inputSchema": {
+                "type": "object",
+                "properties": { "file": { "type": "string", "description": "Path relative to the project root" } },
+            },
+        },
+        {
+            "name": "trash_files",
+            "description": format!("{trash_description} Paths that are not reported as unused are refused."),
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "files": { "type": "array", "items": { "type": "string" } },
+                    "name": { "type": "string", "description": "Optional batch name" },
+                },
+                "required": ["files"],
+            },
+        },
+        {
+            "name": "trash_restore",
+            "description": "Restore a trashed file, or every trashed file under a folder, to its original path.",
+            "inputSchema": {
+                "type": "object",
+                "properties": {
+                    "path": { "type": "string", "description": "File or folder path relative to the project root" },
+                    "on_conflict": {
+                        "type": "string",
+                        "enum": ["skip", "overwrite", "alongside"],
+                        "description": "When the original path exists again: skip (default), overwrite it, or restore as name.restored.ext",
+                    },
+                },
+                "required": ["path"],
+            },
+        },
+    ])
+}
+
+/// Runs one tool. Analysis failures are tool errors (`isError`), not protocol errors, so
+/// the agent sees the message.
+fn call_tool(state: &mut McpState, params: &Value) -> std::result::Result<Value, (i64, String)> {
+    let name = params.get("name").and_then(Value::as_str).unwrap_or("");
+    let args = params.get("arguments").cloned().unwrap_or(Value::Null);
+    let string_arg = |key: &str| args.get(key).and_then(Value::as_str).map(str::to_string);
+
+    let outcome = match name {
+        "analyze_project" => {
+            state.analysis = None;
+            state.analysis().and_then(|(report, _)| {
+                let mut report = report.clone();
+                redact_report(&mut report);
+                Ok(serde_json::to_string_pretty(&report)?)
+            })
+        }
+        "why_is_file_used" => {
+            let Some(file) = string_arg("file") else {
+                return Err((INVALID_PARAMS, "Missing argument: file".to_string()));
+            };
+            let file = file.trim_start_matches("./").replace('\\', "/");
+            state
+                .analysis()
+                .map(|(_, graph)| match graph.import_chain(&file) {
+                    Some(chain) if chain.len() == 1 => format!("{file} is an entry point."),
+                    Some(chain) => format!("{file} is reachable: {}", chain.join(" -> ")),
+                    None => format!("{file} is not reachable from any entry."),
+                })
+        }
+        "list_unused_exports" => {
+            let file = string_arg("file").map(|f| f.trim_start_matches("./").replace('\\', "/"));
+            state.analysis().and_then(|(report, _)| {
+                let exports: Vec<&UnusedExport> = report
+                    .unused_exports
+                    .iter()
+                    .filter(|item| file.as_ref().is_none_or(|file| &item.file == file))
+                    .collect();
+                Ok(serde_json::to_string_pretty(&exports)?)
+            })
+        }
+        "trash_files" => {
+            let files: Vec<String> = args
+                .get("files")
+                .and_then(Value::as_array)
+                .map(|items| {
+                    items
+                        .iter()
+                        .filter_map(Value::as_str)
+                        .map(str::to_string)
+                        .collect()
+                })
+                .unwrap_or_default();
+            if files.is_empty() {
+                return Err((INVALID_PARAMS, "Missing argument: files".to_string()));
+            }
+            let name = string_arg("name");
+            let trash = state.trash;
+            let outcome = state
+                .analysis()
+                .and_then(|(report, _)| trash_report_files(report, &files, name.as_deref(), trash));
+            state.analysis = None;
+            outcome
+        }
+        "trash_restore" => {
+            let Some(path) = string_arg("path") else {
+                return Err((INVALID_PARAMS, "Missing argument: path".to_string()));
+            };
+            let on_conflict = match string_arg("on_conflict") {
+                Some(value) => OnConflict::from_str(&value, false)
+                    .map_err(|err| (INVALID_PARAMS, format!("Invalid on_conflict: {err}")))?,
+                None => OnConflict::Skip,
+            };
+            let outcome = fs::canonicalize(&state.options.root)
+                .with_context(|| {
+                    format!("Failed to access root: {}", shown_path(&state.options.root))
+                })
+                .and_then(|root| restore_from_trash(&root, &path, on_conflict));
+            state.analysis = None;
+            outcome
+        }
+        _ => return Err((INVALID_PARAMS, format!("Unknown tool: {name}"))),
+    };
+
+    Ok(match outcome {
+        Ok(text) => json!({ "content": [{ "type": "text", "text": text }], "isError": false }),
+        Err(err) => {
+            json!({ "content": [{ "type": "text", "text": format!("{err:#}") }], "isError": true })
+        }
+    })
+}
@@ -0,0 +1,137 @@
+use super::*;
+use crate::missing_deps::{Manifest, is_installable_package, read_manifest};
+
+/// Where one declared dependency is imported from.
+struct Usage {
+    kind: DepKind,
+    /// Production files with a runtime import.
+    prod_runtime: BTreeSet<String>,
+    /// Production files with only `import type` or declaration-file imports.
+    prod_types: BTreeSet<String>,
+    /// Files reachable only from dev entries, and config files.
+    dev: BTreeSet<String>,
+}
+
+/// Dependencies declared in the wrong section of their `package.json`, charging each import
+/// to the nearest `package.json` that declares the package.
+///
+/// The first list holds `dependencies` entries that only dev files import (files outside
+/// `prod_reachable`, plus unreachable config files); they belong in `devDependencies`. It is
+/// empty unless `suggest_dev_moves`, because a prod import hidden by an unresolved specifier
+/// would make a runtime dependency look dev-only. The second list holds `devDependencies`
+/// entries that production code imports at runtime; type-only imports do not count.
+pub(crate) fn find_misplaced_dependencies(
+    root: &Path,
+    files: &HashSet<PathBuf>,
+    reachable: &HashSet<PathBuf>,
+    prod_reachable: &HashSet<PathBuf>,
+    modules: &HashMap<PathBuf, ModuleInfo>,
+    resolver: &Resolver,
+    suggest_dev_moves: bool,
+) -> Result<(Vec<MisplacedDependency>, Vec<DevDependencyInProduction>)> {
+    let mut manifests: HashMap<PathBuf, Option<Manifest>> = HashMap::new();
+    let mut usages: BTreeMap<(PathBuf, String), Usage> = BTreeMap::new();
+
+    let config_files = files
+        .iter()
+        .filter(|path| !reachable.contains(*path) && is_common_config_file(path));
+    for file in reachable.iter().chain(config_files) {
+        let Some(module) = modules.get(file) else {
+            continue;
+        };
+        let is_prod = prod_reachable.contains(file);
+        for import in &module.imports {
+            let Some(package) = imported_package(file, &import.specifier, resolver)? else {
+                continue;
+            };
+            if package.starts_with("@types/")
+                || !is_installable_package(&package, resolver)
+                || resolver.is_ambient_module(&import.specifier)
+            {
+                continue;
+            }
+            let Some((dir, kind)) = declaring_manifest(root, file, &package, &mut manifests)?
+            else {
+                continue;
+            };
+            if kind != DepKind::Prod && kind != DepKind::Dev {
+                continue;
+            }
+            let usage = usages.entry((dir, package)).or_insert_with(|| Usage {
+                kind,
+                prod_runtime: BTreeSet::new(),
+                prod_types: BTreeSet::new(),
+                dev: BTreeSet::new(),
+            });
+            let display = relative_display(root, file);
+            if !is_prod {
+                usage.dev.insert(display);
+            } else if import.type_only || is_declaration_file(file) {
+                usage.prod_types.insert(display);
+            } else {
+                usage.prod_runtime.insert(display);
+            }
+        }
+    }
+
+    let mut misplaced = Vec::new();
+    let mut dev_in_production = Vec::new();
+    for ((dir, name), usage) in usages {
+        let package_json = relative_display(root, &dir.join("package.json"));
+        match usage.kind {
+            DepKind::Prod
+                if suggest_dev_moves
+                    && usage.prod_runtime.is_empty()
+                    && usage.prod_types.is_empty()
+                    && !usage.dev.is_empty() =>
+            {
+                misplaced.push(MisplacedDependency {
+                    name,
+                    package_json,
+                    declared_in: "dependencies".to_string(),
+                    move_to: "devDependencies".to_string(),
+                    files: usage.dev.into_iter().collect(),
+                });
+            }
+            DepKind::Dev if !usage.prod_runtime.is_empty() => {
+                dev_in_production.push(DevDependencyInProduction {
+                    name,
+                    package_json,
+                    files: usage.prod_runtime.into_iter().collect(),
+                });
+            }
+            _ => {}
+        }
+    }
+    misplaced.sort_by(|a, b| (&a.name, &a.package_json).cmp(&(&b.name, &b.package_json)));
+    dev_in_production.sort_by(|a, b| (&a.name, &a.package_json).cmp(&(&b.name, &b.package_json)));
+    Ok((misplaced, dev_in_production))
+}
+
+/// The nearest `package.json` from the file's directory up to the root that declares
+/// `package`, with the section it is declared in. `None` when none does, or when the nearest
+/// match is the package itself.
+fn declaring_manifest(
+    root: &Path,
+    file: &Path,
+    package: &str,
+    manifests: &mut HashMap<PathBuf, Option<Manifest>>,
+) -> Result<Option<(PathBuf, DepKind)>> {
+    for dir in file.ancestors().skip(1) {
+        if !dir.starts_with(root) {
+            break;
+        }
+        if !manifests.contains_key(dir) {
+            manifests.insert(dir.to_path_buf(), read_manifest(dir)?);
+        }
+        if let Some(manifest) = &manifests[dir] {
+            if manifest.name.as_deref() == Some(package) {
+                return Ok(None);
+            }
+            if let Some(kind) = manifest.dependencies.get(package) {
+                return Ok(Some((dir.to_path_buf(), *kind)));
+            }
+        }
+    }
+    Ok(None)
+}
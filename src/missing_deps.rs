@@ -0,0 +1,163 @@
+use super::*;
+
+/// Node.js built-in modules, which resolve without the `node:` prefix and need no entry in
+/// `package.json`.
+const NODE_BUILTINS: &[&str] = &[
+    "assert",
+    "async_hooks",
+    "buffer",
+    "child_process",
+    "cluster",
+    "console",
+    "constants",
+    "crypto",
+    "dgram",
+    "diagnostics_channel",
+    "dns",
+    "domain",
+    "events",
+    "fs",
+    "http",
+    "http2",
+    "https",
+    "inspector",
+    "module",
+    "net",
+    "os",
+    "path",
+    "perf_hooks",
+    "process",
+    "punycode",
+    "querystring",
+    "readline",
+    "repl",
+    "stream",
+    "string_decoder",
+    "sys",
+    "timers",
+    "tls",
+    "trace_events",
+    "tty",
+    "url",
+    "util",
+    "v8",
+    "vm",
+    "wasi",
+    "worker_threads",
+    "zlib",
+];
+
+/// Names npm accepts (lowercase, optionally scoped). Bundler aliases such as `~icons/...`,
+/// `$app/...`, or `@/...` fail it and are never reported.
+static NPM_PACKAGE_NAME_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"^(@[a-z0-9][a-z0-9._-]*/)?[a-z0-9][a-z0-9._-]*$").unwrap());
+
+/// Dependencies and name of one `package.json`.
+pub(crate) struct Manifest {
+    pub(crate) name: Option<String>,
+    pub(crate) dependencies: HashMap<String, DepKind>,
+}
+
+/// Packages imported by reachable files but declared in no `package.json` between the
+/// importing file and the root; `@types/<name>` counts as declaring `<name>`. Node built-ins,
+/// `scheme:` imports, workspace packages, and modules covered by a `declare module` are
+/// skipped, as are files with no `package.json` above them.
+pub(crate) fn find_missing_dependencies(
+    root: &Path,
+    reachable: &HashSet<PathBuf>,
+    modules: &HashMap<PathBuf, ModuleInfo>,
+    resolver: &Resolver,
+) -> Result<Vec<MissingDependency>> {
+    let mut manifests: HashMap<PathBuf, Option<Manifest>> = HashMap::new();
+    let mut missing: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+
+    for file in reachable {
+        let Some(module) = modules.get(file) else {
+            continue;
+        };
+        for import in &module.imports {
+            let Some(package) = imported_package(file, &import.specifier, resolver)? else {
+                continue;
+            };
+            if !is_installable_package(&package, resolver)
+                || resolver.is_ambient_module(&import.specifier)
+            {
+                continue;
+            }
+            if is_declared_for(root, file, &package, &mut manifests)? == Some(false) {
+                missing
+                    .entry(package)
+                    .or_default()
+                    .insert(relative_display(root, file));
+            }
+        }
+    }
+
+    Ok(missing
+        .into_iter()
+        .map(|(name, files)| MissingDependency {
+            name,
+            files: files.into_iter().collect(),
+        })
+        .collect())
+}
+
+pub(crate) fn is_installable_package(package: &str, resolver: &Resolver) -> bool {
+    NPM_PACKAGE_NAME_RE.is_match(package)
+        && !NODE_BUILTINS.contains(&package)
+        && !resolver
+            .workspace_packages
+            .iter()
+            .any(|workspace| workspace.name == package)
+}
+
+/// Whether a `package.json` from the file's directory up to the root declares `package`, or
+/// is the package itself. `None` when there is no `package.json` on the way.
+fn is_declared_for(
+    root: &Path,
+    file: &Path,
+    package: &str,
+    manifests: &mut HashMap<PathBuf, Option<Manifest>>,
+) -> Result<Option<bool>> {
+    let types_package = match package.strip_prefix('@') {
+        Some(scoped) => format!("@types/{}", scoped.replacen('/', "__", 1)),
+        None => format!("@types/{package}"),
+    };
+    let mut found_manifest = false;
+    for dir in file.ancestors().skip(1) {
+        if !dir.starts_with(root) {
+            break;
+        }
+        if !manifests.contains_key(dir) {
+            manifests.insert(dir.to_path_buf(), read_manifest(dir)?);
+        }
+        if let Some(manifest) = &manifests[dir] {
+            found_manifest = true;
+            if manifest.name.as_deref() == Some(package)
+                || manifest.dependencies.contains_key(package)
+                || manifest.dependencies.contains_key(&types_package)
+            {
+                return Ok(Some(true));
+            }
+        }
+    }
+    Ok(found_manifest.then_some(false))
+}
+
+pub(crate) fn read_manifest(dir: &Path) -> Result<Option<Manifest>> {
+    let path = dir.join("package.json");
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let raw = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", shown_path(&path)))?;
+    let value: serde_json::Value = serde_json::from_str(&raw)
+        .with_context(|| format!("Failed to parse {}", shown_path(&path)))?;
+    Ok(Some(Manifest {
+        name: value
+            .get("name")
+            .and_then(|name| name.as_str())
+            .map(str::to_string),
+        dependencies: declared_dependencies(&value),
+    }))
+}
@@ -0,0 +1,167 @@
+use super::*;
+use crate::config_aliases::{
+    AliasExtraction, balanced_block, eval_path_expr, find_config_files, has_js_config_extension,
+    push_alias_rules, split_key_value, split_top_level,
+};
+
+static SRC_DIR_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\bsrcDir['"]?\s*:\s*"#).unwrap());
+static NUXT_ALIAS_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r#"\balias['"]?\s*:\s*\{"#).unwrap());
+
+/// Folders of the source dir whose files Nuxt loads or auto-imports everywhere in them.
+const NUXT_TREE_DIRS: &[&str] = &["pages", "layouts", "components"];
+/// Folders of the source dir where Nuxt loads or auto-imports only top-level files and
+/// `<name>/index.*`.
+const NUXT_FLAT_DIRS: &[&str] = &["plugins", "middleware", "composables", "utils"];
+/// Folders of `server/` that Nitro turns into handlers, middleware, or plugins, or
+/// auto-imports (`utils/`).
+const NUXT_SERVER_DIRS: &[&str] = &["api", "routes", "middleware", "plugins", "utils"];
+/// Folders of the root that Nuxt 4 auto-imports in both the app and the server, like
+/// [`NUXT_FLAT_DIRS`].
+const NUXT_SHARED_DIRS: &[&str] = &["shared/utils", "shared/types"];
+
+/// The `nuxt.config.*` files of `root`. A folder without one is not a Nuxt app.
+fn nuxt_configs(root: &Path) -> Vec<PathBuf> {
+    find_config_files(root, &[""], |name| {
+        name.starts_with("nuxt.config.") && has_js_config_extension(name)
+    })
+}
+
+/// Where a Nuxt app in `root` keeps its sources: `srcDir` of `nuxt.config.*`, else `app/` when
+/// it exists (the Nuxt 4 layout), else `root` itself. `None` without a `nuxt.config.*`.
+fn nuxt_src_dir(root: &Path) -> Option<PathBuf> {
+    let configs = nuxt_configs(root);
+    let config_path = configs.first()?;
+    let source = strip_comments(&fs::read_to_string(config_path).unwrap_or_default());
+    let configured = SRC_DIR_RE.find(&source).and_then(|m| {
+        let value = source[m.end()..]
+            .split([',', '\n', '}'])
+            .next()
+            .unwrap_or_default();
+        eval_path_expr(value, root, root, false)
+    });
+    Some(match configured {
+        Some(dir) => normalize_path(dir),
+        None if root.join("app").is_dir() => root.join("app"),
+        None => root.to_path_buf(),
+    })
+}
+
+/// Files Nuxt registers or auto-imports by where they live, which nothing imports: everything
+/// under `pages/`, `layouts/`, and `components/` of the source dir, the top-level files (or
+/// `<name>/index.*`) of `plugins/`, `middleware/`, `composables/`, and `utils/` and of the
+/// root's `shared/utils/` and `shared/types/`, and the `api/`, `routes/`, `middleware/`,
+/// `plugins/`, and `utils/` of `server/`, in the source dir or in `root`. Vue components are
+/// not parsed as modules, so every source file a `.vue` file in the source dir imports is an
+/// entry too.
+pub(crate) fn nuxt_entries(
+    root: &Path,
+    files: &HashSet<PathBuf>,
+    resolver: &Resolver,
+) -> Result<Vec<PathBuf>> {
+    let Some(src_dir) = nuxt_src_dir(root) else {
+        return Ok(Vec::new());
+    };
+    let mut server_dirs = vec![root.join("server"), src_dir.join("server")];
+    server_dirs.dedup();
+    let mut out: Vec<PathBuf> = files
+        .iter()
+        .filter(|file| {
+            let in_tree = |dir: &Path| {
+                file.strip_prefix(dir)
+                    .ok()
+                    .map(|rel| rel.components().count())
+            };
+            let in_flat = |dir: &Path| match in_tree(dir) {
+                Some(1) => true,
+                Some(2) => file.file_stem().is_some_and(|stem| stem == "index"),
+                _ => false,
+            };
+            NUXT_TREE_DIRS
+                .iter()
+                .any(|dir| in_tree(&src_dir.join(dir)).is_some())
+                || NUXT_FLAT_DIRS.iter().any(|dir| in_flat(&src_dir.join(dir)))
+                || NUXT_SHARED_DIRS.iter().any(|dir| in_flat(&root.join(dir)))
+                || server_dirs.iter().any(|server| {
+                    NUXT_SERVER_DIRS
+                        .iter()
+                        .any(|dir| in_tree(&server.join(dir)).is_some())
+                })
+        })
+        .cloned()
+        .collect();
+    for component in files_with_extension(&src_dir, "vue") {
+        out.extend(component_imports(&component, resolver)?);
+    }
+    out.sort();
+    out.dedup();
+    for path in &out {
+        debug!(entry = %relative_display(root, path), "nuxt entry");
+    }
+    Ok(out)
+}
+
+/// Registers the aliases Nuxt sets up in `.nuxt/tsconfig.json`, which is generated and often
+/// missing: `~` and `@` for the source dir, `~~` and `@@` for `root`, and `assets` and
+/// `public` in the source dir, plus each static entry of `alias` in `nuxt.config.*`.
+pub(crate) fn apply_nuxt_aliases(root: &Path, resolver: &mut Resolver) -> Result<()> {
+    let Some(src_dir) = nuxt_src_dir(root) else {
+        return Ok(());
+    };
+    let mut extraction = AliasExtraction::default();
+    for config_path in nuxt_configs(root) {
+        let source = strip_comments(&fs::read_to_string(&config_path).unwrap_or_default());
+        for m in NUXT_ALIAS_RE.find_iter(&source) {
+            let Some(body) = balanced_block(&source, m.end() - 1) else {
+                continue;
+            };
+            for (key, value) in split_top_level(body)
+                .into_iter()
+                .filter_map(split_key_value)
+            {
+                let target = match plain_nuxt_alias_target(value) {
+                    Some(prefixed) => Some(nuxt_alias_base(prefixed, root, &src_dir)),
+                    None => eval_path_expr(value, root, root, false),
+                };
+                match target {
+                    Some(target) => extraction.aliases.push((key, target)),
+                    None => extraction.skipped += 1,
+                }
+            }
+        }
+    }
+    extraction.aliases.extend([
+        ("~~".to_string(), root.to_path_buf()),
+        ("@@".to_string(), root.to_path_buf()),
+        ("~".to_string(), src_dir.clone()),
+        ("@".to_string(), src_dir.clone()),
+        ("assets".to_string(), src_dir.join("assets")),
+        ("public".to_string(), src_dir.join("public")),
+    ]);
+    push_alias_rules(resolver, &extraction);
+    Ok(())
+}
+
+/// An alias value written with Nuxt's own prefixes, e.g. `~/utils` or `~~/shared`, unquoted.
+fn plain_nuxt_alias_target(value: &str) -> Option<&str> {
+    let value = value
+        .trim()
+        .trim_matches(|c| c == '\'' || c == '"' || c == '`');
+    ["~~", "@@", "~", "@"]
+        .iter()
+        .any(|prefix| value.starts_with(&format!("{prefix}/")) || value == *prefix)
+        .then_some(value)
+}
+
+/// `~/x` and `@/x` in the source dir, `~~/x` and `@@/x` in `root`.
+fn nuxt_alias_base(value: &str, root: &Path, src_dir: &Path) -> PathBuf {
+    let (base, rest) = match value.split_once('/') {
+        Some((prefix, rest)) => (prefix, rest),
+        None => (value, ""),
+    };
+    let dir = if base == "~~" || base == "@@" {
+        root
+    } else {
+        src_dir
+    };
+    normalize_path(dir.join(rest))
+}
@@ -1,22 +1,33 @@
 use super::*;
-use crossterm::event::{self, Event, KeyCode, KeyEventKind};
-use crossterm::execute;
-use crossterm::terminal::{
-    EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode,
+use crate::findings::{
+    BROKEN_ASSET_REFERENCE, BROKEN_IMPORT, COMMENTED_CODE, DEV_DEPENDENCY_IN_PRODUCTION,
+    DUPLICATE_ASSET, MISPLACED_DEPENDENCY, MISSING_DEPENDENCY, ORPHANED_TYPES_PACKAGE,
+    STORYBOOK_ONLY_EXPORT, STORYBOOK_ONLY_FILE, TOOLING_ONLY_FILE, UNDECLARED_ENV_VAR,
+    UNUSED_ASSET, UNUSED_DEPENDENCY, UNUSED_ENV_VAR, UNUSED_EXPORT, UNUSED_FILE, UNUSED_SCRIPT,
 };
+#[cfg(feature = "tui")]
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use once_cell::sync::OnceCell;
+#[cfg(feature = "tui")]
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph, Wrap};
+#[cfg(feature = "tui")]
+use ratatui::widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap};
 use regex::RegexBuilder;
+#[cfg(feature = "tui")]
 use std::io;
 use std::path::{Path, PathBuf};
+#[cfg(feature = "tui")]
 use std::time::Duration;
 use std::time::{SystemTime, UNIX_EPOCH};
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 use walkdir::WalkDir;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum TuiPage {
     Summary,
     Delete,
+    CommentedCode,
+    Dependencies,
 }
 
 #[derive(Debug, Clone)]
@@ -66,16 +77,45 @@ struct DeleteState {
     confirm_empty_trash: bool,
     confirm_restore_previous: bool,
     confirm_restore_all: bool,
+    /// Paths the pending confirmation will touch, listed in the confirm dialog.
+    confirm_paths: Vec<String>,
+    confirm_scroll: usize,
+    /// Rows of `confirm_paths` the confirm dialog showed when last drawn, so scrolling stops
+    /// once the last path is in view.
+    confirm_visible: std::cell::Cell<usize>,
+    on_conflict: OnConflict,
     filter: DeleteFilter,
     search_query: String,
     search_input: String,
     editing_search: bool,
+    /// Folder the candidate list is limited to, picked in the folder sidebar (`t`).
+    scope: Option<String>,
+    show_folders: bool,
+    folder_cursor: usize,
+    /// Sidebar folders whose subfolders are listed.
+    expanded_folders: BTreeSet<String>,
+    show_detail: bool,
+    range_anchor: Option<usize>,
+    editing_session_name: bool,
+    session_name_input: String,
+    pending_session_name: String,
+    trashed_sessions: BTreeMap<String, String>,
+    session_names: BTreeMap<String, String>,
     message: String,
     root: PathBuf,
     trash_root: PathBuf,
+    trash: TrashOptions,
     undo_stack: Vec<Vec<DeletedEntry>>,
 }
 
+/// How the delete page disposes of files: `--trash` and `--trash-max-age`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TrashOptions {
+    pub(crate) mode: TrashMode,
+    /// Sessions in `.haadi_trash` older than this are emptied before the next batch moves in.
+    pub(crate) max_age: Option<std::time::Duration>,
+}
+
 #[derive(Debug, Clone)]
 struct DeletedEntry {
     candidate: DeleteCandidate,
@@ -87,6 +127,8 @@ struct DeletedEntry {
 struct DeleteLogRecord {
     action: &'static str,
     batch_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    session_name: Option<String>,
     kind: String,
     rel_path: String,
     original_abs: String,
@@ -94,20 +136,197 @@ struct DeleteLogRecord {
     ts_unix_ms: u128,
 }
 
+/// One folder in the delete page's folder sidebar. The root row has an empty path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FolderRow {
+    path: String,
+    depth: usize,
+    candidates: usize,
+    has_children: bool,
+}
+
+/// Per-session metadata stored in `.haadi_trash/meta/<batch_id>.json`, kept outside the session
+/// directory so restores never treat it as a trashed file.
+#[derive(Debug, Serialize, Deserialize)]
+struct SessionMeta {
+    batch_id: String,
+    name: String,
+    created_unix_ms: u128,
+    files: usize,
+}
+
 #[derive(Debug)]
 struct TuiState {
     page: TuiPage,
     delete: DeleteState,
+    show_help: bool,
+    /// Highlighted row of the commented-out code page.
+    code_cursor: usize,
+    code_message: String,
+    /// Block to open in the editor; the loop suspends the TUI around it.
+    open_in_editor: Option<(String, usize)>,
+    /// Highlighted row of the dependencies page.
+    dependency_cursor: usize,
+    /// The highlighted dependency's row and [`dependency_why_lines`], which read the
+    /// importing files, so they are built once per selection rather than every frame.
+    dependency_details: Option<(usize, Vec<String>)>,
 }
 
+/// Keybindings listed by the `?` help overlay, per page.
+const SUMMARY_KEYS: &[(&str, &str)] = &[
+    ("d", "open the delete page"),
+    ("c", "list commented-out code blocks (--commented-code)"),
+    ("u", "show which files import each dependency, and how"),
+    ("?", "show or hide this help"),
+    ("q / Esc", "quit"),
+];
+
+const DEPENDENCY_KEYS: &[(&str, &str)] = &[
+    ("j / k / arrows", "move"),
+    ("b / Esc", "back to the summary page"),
+    ("?", "show or hide this help"),
+    ("q", "quit"),
+];
+
+const COMMENTED_CODE_KEYS: &[(&str, &str)] = &[
+    ("j / k / arrows", "move"),
+    (
+        "Enter / o",
+        "open the block in $VISUAL / $EDITOR (vi by default), then come back",
+    ),
+    ("b / Esc", "back to the summary page"),
+    ("?", "show or hide this help"),
+    ("q", "quit"),
+];
+
+const DELETE_KEYS: &[(&str, &str)] = &[
+    ("j / k / arrows", "move"),
+    ("space / Enter", "select or unselect the highlighted item"),
+    (
+        "V",
+        "start a range at the cursor; space toggles it, Esc cancels",
+    ),
+    ("a / c", "select all listed items / clear the selection"),
+    ("f", "cycle the filter: all, files, assets"),
+    (
+        "/",
+        "search paths: substring, wildcards (* ?), or re:<pattern>",
+    ),
+    (
+        "t",
+        "folder sidebar: j/k limit the list to a folder, l/h expand/collapse, t/Enter/Esc close",
+    ),
+    ("g", "reset filter, search, and folder"),
+    ("p", "show details of the highlighted item"),
+    ("x", "delete selected items (y approves, m names the batch)"),
+    ("u", "undo the last delete batch"),
+    (
+        "i",
+        "restore selected deleted rows, or the file matching the search",
+    ),
+    ("o", "restore the folder matching the search"),
+    ("r / R", "restore the previous trash session / all sessions"),
+    (
+        "C",
+        "when a restored path exists again: skip, overwrite, or restore alongside",
+    ),
+    ("z", "empty the trash"),
+    ("b / Esc", "back to the summary page"),
+    ("?", "show or hide this help"),
+    ("q", "quit"),
+];
+
 pub(crate) fn relative_display(root: &Path, path: &Path) -> String {
-    path.strip_prefix(root)
-        .unwrap_or(path)
-        .display()
-        .to_string()
+    match path.strip_prefix(root) {
+        Ok(rel) => rel.display().to_string(),
+        Err(_) => shown_path(path),
+    }
+}
+
+/// The root `--redact-paths` keeps out of outputs, set once by the CLI.
+static REDACTED_ROOT: OnceCell<PathBuf> = OnceCell::new();
+
+/// `--redact-paths`: from now on, [`shown_path`] and [`redact_report`] hide where `root` is.
+pub(crate) fn redact_paths_under(root: &Path) {
+    let _ = REDACTED_ROOT.set(root.to_path_buf());
+}
+
+/// `path` as logs and messages show it: as is, or under `--redact-paths` relative to the root
+/// (`.` for the root itself). Other absolute paths keep only what cannot identify the
+/// machine: `~/...` inside the home directory, else `.../<file name>`.
+pub(crate) fn shown_path(path: &Path) -> String {
+    let Some(root) = REDACTED_ROOT.get() else {
+        return path.display().to_string();
+    };
+    if let Ok(rel) = path.strip_prefix(root) {
+        return match rel.as_os_str().is_empty() {
+            true => ".".to_string(),
+            false => rel.display().to_string(),
+        };
+    }
+    if !path.is_absolute() {
+        return path.display().to_string();
+    }
+    if let Some(home) = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"))
+        && let Ok(rel) = path.strip_prefix(home)
+    {
+        return Path::new("~").join(rel).display().to_string();
+    }
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    format!(".../{name}")
+}
+
+/// Under `--redact-paths`, replaces the absolute root in `report` with `.`: its `root`, the
+/// warnings that name it, and the `--compare` baseline path.
+pub(crate) fn redact_report(report: &mut Report) {
+    let Some(root) = REDACTED_ROOT.get() else {
+        return;
+    };
+    let shown = root.display().to_string();
+    let nested = format!("{shown}{}", std::path::MAIN_SEPARATOR);
+    report.root = ".".to_string();
+    for warning in &mut report.warnings {
+        *warning = warning.replace(&nested, "").replace(&shown, ".");
+    }
+    if let Some(comparison) = &mut report.comparison {
+        comparison.baseline = shown_path(Path::new(&comparison.baseline));
+    }
+}
+
+/// Under `--redact-paths`, the JSON of a `--stop-after` artifact with every absolute path, in
+/// values and in keys alike, shown as [`shown_path`] shows it.
+pub(crate) fn redact_json(value: &mut serde_json::Value) {
+    if REDACTED_ROOT.get().is_none() {
+        return;
+    }
+    match value {
+        serde_json::Value::String(text) => *text = redact_text(text),
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_json),
+        serde_json::Value::Object(map) => {
+            *map = std::mem::take(map)
+                .into_iter()
+                .map(|(key, mut item)| {
+                    redact_json(&mut item);
+                    (redact_text(&key), item)
+                })
+                .collect();
+        }
+        _ => {}
+    }
+}
+
+fn redact_text(text: &str) -> String {
+    if Path::new(text).is_absolute() {
+        shown_path(Path::new(text))
+    } else {
+        text.to_string()
+    }
 }
 
-pub(crate) fn print_human_report(report: &Report) {
+/// Items listed per human-output section unless `--verbose-findings` is set.
+const HUMAN_LIST_LIMIT: usize = 20;
+
+pub(crate) fn print_human_report(report: &Report, verbose: bool) {
     println!("Root: {}", report.root);
     println!("\nSummary:");
     println!(
@@ -136,6 +355,18 @@ pub(crate) fn print_human_report(report: &Report) {
         report.summary.omitted_risky_findings
     );
     println!("  - Unused files: {}", report.summary.unused_files_count);
+    if report.summary.tooling_only_files_count > 0 {
+        println!(
+            "  - Only used by tooling: {}",
+            report.summary.tooling_only_files_count
+        );
+    }
+    if report.summary.storybook_only_files_count > 0 {
+        println!(
+            "  - Only used by Storybook: {}",
+            report.summary.storybook_only_files_count
+        );
+    }
     println!("  - Used assets: {}", report.summary.used_assets_count);
     println!("  - Unused assets: {}", report.summary.unused_assets_count);
     println!(
@@ -150,14 +381,121 @@ pub(crate) fn print_human_report(report: &Report) {
         "  - Unused exports: {}",
         report.summary.unused_exports_count
     );
+    if report.summary.storybook_only_exports_count > 0 {
+        println!(
+            "  - Exports only used by Storybook: {}",
+            report.summary.storybook_only_exports_count
+        );
+    }
+    if report.summary.missing_dependencies_count > 0 {
+        println!(
+            "  - Missing dependencies: {}",
+            report.summary.missing_dependencies_count
+        );
+    }
+    if report.summary.orphaned_types_packages_count > 0 {
+        println!(
+            "  - Orphaned @types packages: {}",
+            report.summary.orphaned_types_packages_count
+        );
+    }
+    if report.summary.dev_dependencies_in_production_count > 0 {
+        println!(
+            "  - Dev dependencies used in production: {}",
+            report.summary.dev_dependencies_in_production_count
+        );
+    }
+    if report.summary.misplaced_dependencies_count > 0 {
+        println!(
+            "  - Misplaced dependencies: {}",
+            report.summary.misplaced_dependencies_count
+        );
+    }
+    if report.summary.broken_imports_count > 0 {
+        println!(
+            "  - Broken imports: {}",
+            report.summary.broken_imports_count
+        );
+    }
+    if report.summary.broken_asset_references_count > 0 {
+        println!(
+            "  - Broken asset references: {}",
+            report.summary.broken_asset_references_count
+        );
+    }
+    if report.summary.commented_out_imports_count > 0 {
+        println!(
+            "  - Commented-out imports: {}",
+            report.summary.commented_out_imports_count
+        );
+    }
+    if report.summary.unused_scripts_count > 0 {
+        println!(
+            "  - Unused scripts: {}",
+            report.summary.unused_scripts_count
+        );
+    }
+    if report.summary.commented_code_count > 0 {
+        println!(
+            "  - Commented-out code blocks: {}",
+            report.summary.commented_code_count
+        );
+    }
+    if report.summary.duplicate_assets_count > 0 {
+        println!(
+            "  - Duplicate assets: {}",
+            report.summary.duplicate_assets_count
+        );
+    }
+    if let Some(timing) = &report.summary.timing {
+        println!("\nTiming:");
+        for (phase, ms) in [
+            ("scan", timing.scan_ms),
+            ("parse", timing.parse_ms),
+            ("resolve", timing.resolve_ms),
+            ("reachability", timing.reachability_ms),
+            ("assets", timing.assets_ms),
+            ("exports", timing.exports_ms),
+            ("other", timing.other_ms),
+        ] {
+            if phase == "parse" && timing.parsed_bytes > 0 && ms > 0.0 {
+                let mb_per_s = timing.parsed_bytes as f64 / 1_048_576.0 / (ms / 1000.0);
+                println!("  - {phase:<13} {ms:>9.1} ms ({mb_per_s:.1} MB/s)");
+            } else {
+                println!("  - {phase:<13} {ms:>9.1} ms");
+            }
+        }
+        println!("  - {:<13} {:>9.1} ms", "total", timing.total_ms);
+    }
+
+    if !report.packages.is_empty() {
+        println!("\nPackages ({}):", report.packages.len());
+        for package in &report.packages {
+            let summary = &package.summary;
+            println!("  - {} ({})", package.name, package.dir);
+            println!(
+                "      files: {} | reachable: {} | entries: {}",
+                summary.total_source_files, summary.total_reachable_files, summary.total_entries
+            );
+            println!(
+                "      unused files: {} | unused assets: {} | unused deps: {} | unused exports: {}",
+                summary.unused_files_count,
+                summary.unused_assets_count,
+                summary.unused_dependencies_count,
+                summary.unused_exports_count
+            );
+            for dep in &package.unused_dependencies {
+                println!("      - unused dependency: {dep}");
+            }
+        }
+        println!();
+    }
 
     if report.entries.is_empty() {
         println!("Entries: (none detected)");
     } else {
         println!("Entries:");
-        for entry in &report.entries {
-            println!("  - {entry}");
-        }
+        print_limited(&report.entries, verbose, |entry| println!("  - {entry}"));
     }
 
     if !report.warnings.is_empty() {
@@ -168,26 +506,314 @@ pub(crate) fn print_human_report(report: &Report) {
     }
 
     println!("\nUnused files ({}):", report.unused_files.len());
-    for path in &report.unused_files {
-        println!("  - {path}");
+    print_limited(&report.unused_files, verbose, |path| {
+        println!("  - {path}{}", id_suffix(verbose, UNUSED_FILE, path, None))
+    });
+
+    if !report.tooling_only_files.is_empty() {
+        println!(
+            "\nOnly used by tooling ({}): reachable only from tests, package.json scripts, or CI/git hook scripts",
+            report.tooling_only_files.len()
+        );
+        print_limited(&report.tooling_only_files, verbose, |path| {
+            println!(
+                "  - {path}{}",
+                id_suffix(verbose, TOOLING_ONLY_FILE, path, None)
+            )
+        });
     }
 
-    println!("\nUsed assets ({}):", report.used_assets.len());
-    for path in &report.used_assets {
-        println!("  - {path}");
+    if !report.storybook_only_files.is_empty() {
+        println!(
+            "\nOnly used by Storybook ({}): reachable only from stories",
+            report.storybook_only_files.len()
+        );
+        print_limited(&report.storybook_only_files, verbose, |path| {
+            println!(
+                "  - {path}{}",
+                id_suffix(verbose, STORYBOOK_ONLY_FILE, path, None)
+            )
+        });
     }
 
+    println!("\nUsed assets ({}):", report.used_assets.len());
+    print_limited(&report.used_assets, verbose, |path| println!("  - {path}"));
+
     println!("\nUnused assets ({}):", report.unused_assets.len());
-    for path in &report.unused_assets {
-        println!("  - {path}");
+    print_limited(&report.unused_assets, verbose, |path| {
+        println!("  - {path}{}", id_suffix(verbose, UNUSED_ASSET, path, None))
+    });
+
+    if !report.route_groups.is_empty() {
+        println!("\nUnused code by route ({}):", report.route_groups.len());
+        print_limited(&report.route_groups, verbose, |group| {
+            let mut parts = vec![format!(
+                "{} of {} files",
+                group.unused_files.len(),
+                group.total_files
+            )];
+            if !group.unused_assets.is_empty() {
+                parts.push(format!(
+                    "{} of {} assets",
+                    group.unused_assets.len(),
+                    group.total_assets
+                ));
+            }
+            println!("  - {}/**: {} unused", group.route, parts.join(", "));
+        });
     }
 
     println!(
         "\nUnused dependencies ({}):",
         report.unused_dependencies.len()
     );
-    for dep in &report.unused_dependencies {
-        println!("  - {dep}");
+    print_limited(&report.unused_dependencies, verbose, |dep| {
+        let note = report
+            .dependency_usage
+            .iter()
+            .find(|usage| usage.name == *dep)
+            .and_then(|usage| usage.note.as_deref());
+        println!(
+            "  - {dep}{}{}",
+            note.map(|note| format!(": {note}")).unwrap_or_default(),
+            id_suffix(verbose, UNUSED_DEPENDENCY, dep, None)
+        )
+    });
+
+    if !report.kept_dependencies.is_empty() {
+        println!("\nKept dependencies ({}):", report.kept_dependencies.len());
+        print_limited(&report.kept_dependencies, verbose, |keep| {
+            println!(
+                "  - {} ({}{}){}",
+                keep.name,
+                keep.source,
+                if keep.suppressed {
+                    ", otherwise unused"
+                } else {
+                    ""
+                },
+                keep.reason
+                    .as_ref()
+                    .map(|reason| format!(": {reason}"))
+                    .unwrap_or_default()
+            )
+        });
+    }
+
+    let used: Vec<&DependencyUsage> = report
+        .dependency_usage
+        .iter()
+        .filter(|usage| usage.used)
+        .collect();
+    if !used.is_empty() {
+        println!("\nUsed dependencies ({}):", used.len());
+        print_limited(&used, verbose, |usage| {
+            println!("  - {}: {}", usage.name, dependency_uses(usage, verbose))
+        });
+    }
+
+    if !report.orphaned_types_packages.is_empty() {
+        println!(
+            "\nOrphaned @types packages ({}): the typed package is neither declared nor imported",
+            report.orphaned_types_packages.len()
+        );
+        print_limited(&report.orphaned_types_packages, verbose, |name| {
+            println!(
+                "  - {name}{}",
+                id_suffix(verbose, ORPHANED_TYPES_PACKAGE, name, None)
+            )
+        });
+    }
+
+    if !report.missing_dependencies.is_empty() {
+        println!(
+            "\nMissing dependencies ({}): imported but not declared in package.json",
+            report.missing_dependencies.len()
+        );
+        print_limited(&report.missing_dependencies, verbose, |item| {
+            println!(
+                "  - {} (imported by {}){}",
+                item.name,
+                item.files.join(", "),
+                id_suffix(verbose, MISSING_DEPENDENCY, &item.name, None)
+            )
+        });
+    }
+
+    if !report.dev_dependencies_in_production.is_empty() {
+        println!(
+            "\nDev dependencies used in production ({}): a production install leaves them out",
+            report.dev_dependencies_in_production.len()
+        );
+        print_limited(&report.dev_dependencies_in_production, verbose, |item| {
+            let manifest = if item.package_json == "package.json" {
+                String::new()
+            } else {
+                format!(" ({})", item.package_json)
+            };
+            println!(
+                "  - {}{manifest} (imported by {}){}",
+                item.name,
+                item.files.join(", "),
+                id_suffix(verbose, DEV_DEPENDENCY_IN_PRODUCTION, &item.name, None)
+            )
+        });
+    }
+
+    if !report.misplaced_dependencies.is_empty() {
+        println!(
+            "\nMisplaced dependencies ({}):",
+            report.misplaced_dependencies.len()
+        );
+        print_limited(&report.misplaced_dependencies, verbose, |item| {
+            let manifest = if item.package_json == "package.json" {
+                String::new()
+            } else {
+                format!(" in {}", item.package_json)
+            };
+            println!(
+                "  - {}{manifest}: move from {} to {} (only imported by dev files: {}){}",
+                item.name,
+                item.declared_in,
+                item.move_to,
+                item.files.join(", "),
+                id_suffix(verbose, MISPLACED_DEPENDENCY, &item.name, None)
+            )
+        });
+    }
+
+    if !report.broken_imports.is_empty() {
+        println!("\nBroken imports ({}):", report.broken_imports.len());
+        print_limited(&report.broken_imports, verbose, |item| {
+            let hint = match &item.suggestion {
+                Some(path) => format!(" (did you mean {path}?)"),
+                None => String::new(),
+            };
+            println!(
+                "  - {} imports missing {}{hint}{}",
+                item.file,
+                item.specifier,
+                id_suffix(verbose, BROKEN_IMPORT, &item.file, Some(&item.specifier))
+            )
+        });
+    }
+
+    if !report.broken_asset_references.is_empty() {
+        println!(
+            "\nBroken asset references ({}):",
+            report.broken_asset_references.len()
+        );
+        print_limited(&report.broken_asset_references, verbose, |item| {
+            println!(
+                "  - {} imports missing {}{}",
+                item.file,
+                item.specifier,
+                id_suffix(
+                    verbose,
+                    BROKEN_ASSET_REFERENCE,
+                    &item.file,
+                    Some(&item.specifier)
+                )
+            )
+        });
+    }
+
+    if !report.commented_out_imports.is_empty() {
+        println!(
+            "\nCommented-out imports ({}, informational):",
+            report.commented_out_imports.len()
+        );
+        print_limited(&report.commented_out_imports, verbose, |item| {
+            println!("  - {}:{} imports {}", item.file, item.line, item.specifier)
+        });
+    }
+
+    if !report.unused_scripts.is_empty() {
+        println!("\nUnused scripts ({}):", report.unused_scripts.len());
+        print_limited(&report.unused_scripts, verbose, |item| {
+            println!(
+                "  - {}: {} ({}){}",
+                item.package_json,
+                item.name,
+                item.command,
+                id_suffix(verbose, UNUSED_SCRIPT, &item.package_json, Some(&item.name))
+            )
+        });
+    }
+
+    if !report.commented_code.is_empty() {
+        println!("\nCommented-out code ({}):", report.commented_code.len());
+        print_limited(&report.commented_code, verbose, |item| {
+            println!(
+                "  - {}:{}-{}: {}{}",
+                item.file,
+                item.start_line,
+                item.end_line,
+                item.statement,
+                id_suffix(verbose, COMMENTED_CODE, &item.file, Some(&item.statement))
+            )
+        });
+    }
+
+    if !report.duplicate_assets.is_empty() {
+        println!("\nDuplicate assets ({}):", report.duplicate_assets.len());
+        print_limited(&report.duplicate_assets, verbose, |item| {
+            println!(
+                "  - {} (same as {}/{}){}",
+                item.file,
+                item.package,
+                item.package_file,
+                id_suffix(verbose, DUPLICATE_ASSET, &item.file, None)
+            )
+        });
+    }
+
+    if !report.unused_licensed_assets.is_empty() {
+        println!(
+            "\nUnused assets with attribution ({}): deleting them may also end these obligations",
+            report.unused_licensed_assets.len()
+        );
+        print_limited(&report.unused_licensed_assets, verbose, |item| {
+            println!(
+                "  - {} (license: {})",
+                item.file,
+                item.license_files.join(", ")
+            )
+        });
+    }
+
+    if !report.unlicensed_used_assets.is_empty() {
+        println!(
+            "\nUsed assets without attribution ({}):",
+            report.unlicensed_used_assets.len()
+        );
+        print_limited(&report.unlicensed_used_assets, verbose, |path| {
+            println!("  - {path}")
+        });
+    }
+
+    if !report.unused_env_vars.is_empty() || !report.undeclared_env_vars.is_empty() {
+        println!("\nUnused env vars ({}):", report.unused_env_vars.len());
+        print_limited(&report.unused_env_vars, verbose, |item| {
+            println!(
+                "  - {} ({}){}",
+                item.name,
+                item.env_files.join(", "),
+                id_suffix(verbose, UNUSED_ENV_VAR, &item.name, None)
+            )
+        });
+        println!(
+            "\nUndeclared env vars ({}):",
+            report.undeclared_env_vars.len()
+        );
+        print_limited(&report.undeclared_env_vars, verbose, |item| {
+            println!(
+                "  - {} (read in {}){}",
+                item.name,
+                item.files.join(", "),
+                id_suffix(verbose, UNDECLARED_ENV_VAR, &item.name, None)
+            )
+        });
     }
 
     let mut grouped: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
@@ -199,25 +825,332 @@ pub(crate) fn print_human_report(report: &Report) {
     }
 
     println!("\nUnused exports ({}):", report.unused_exports.len());
-    for (file, exports) in grouped {
+    let grouped: Vec<(&str, Vec<&str>)> = grouped.into_iter().collect();
+    print_limited(&grouped, verbose, |(file, exports)| {
         println!("  - {file}");
         for export in exports {
-            println!("      - {export}");
+            println!(
+                "      - {export}{}",
+                id_suffix(verbose, UNUSED_EXPORT, file, Some(export))
+            );
         }
+    });
+
+    if !report.storybook_only_exports.is_empty() {
+        println!(
+            "\nExports only used by Storybook ({}):",
+            report.storybook_only_exports.len()
+        );
+        print_limited(&report.storybook_only_exports, verbose, |item| {
+            println!(
+                "  - {}: {}{}",
+                item.file,
+                item.export,
+                id_suffix(
+                    verbose,
+                    STORYBOOK_ONLY_EXPORT,
+                    &item.file,
+                    Some(&item.export)
+                )
+            )
+        });
+    }
+
+    if !report.import_costs.is_empty() {
+        println!("\nHeaviest importers ({}):", report.import_costs.len());
+        print_limited(&report.import_costs, verbose, |cost| {
+            println!(
+                "  - {} ({} files, {} transitively; {} direct imports)",
+                cost.file,
+                cost.transitive_files,
+                format_bytes(cost.transitive_bytes),
+                cost.direct_imports
+            )
+        });
+    }
+
+    if !report.entry_reachability.is_empty() {
+        println!(
+            "\nReachability by entry ({}):",
+            report.entry_reachability.len()
+        );
+        print_limited(&report.entry_reachability, verbose, |item| {
+            println!(
+                "  - {}{} <- {}",
+                item.file,
+                if item.dev_only { " [dev only]" } else { "" },
+                item.entries.join(", ")
+            )
+        });
+    }
+
+    if !report.export_fixes.is_empty() {
+        let applied = report.export_fixes.iter().filter(|fix| fix.applied).count();
+        let safe = report.export_fixes.iter().filter(|fix| fix.safe).count();
+        println!(
+            "\nExport fixes ({}): {} safe, {} applied, {} refused",
+            report.export_fixes.len(),
+            safe,
+            applied,
+            report.export_fixes.len() - safe
+        );
+        print_limited(&report.export_fixes, verbose, |fix| {
+            let status = match (&fix.reason, fix.applied) {
+                (Some(reason), _) => format!("refused: {reason}"),
+                (None, true) => "removed `export`".to_string(),
+                (None, false) => "would remove `export`".to_string(),
+            };
+            println!("  - {} :: {} ({status})", fix.file, fix.export)
+        });
+    }
+
+    for wave in &report.cascade_waves {
+        print_cascade_wave(wave, verbose);
+    }
+
+    if let Some(comparison) = &report.comparison {
+        print_comparison(comparison, verbose);
     }
 }
 
-pub(crate) fn print_tui_report(report: &Report) -> Result<()> {
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+/// One `--cascade` wave: what becomes unused once the findings of the earlier waves are
+/// deleted.
+fn print_cascade_wave(wave: &CascadeWave, verbose: bool) {
+    println!(
+        "\nCascade wave {}: unused once the files and assets of earlier waves are deleted",
+        wave.wave
+    );
+    for (label, paths) in [
+        ("Unused files", &wave.unused_files),
+        ("Unused assets", &wave.unused_assets),
+        ("Unused dependencies", &wave.unused_dependencies),
+    ] {
+        if !paths.is_empty() {
+            println!("  {label} ({}):", paths.len());
+            print_limited(paths, verbose, |path| println!("    - {path}"));
+        }
+    }
+    if !wave.unused_exports.is_empty() {
+        println!("  Unused exports ({}):", wave.unused_exports.len());
+        print_limited(&wave.unused_exports, verbose, |item| {
+            println!("    - {} :: {}", item.file, item.export)
+        });
+    }
+}
 
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+fn print_comparison(comparison: &FindingComparison, verbose: bool) {
+    println!("\nCompared with {}:", comparison.baseline);
+    println!(
+        "  - New: {} | Fixed: {} | Renamed: {} | Unchanged: {}",
+        comparison.added.len(),
+        comparison.fixed.len(),
+        comparison.renamed.len(),
+        comparison.unchanged_count
+    );
 
-    let mut state = TuiState {
-        page: TuiPage::Summary,
-        delete: DeleteState {
+    for (label, findings) in [("New", &comparison.added), ("Fixed", &comparison.fixed)] {
+        if findings.is_empty() {
+            continue;
+        }
+        println!("\n{label} findings ({}):", findings.len());
+        print_limited(findings, verbose, |finding| {
+            println!("  - {}", describe_finding(finding))
+        });
+    }
+
+    if !comparison.renamed.is_empty() {
+        println!("\nRenamed findings ({}):", comparison.renamed.len());
+        print_limited(&comparison.renamed, verbose, |item| {
+            println!(
+                "  - {} -> {}",
+                describe_finding(&item.from),
+                describe_finding(&item.to)
+            )
+        });
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        0..1024 => format!("{bytes} B"),
+        1024..1_048_576 => format!("{:.1} KiB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MiB", bytes as f64 / 1_048_576.0),
+    }
+}
+
+fn describe_finding(finding: &Finding) -> String {
+    match &finding.export {
+        Some(export) => format!("{}: {} ({export})", finding.category, finding.subject),
+        None => format!("{}: {}", finding.category, finding.subject),
+    }
+}
+
+/// Finding IDs are only shown in verbose output to keep the default listing compact.
+fn id_suffix(verbose: bool, category: &str, subject: &str, export: Option<&str>) -> String {
+    if verbose {
+        format!("  [{}]", finding_id(category, subject, export))
+    } else {
+        String::new()
+    }
+}
+
+/// `src/a.ts, src/b.ts (+3 more); package.json script`: the importing files, shortened to
+/// the first few unless `verbose`, then the other uses.
+fn dependency_uses(usage: &DependencyUsage, verbose: bool) -> String {
+    const SHOWN_FILES: usize = 3;
+    let mut parts = Vec::new();
+    if !usage.files.is_empty() {
+        let shown = if verbose {
+            usage.files.len()
+        } else {
+            usage.files.len().min(SHOWN_FILES)
+        };
+        let mut files = usage.files[..shown].join(", ");
+        if shown < usage.files.len() {
+            files.push_str(&format!(" (+{} more)", usage.files.len() - shown));
+        }
+        parts.push(files);
+    }
+    parts.extend(usage.other_uses.iter().cloned());
+    parts.join("; ")
+}
+
+/// `haadi deps`: one line per declared dependency with its uses, or with `--why`, every import
+/// of one package. `json` prints the [`DependencyUsage`] entries instead.
+pub(crate) fn print_dependency_why(
+    report: &Report,
+    package: Option<&str>,
+    json: bool,
+) -> Result<()> {
+    let usages: Vec<&DependencyUsage> = match package {
+        Some(package) => vec![
+            report
+                .dependency_usage
+                .iter()
+                .find(|usage| usage.name == package)
+                .with_context(|| {
+                    format!(
+                        "{package} is not declared in the root package.json (@types/* packages are not tracked)"
+                    )
+                })?,
+        ],
+        None => report.dependency_usage.iter().collect(),
+    };
+    if json {
+        println!("{}", serde_json::to_string_pretty(&usages)?);
+        return Ok(());
+    }
+    if package.is_none() {
+        for usage in usages {
+            let uses = match &usage.note {
+                Some(note) if !usage.used => format!("unused ({note})"),
+                _ => dependency_uses(usage, false),
+            };
+            println!("{}: {uses}", usage.name);
+        }
+        return Ok(());
+    }
+    for usage in usages {
+        println!(
+            "{}: {}",
+            usage.name,
+            if usage.used { "used" } else { "unused" }
+        );
+        for line in dependency_why_lines(Path::new(&report.root), usage) {
+            println!("{line}");
+        }
+    }
+    Ok(())
+}
+
+/// What keeps a dependency used: each import statement from reachable files, its other uses,
+/// then the imports from files no entry reaches, which deleting those files would not change.
+fn dependency_why_lines(root: &Path, usage: &DependencyUsage) -> Vec<String> {
+    let mut lines = Vec::new();
+    if let Some(note) = &usage.note {
+        lines.push(format!("Unused: {note}"));
+    }
+    let (reachable, unreachable): (Vec<&DependencyImport>, Vec<&DependencyImport>) =
+        usage.imports.iter().partition(|import| import.reachable);
+    // Imports are sorted by file, so each file is read once.
+    let mut source: Option<(String, String)> = None;
+    let mut statement_line = |import: &DependencyImport| {
+        if source.as_ref().is_none_or(|(file, _)| *file != import.file) {
+            let text = fs::read_to_string(root.join(&import.file)).unwrap_or_default();
+            source = Some((import.file.clone(), text));
+        }
+        let text = source.as_ref().map_or("", |(_, text)| text.as_str());
+        format!(
+            "  {}:{}: {}",
+            import.file,
+            import.line,
+            import_statement(text, import)
+        )
+    };
+    if !reachable.is_empty() {
+        lines.push(format!(
+            "Imported by reachable files ({}):",
+            usage.files.len()
+        ));
+        lines.extend(reachable.into_iter().map(&mut statement_line));
+    }
+    if !usage.other_uses.is_empty() {
+        lines.push(format!("Also used by: {}", usage.other_uses.join(", ")));
+    }
+    if !unreachable.is_empty() {
+        lines.push(
+            "Also imported by files no entry reaches, which do not keep it used:".to_string(),
+        );
+        lines.extend(unreachable.into_iter().map(&mut statement_line));
+    }
+    lines
+}
+
+/// The statement of `import` in `text` on one line: from its line through the line naming the
+/// specifier, which a multi-line `import { ... } from` ends on.
+fn import_statement(text: &str, import: &DependencyImport) -> String {
+    const MAX_LINES: usize = 20;
+    let mut parts = Vec::new();
+    for line in text
+        .lines()
+        .skip(import.line.saturating_sub(1))
+        .take(MAX_LINES)
+    {
+        parts.push(line.trim());
+        if line.contains(&import.specifier) {
+            break;
+        }
+    }
+    parts.join(" ")
+}
+
+/// Prints the first [`HUMAN_LIST_LIMIT`] items (all of them when `verbose`) followed by a
+/// count of the hidden rest.
+fn print_limited<T>(items: &[T], verbose: bool, print_item: impl Fn(&T)) {
+    let shown = if verbose {
+        items.len()
+    } else {
+        items.len().min(HUMAN_LIST_LIMIT)
+    };
+    for item in &items[..shown] {
+        print_item(item);
+    }
+    if shown < items.len() {
+        println!(
+            "  ... and {} more (use --verbose-findings or --json to list all)",
+            items.len() - shown
+        );
+    }
+}
+
+/// Polled by the TUI between key events in `--watch` mode; returns a new report after the
+/// project changed. A run that fails leaves the current report and explains why in the
+/// status message it is given.
+pub(crate) type ReportRefresh<'a> = &'a mut dyn FnMut(&mut String) -> Result<Option<Report>>;
+
+impl DeleteState {
+    fn new(report: &Report) -> Self {
+        let mut state = DeleteState {
             items: build_delete_candidates(report),
             selected: BTreeSet::new(),
             cursor: 0,
@@ -225,34 +1158,185 @@ pub(crate) fn print_tui_report(report: &Report) -> Result<()> {
             confirm_empty_trash: false,
             confirm_restore_previous: false,
             confirm_restore_all: false,
+            confirm_paths: Vec::new(),
+            confirm_scroll: 0,
+            confirm_visible: std::cell::Cell::new(1),
+            on_conflict: OnConflict::Skip,
             filter: DeleteFilter::All,
             search_query: String::new(),
             search_input: String::new(),
             editing_search: false,
+            scope: None,
+            show_folders: false,
+            folder_cursor: 0,
+            expanded_folders: BTreeSet::new(),
+            show_detail: false,
+            range_anchor: None,
+            editing_session_name: false,
+            session_name_input: String::new(),
+            pending_session_name: String::new(),
+            trashed_sessions: BTreeMap::new(),
+            session_names: BTreeMap::new(),
             message: "Select unused files/assets, then press x and confirm with y.".to_string(),
             root: PathBuf::from(&report.root),
             trash_root: PathBuf::from(&report.root).join(".haadi_trash"),
+            trash: TrashOptions {
+                mode: TrashMode::Repo,
+                max_age: None,
+            },
             undo_stack: Vec::new(),
-        },
+        };
+        let _ = hydrate_deleted_candidates_from_trash(&mut state);
+        refresh_trash_index(&mut state);
+        state
+    }
+}
+
+/// Disposes of unused files/assets of `report` as `trash` says, as the TUI delete page does:
+/// into a new `.haadi_trash` session by default. Paths that are not unused findings are
+/// refused. Returns the outcome message.
+pub(crate) fn trash_report_files(
+    report: &Report,
+    rel_paths: &[String],
+    session_name: Option<&str>,
+    trash: TrashOptions,
+) -> Result<String> {
+    let mut state = DeleteState::new(report);
+    state.trash = trash;
+    let requested: HashSet<String> = rel_paths.iter().map(|p| normalized_rel_query(p)).collect();
+    state.selected = state
+        .items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| {
+            item.state == CandidateState::Active && requested.contains(&item.rel_path)
+        })
+        .map(|(idx, _)| idx)
+        .collect();
+    let attempted = state.selected.len();
+    let refused = requested.len() - attempted;
+    state.pending_session_name = session_name.unwrap_or_default().to_string();
+    apply_selected_deletions(&mut state)?;
+
+    let trashed = state
+        .items
+        .iter()
+        .filter(|item| item.state == CandidateState::Deleted && requested.contains(&item.rel_path))
+        .count();
+    // Batches are all-or-nothing; on failure the status message names the cause. Outside
+    // `.haadi_trash` the status message also reports success.
+    let mut message = if trash.mode == TrashMode::Repo && trashed == attempted {
+        format!("Moved {trashed} file(s) to .haadi_trash.")
+    } else {
+        state.message.clone()
     };
-    let _ = hydrate_deleted_candidates_from_trash(&mut state.delete);
+    if refused > 0 {
+        message.push_str(&format!(
+            " Refused {refused} path(s) that are not unused files or assets."
+        ));
+    }
+    Ok(message)
+}
 
-    let result = run_tui_loop(&mut terminal, report, &mut state);
+/// Restores the most recently trashed copy of a file, or every trashed file under a folder.
+/// Returns the outcome message.
+pub(crate) fn restore_from_trash(
+    root: &Path,
+    rel_path: &str,
+    on_conflict: OnConflict,
+) -> Result<String> {
+    let mut state = DeleteState::new(&Report {
+        root: root.display().to_string(),
+        ..Report::default()
+    });
+    state.on_conflict = on_conflict;
+    let query = normalized_rel_query(rel_path);
+    let prefix = format!("{query}/");
+    restore_from_trash_matching(&mut state, "restore_file", "file", &query, |rel| {
+        rel == query || rel.starts_with(&prefix)
+    })?;
+    Ok(state.message)
+}
 
-    disable_raw_mode()?;
-    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
-    terminal.show_cursor()?;
+#[cfg(feature = "tui")]
+pub(crate) fn print_tui_report(
+    report: &Report,
+    refresh: Option<ReportRefresh>,
+    on_conflict: OnConflict,
+    trash: TrashOptions,
+) -> Result<()> {
+    let _terminal_guard = TerminalGuard::enter()?;
+    let _interruptible = Interruptible::enter();
+    let backend = CrosstermBackend::new(io::stdout());
+    let mut terminal = Terminal::new(backend)?;
 
-    result
+    let mut state = TuiState {
+        page: TuiPage::Summary,
+        delete: DeleteState::new(report),
+        show_help: false,
+        code_cursor: 0,
+        code_message: String::new(),
+        open_in_editor: None,
+        dependency_cursor: 0,
+        dependency_details: None,
+    };
+    state.delete.on_conflict = on_conflict;
+    state.delete.trash = trash;
+
+    run_tui_loop(&mut terminal, report.clone(), &mut state, refresh)
 }
 
+#[cfg(not(feature = "tui"))]
+pub(crate) fn print_tui_report(
+    _report: &Report,
+    _refresh: Option<ReportRefresh>,
+    _on_conflict: OnConflict,
+    _trash: TrashOptions,
+) -> Result<()> {
+    anyhow::bail!("--tui is not available: haadi was built without the `tui` feature")
+}
+
+#[cfg(feature = "tui")]
 fn run_tui_loop(
     terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
-    report: &Report,
+    mut report: Report,
     state: &mut TuiState,
+    mut refresh: Option<ReportRefresh>,
 ) -> Result<()> {
-    loop {
-        terminal.draw(|frame| draw_page(frame, report, state))?;
+    // Ctrl-C from another process arrives as SIGINT; in raw mode the key itself is an event.
+    while !interrupted() {
+        if let Some(refresh) = refresh.as_mut()
+            && let Some(updated) = refresh(&mut state.delete.message)?
+        {
+            report = updated;
+            replace_delete_candidates(&mut state.delete, &report);
+            state.code_cursor = state
+                .code_cursor
+                .min(report.commented_code.len().saturating_sub(1));
+            state.dependency_cursor = state
+                .dependency_cursor
+                .min(report.dependency_usage.len().saturating_sub(1));
+            state.dependency_details = None;
+        }
+        if state.page == TuiPage::Dependencies
+            && state
+                .dependency_details
+                .as_ref()
+                .is_none_or(|(row, _)| *row != state.dependency_cursor)
+        {
+            state.dependency_details =
+                report
+                    .dependency_usage
+                    .get(state.dependency_cursor)
+                    .map(|usage| {
+                        (
+                            state.dependency_cursor,
+                            dependency_why_lines(Path::new(&report.root), usage),
+                        )
+                    });
+        }
+
+        terminal.draw(|frame| draw_page(frame, &report, state))?;
 
         if event::poll(Duration::from_millis(200))? {
             let Event::Key(key) = event::read()? else {
@@ -261,6 +1345,22 @@ fn run_tui_loop(
             if key.kind != KeyEventKind::Press {
                 continue;
             }
+            if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+                break;
+            }
+            if state.show_help {
+                // Any key closes the overlay; only q goes on to quit.
+                state.show_help = false;
+                if key.code == KeyCode::Char('q') {
+                    break;
+                }
+                continue;
+            }
+            let typing = state.delete.editing_search || state.delete.editing_session_name;
+            if key.code == KeyCode::Char('?') && !(state.page == TuiPage::Delete && typing) {
+                state.show_help = true;
+                continue;
+            }
 
             match state.page {
                 TuiPage::Summary => {
@@ -273,6 +1373,24 @@ fn run_tui_loop(
                         break;
                     }
                 }
+                TuiPage::CommentedCode => {
+                    if handle_commented_code_key(key.code, state, &report) {
+                        break;
+                    }
+                }
+                TuiPage::Dependencies => {
+                    if handle_dependency_key(key.code, state, &report) {
+                        break;
+                    }
+                }
+            }
+            if let Some((file, line)) = state.open_in_editor.take() {
+                let path = Path::new(&report.root).join(&file);
+                state.code_message = match TerminalGuard::suspend(|| open_in_editor(&path, line))? {
+                    Ok(editor) => format!("Opened {file}:{line} in {editor}."),
+                    Err(err) => err.to_string(),
+                };
+                terminal.clear()?;
             }
         }
     }
@@ -280,6 +1398,33 @@ fn run_tui_loop(
     Ok(())
 }
 
+/// Swaps in the candidates of a re-analyzed report, keeping the selection of rows that are
+/// still listed.
+fn replace_delete_candidates(state: &mut DeleteState, report: &Report) {
+    let selected: BTreeSet<String> = state
+        .selected
+        .iter()
+        .filter_map(|idx| state.items.get(*idx))
+        .map(|item| item.rel_path.clone())
+        .collect();
+
+    state.items = build_delete_candidates(report);
+    let _ = hydrate_deleted_candidates_from_trash(state);
+    state.selected = state
+        .items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| selected.contains(&item.rel_path))
+        .map(|(idx, _)| idx)
+        .collect();
+    clamp_delete_cursor(state);
+    state.folder_cursor = state
+        .folder_cursor
+        .min(folder_rows(state).len().saturating_sub(1));
+    state.message = "Project files changed; report refreshed.".to_string();
+}
+
+#[cfg(feature = "tui")]
 fn handle_summary_key(code: KeyCode, state: &mut TuiState) -> bool {
     match code {
         KeyCode::Char('q') | KeyCode::Esc => true,
@@ -287,10 +1432,83 @@ fn handle_summary_key(code: KeyCode, state: &mut TuiState) -> bool {
             state.page = TuiPage::Delete;
             false
         }
+        KeyCode::Char('c') => {
+            state.page = TuiPage::CommentedCode;
+            false
+        }
+        KeyCode::Char('u') => {
+            state.page = TuiPage::Dependencies;
+            false
+        }
         _ => false,
     }
 }
 
+#[cfg(feature = "tui")]
+fn handle_commented_code_key(code: KeyCode, state: &mut TuiState, report: &Report) -> bool {
+    match code {
+        KeyCode::Char('q') => return true,
+        KeyCode::Char('b') | KeyCode::Esc => state.page = TuiPage::Summary,
+        KeyCode::Up | KeyCode::Char('k') => state.code_cursor = state.code_cursor.saturating_sub(1),
+        KeyCode::Down | KeyCode::Char('j')
+            if state.code_cursor + 1 < report.commented_code.len() =>
+        {
+            state.code_cursor += 1;
+        }
+        KeyCode::Enter | KeyCode::Char('o') => {
+            state.open_in_editor = report
+                .commented_code
+                .get(state.code_cursor)
+                .map(|item| (item.file.clone(), item.start_line));
+        }
+        _ => {}
+    }
+    false
+}
+
+#[cfg(feature = "tui")]
+fn handle_dependency_key(code: KeyCode, state: &mut TuiState, report: &Report) -> bool {
+    match code {
+        KeyCode::Char('q') => return true,
+        KeyCode::Char('b') | KeyCode::Esc => state.page = TuiPage::Summary,
+        KeyCode::Up | KeyCode::Char('k') => {
+            state.dependency_cursor = state.dependency_cursor.saturating_sub(1)
+        }
+        KeyCode::Down | KeyCode::Char('j')
+            if state.dependency_cursor + 1 < report.dependency_usage.len() =>
+        {
+            state.dependency_cursor += 1;
+        }
+        _ => {}
+    }
+    false
+}
+
+/// Runs `$VISUAL` (or `$EDITOR`, or `vi`) on `path` at `line` and waits for it to exit.
+/// Returns the editor's name; the inner error is shown in the TUI rather than ending it.
+#[cfg(feature = "tui")]
+fn open_in_editor(path: &Path, line: usize) -> Result<String> {
+    let editor = ["VISUAL", "EDITOR"]
+        .into_iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|editor| !editor.trim().is_empty())
+        .unwrap_or_else(|| "vi".to_string());
+    // `EDITOR="code --wait"` carries its own arguments.
+    let mut words = editor.split_whitespace();
+    let program = words.next().unwrap_or("vi");
+    let status = std::process::Command::new(program)
+        .args(words)
+        .arg(format!("+{line}"))
+        .arg(path)
+        .status()
+        .with_context(|| format!("Failed to start editor '{editor}'"))?;
+    if !status.success() {
+        anyhow::bail!("Editor '{editor}' exited with {status}");
+    }
+    Ok(editor)
+}
+
+#[cfg(feature = "tui")]
 fn handle_delete_key(code: KeyCode, state: &mut TuiState) -> Result<bool> {
     if state.delete.editing_search {
         match code {
@@ -323,14 +1541,83 @@ fn handle_delete_key(code: KeyCode, state: &mut TuiState) -> Result<bool> {
         return Ok(false);
     }
 
+    if state.delete.editing_session_name {
+        match code {
+            KeyCode::Enter => {
+                state.delete.pending_session_name =
+                    state.delete.session_name_input.trim().to_string();
+                state.delete.editing_session_name = false;
+                state.delete.message = format!(
+                    "Batch name: '{}'. Press y to confirm delete, n to cancel.",
+                    state.delete.pending_session_name
+                );
+            }
+            KeyCode::Esc => {
+                state.delete.editing_session_name = false;
+                state.delete.message =
+                    "Batch name edit canceled. Press y to confirm delete, n to cancel.".to_string();
+            }
+            KeyCode::Backspace => {
+                state.delete.session_name_input.pop();
+            }
+            KeyCode::Char(c) => {
+                state.delete.session_name_input.push(c);
+            }
+            _ => {}
+        }
+        return Ok(false);
+    }
+
+    if is_confirming(&state.delete) {
+        match code {
+            KeyCode::Up | KeyCode::Char('k') => {
+                // Clamped first in case the dialog grew since the last key press.
+                let max_scroll = confirm_max_scroll(&state.delete);
+                state.delete.confirm_scroll = state
+                    .delete
+                    .confirm_scroll
+                    .min(max_scroll)
+                    .saturating_sub(1);
+                return Ok(false);
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                let max_scroll = confirm_max_scroll(&state.delete);
+                state.delete.confirm_scroll = (state.delete.confirm_scroll + 1).min(max_scroll);
+                return Ok(false);
+            }
+            KeyCode::Char('C')
+                if state.delete.confirm_restore_previous || state.delete.confirm_restore_all =>
+            {
+                cycle_on_conflict(&mut state.delete);
+                let sessions = list_trash_sessions(&state.delete.trash_root);
+                let sessions = if state.delete.confirm_restore_all {
+                    &sessions[..]
+                } else {
+                    &sessions[sessions.len().saturating_sub(1)..]
+                };
+                state.delete.confirm_paths = trash_listing(&state.delete, sessions, true);
+                return Ok(false);
+            }
+            _ => {}
+        }
+    }
+
     if state.delete.confirm_delete {
         match code {
             KeyCode::Char('y') => {
                 apply_selected_deletions(&mut state.delete)?;
                 state.delete.confirm_delete = false;
+                state.delete.pending_session_name.clear();
+            }
+            KeyCode::Char('m') => {
+                state.delete.editing_session_name = true;
+                state.delete.session_name_input = state.delete.pending_session_name.clone();
+                state.delete.message =
+                    "Name this batch: type and press Enter to apply.".to_string();
             }
             KeyCode::Char('n') | KeyCode::Esc => {
                 state.delete.confirm_delete = false;
+                state.delete.pending_session_name.clear();
                 state.delete.message = "Deletion canceled.".to_string();
             }
             _ => {}
@@ -383,8 +1670,41 @@ fn handle_delete_key(code: KeyCode, state: &mut TuiState) -> Result<bool> {
         return Ok(false);
     }
 
+    if state.delete.show_folders {
+        if code == KeyCode::Char('q') {
+            return Ok(true);
+        }
+        handle_folder_key(code, &mut state.delete);
+        return Ok(false);
+    }
+
+    if state.delete.show_detail && matches!(code, KeyCode::Esc | KeyCode::Char('p')) {
+        state.delete.show_detail = false;
+        return Ok(false);
+    }
+
+    if state.delete.range_anchor.is_some() && code == KeyCode::Esc {
+        state.delete.range_anchor = None;
+        state.delete.message = "Range selection canceled.".to_string();
+        return Ok(false);
+    }
+
     match code {
         KeyCode::Char('q') => Ok(true),
+        KeyCode::Char('V') => {
+            if state.delete.range_anchor.take().is_some() {
+                state.delete.message = "Range selection canceled.".to_string();
+            } else if !filtered_indices(&state.delete).is_empty() {
+                state.delete.range_anchor = Some(state.delete.cursor);
+                state.delete.message =
+                    "Range mode: move with j/k, space to toggle range, Esc to cancel.".to_string();
+            }
+            Ok(false)
+        }
+        KeyCode::Char('p') => {
+            state.delete.show_detail = true;
+            Ok(false)
+        }
         KeyCode::Char('b') | KeyCode::Esc => {
             state.page = TuiPage::Summary;
             Ok(false)
@@ -404,7 +1724,11 @@ fn handle_delete_key(code: KeyCode, state: &mut TuiState) -> Result<bool> {
             Ok(false)
         }
         KeyCode::Enter | KeyCode::Char(' ') => {
-            toggle_selected(&mut state.delete);
+            if state.delete.range_anchor.is_some() {
+                toggle_selected_range(&mut state.delete);
+            } else {
+                toggle_selected(&mut state.delete);
+            }
             Ok(false)
         }
         KeyCode::Char('a') => {
@@ -423,8 +1747,16 @@ fn handle_delete_key(code: KeyCode, state: &mut TuiState) -> Result<bool> {
                 state.delete.message = "No items selected for deletion.".to_string();
             } else {
                 state.delete.confirm_delete = true;
+                let paths = state
+                    .delete
+                    .selected
+                    .iter()
+                    .filter_map(|idx| state.delete.items.get(*idx))
+                    .map(|item| item.rel_path.clone())
+                    .collect();
+                open_confirmation(&mut state.delete, paths);
                 state.delete.message = format!(
-                    "Confirm delete {} selected files? Press y to confirm, n to cancel.",
+                    "Confirm delete {} selected files? Press y to confirm, m to name the batch, n to cancel.",
                     state.delete.selected.len()
                 );
             }
@@ -432,21 +1764,52 @@ fn handle_delete_key(code: KeyCode, state: &mut TuiState) -> Result<bool> {
         }
         KeyCode::Char('z') => {
             state.delete.confirm_empty_trash = true;
+            let sessions = list_trash_sessions(&state.delete.trash_root);
+            let paths = trash_listing(&state.delete, &sessions, false);
+            open_confirmation(&mut state.delete, paths);
             state.delete.message =
                 "Empty trash and clear undo history? Press y to confirm, n to cancel.".to_string();
             Ok(false)
         }
         KeyCode::Char('r') => {
             state.delete.confirm_restore_previous = true;
-            state.delete.message =
-                "Restore most recent previous trash session? Press y to confirm, n to cancel."
-                    .to_string();
+            let latest = list_trash_sessions(&state.delete.trash_root).pop();
+            let paths = trash_listing(&state.delete, latest.as_slice(), true);
+            open_confirmation(&mut state.delete, paths);
+            let latest = latest.map(|(id, _)| session_label(&state.delete, &id));
+            state.delete.message = match latest {
+                Some(label) => format!(
+                    "Restore most recent previous trash session {label}? Press y to confirm, n to cancel."
+                ),
+                None => {
+                    "Restore most recent previous trash session? Press y to confirm, n to cancel."
+                        .to_string()
+                }
+            };
             Ok(false)
         }
         KeyCode::Char('R') => {
             state.delete.confirm_restore_all = true;
-            state.delete.message =
-                "Restore ALL trash sessions? Press y to confirm, n to cancel.".to_string();
+            let sessions = list_trash_sessions(&state.delete.trash_root);
+            let paths = trash_listing(&state.delete, &sessions, true);
+            open_confirmation(&mut state.delete, paths);
+            let named: Vec<String> = sessions
+                .iter()
+                .filter_map(|(id, _)| state.delete.session_names.get(id))
+                .map(|name| format!("'{name}'"))
+                .collect();
+            state.delete.message = if named.is_empty() {
+                format!(
+                    "Restore ALL {} trash sessions? Press y to confirm, n to cancel.",
+                    sessions.len()
+                )
+            } else {
+                format!(
+                    "Restore ALL {} trash sessions (including {})? Press y to confirm, n to cancel.",
+                    sessions.len(),
+                    named.join(", ")
+                )
+            };
             Ok(false)
         }
         KeyCode::Char('u') => {
@@ -471,6 +1834,14 @@ fn handle_delete_key(code: KeyCode, state: &mut TuiState) -> Result<bool> {
             reset_filter_and_search(&mut state.delete);
             Ok(false)
         }
+        KeyCode::Char('t') => {
+            open_folder_sidebar(&mut state.delete);
+            Ok(false)
+        }
+        KeyCode::Char('C') => {
+            cycle_on_conflict(&mut state.delete);
+            Ok(false)
+        }
         KeyCode::Char('/') => {
             state.delete.editing_search = true;
             state.delete.search_input = state.delete.search_query.clone();
@@ -481,13 +1852,198 @@ fn handle_delete_key(code: KeyCode, state: &mut TuiState) -> Result<bool> {
     }
 }
 
+/// Keys while the folder sidebar is open. Moving the cursor limits the list to the highlighted
+/// folder right away; closing the sidebar keeps that folder.
+#[cfg(feature = "tui")]
+fn handle_folder_key(code: KeyCode, state: &mut DeleteState) {
+    let rows = folder_rows(state);
+    let Some(row) = rows.get(state.folder_cursor) else {
+        state.show_folders = false;
+        return;
+    };
+    match code {
+        KeyCode::Up | KeyCode::Char('k') => {
+            state.folder_cursor = state.folder_cursor.saturating_sub(1);
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            if state.folder_cursor + 1 < rows.len() {
+                state.folder_cursor += 1;
+            }
+        }
+        KeyCode::Right | KeyCode::Char('l') => {
+            if row.has_children && !row.path.is_empty() {
+                state.expanded_folders.insert(row.path.clone());
+            }
+            return;
+        }
+        KeyCode::Left | KeyCode::Char('h') => {
+            if state.expanded_folders.remove(&row.path) {
+                return;
+            }
+            // Already collapsed: move up to the parent folder.
+            let parent = row.path.rsplit_once('/').map_or("", |(parent, _)| parent);
+            state.folder_cursor = rows
+                .iter()
+                .position(|candidate| candidate.path == parent)
+                .unwrap_or(0);
+        }
+        KeyCode::Char('t') | KeyCode::Enter | KeyCode::Esc => {
+            state.show_folders = false;
+            state.message = match &state.scope {
+                Some(scope) => format!(
+                    "Folder: {scope}/ ({} candidates).",
+                    filtered_indices(state).len()
+                ),
+                None => "Folder: all.".to_string(),
+            };
+            return;
+        }
+        _ => return,
+    }
+    let path = &rows[state.folder_cursor].path;
+    state.scope = (!path.is_empty()).then(|| path.clone());
+    clamp_delete_cursor(state);
+}
+
+/// Opens the folder sidebar with the current folder expanded and highlighted.
+fn open_folder_sidebar(state: &mut DeleteState) {
+    if let Some(scope) = &state.scope {
+        let mut ancestor = scope.as_str();
+        while let Some((parent, _)) = ancestor.rsplit_once('/') {
+            state.expanded_folders.insert(parent.to_string());
+            ancestor = parent;
+        }
+    }
+    let scope = state.scope.clone().unwrap_or_default();
+    state.folder_cursor = folder_rows(state)
+        .iter()
+        .position(|row| row.path == scope)
+        .unwrap_or(0);
+    state.show_folders = true;
+    state.message =
+        "Folders: j/k pick a folder, l/h expand/collapse, t/Enter to return to the list."
+            .to_string();
+}
+
+/// Folders holding listed candidates (ignoring the folder scope), in tree order, with their
+/// candidate counts. Subfolders only show up while their parent is expanded.
+fn folder_rows(state: &DeleteState) -> Vec<FolderRow> {
+    let indices = unscoped_indices(state);
+    let mut counts: BTreeMap<Vec<&str>, usize> = BTreeMap::new();
+    for idx in &indices {
+        let parts: Vec<&str> = state.items[*idx].rel_path.split('/').collect();
+        for end in 1..parts.len() {
+            *counts.entry(parts[..end].to_vec()).or_default() += 1;
+        }
+    }
+
+    let mut rows = vec![FolderRow {
+        path: String::new(),
+        depth: 0,
+        candidates: indices.len(),
+        has_children: !counts.is_empty(),
+    }];
+    let folders: Vec<&Vec<&str>> = counts.keys().collect();
+    for (i, (parts, candidates)) in counts.iter().enumerate() {
+        let visible =
+            (1..parts.len()).all(|end| state.expanded_folders.contains(&parts[..end].join("/")));
+        if !visible {
+            continue;
+        }
+        let has_children = folders
+            .get(i + 1)
+            .is_some_and(|next| next.len() > parts.len() && next.starts_with(parts));
+        rows.push(FolderRow {
+            path: parts.join("/"),
+            depth: parts.len(),
+            candidates: *candidates,
+            has_children,
+        });
+    }
+    rows
+}
+
+fn cycle_on_conflict(state: &mut DeleteState) {
+    state.on_conflict = state.on_conflict.next();
+    state.message = format!(
+        "Restores into existing paths: {}.",
+        state.on_conflict.label()
+    );
+}
+
+fn is_confirming(state: &DeleteState) -> bool {
+    state.confirm_delete
+        || state.confirm_empty_trash
+        || state.confirm_restore_previous
+        || state.confirm_restore_all
+}
+
+fn confirm_max_scroll(state: &DeleteState) -> usize {
+    state
+        .confirm_paths
+        .len()
+        .saturating_sub(state.confirm_visible.get())
+}
+
+fn open_confirmation(state: &mut DeleteState, paths: Vec<String>) {
+    state.confirm_paths = paths;
+    state.confirm_scroll = 0;
+}
+
+/// Files inside the given trash sessions, relative to the project root. Multiple sessions are
+/// labelled; with `mark_conflicts`, files whose original path is occupied again are flagged,
+/// since a restore leaves them in the trash.
+fn trash_listing(
+    state: &DeleteState,
+    sessions: &[(String, PathBuf)],
+    mark_conflicts: bool,
+) -> Vec<String> {
+    let mut paths = Vec::new();
+    for (session_id, session_path) in sessions {
+        let mut files: Vec<String> = WalkDir::new(session_path)
+            .into_iter()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .filter_map(|entry| {
+                let rel = entry.path().strip_prefix(session_path).ok()?;
+                let mut line = rel.to_string_lossy().replace('\\', "/");
+                let target = state.root.join(rel);
+                if mark_conflicts && target.exists() {
+                    line.push_str(&match state.on_conflict {
+                        OnConflict::Skip => "  (path exists, stays in trash)".to_string(),
+                        OnConflict::Overwrite if target.is_file() => {
+                            "  (path exists, will be overwritten)".to_string()
+                        }
+                        OnConflict::Overwrite => "  (path is a folder, stays in trash)".to_string(),
+                        OnConflict::Alongside => format!(
+                            "  (path exists, restored as {})",
+                            alongside_path(&target)
+                                .file_name()
+                                .unwrap_or_default()
+                                .to_string_lossy()
+                        ),
+                    });
+                }
+                if sessions.len() > 1 {
+                    line = format!("[{}] {line}", session_label(state, session_id));
+                }
+                Some(line)
+            })
+            .collect();
+        files.sort();
+        paths.extend(files);
+    }
+    paths
+}
+
 fn reset_filter_and_search(state: &mut DeleteState) {
     state.filter = DeleteFilter::All;
     state.search_query.clear();
     state.search_input.clear();
     state.editing_search = false;
+    state.scope = None;
     clamp_delete_cursor(state);
-    state.message = "Reset filter and search.".to_string();
+    state.message = "Reset filter, search, and folder.".to_string();
 }
 
 fn toggle_selected(state: &mut DeleteState) {
@@ -506,6 +2062,51 @@ fn toggle_selected(state: &mut DeleteState) {
     state.message = format!("Selected {} items.", state.selected.len());
 }
 
+/// Filtered-list positions covered by the active range, in display order.
+fn active_range(state: &DeleteState) -> Option<std::ops::RangeInclusive<usize>> {
+    let anchor = state.range_anchor?;
+    Some(anchor.min(state.cursor)..=anchor.max(state.cursor))
+}
+
+/// Selects every row in the range, or unselects them all when the range is already fully
+/// selected. Leaves range mode afterwards.
+fn toggle_selected_range(state: &mut DeleteState) {
+    let filtered = filtered_indices(state);
+    let Some(range) = active_range(state) else {
+        return;
+    };
+    state.range_anchor = None;
+
+    let indices: Vec<usize> = filtered
+        .get(range)
+        .map(|rows| rows.to_vec())
+        .unwrap_or_default();
+    if indices.is_empty() {
+        return;
+    }
+
+    if indices.iter().all(|idx| state.selected.contains(idx)) {
+        for idx in &indices {
+            state.selected.remove(idx);
+        }
+        state.message = format!(
+            "Unselected {} items. Selected {} items.",
+            indices.len(),
+            state.selected.len()
+        );
+    } else {
+        state.selected.extend(indices.iter().copied());
+        state.message = format!(
+            "Selected range of {} items. Selected {} items.",
+            indices.len(),
+            state.selected.len()
+        );
+    }
+}
+
+/// Moves the selected files into a new trash session as one transaction: every candidate is
+/// checked before anything moves, and when a move fails the files already moved are put back,
+/// so a failed batch never leaves the project half-deleted.
 fn apply_selected_deletions(state: &mut DeleteState) -> Result<()> {
     if state.selected.is_empty() {
         state.message = "No items selected for deletion.".to_string();
@@ -513,42 +2114,77 @@ fn apply_selected_deletions(state: &mut DeleteState) -> Result<()> {
     }
 
     let root = fs::canonicalize(&state.root).unwrap_or_else(|_| state.root.clone());
-    let mut deleted_indices = Vec::new();
-    let mut deleted_entries = Vec::new();
-    let mut failed = 0usize;
     let batch_id = generate_batch_id();
 
+    let mut planned = Vec::new();
+    let mut problems = Vec::new();
     for idx in state.selected.iter().copied() {
         let Some(item) = state.items.get(idx) else {
             continue;
         };
-        if item.state == CandidateState::Deleted {
-            failed += 1;
-            continue;
-        }
-
         let joined = root.join(&item.rel_path);
         let absolute = fs::canonicalize(&joined).unwrap_or(joined.clone());
-        if !absolute.starts_with(&root) {
-            failed += 1;
-            continue;
-        }
-        if !absolute.is_file() {
-            failed += 1;
-            continue;
+        let problem = if item.state == CandidateState::Deleted {
+            Some("already in trash".to_string())
+        } else if !absolute.starts_with(&root) {
+            Some("outside the project root".to_string())
+        } else if !absolute.is_file() {
+            Some("no longer a file".to_string())
+        } else if state.trash.mode == TrashMode::System
+            && let Err(err) = system_trash_for(&absolute)
+        {
+            // A file the system trash cannot take would otherwise fail the batch midway.
+            Some(format!("{err:#}"))
+        } else {
+            None
+        };
+        match problem {
+            Some(reason) => problems.push(format!("{}: {reason}", item.rel_path)),
+            None => planned.push((idx, absolute)),
         }
+    }
+    if !problems.is_empty() {
+        state.message = format!(
+            "Delete canceled, nothing was moved. {}",
+            summarize_failures(&problems)
+        );
+        return Ok(());
+    }
+    if state.trash.mode != TrashMode::Repo {
+        dispose_outside_trash(state, planned);
+        return Ok(());
+    }
+    let expired = match state.trash.max_age {
+        Some(max_age) => empty_sessions_older_than(&state.trash_root, max_age)?,
+        None => 0,
+    };
+    if expired > 0 {
+        state
+            .undo_stack
+            .retain(|batch| batch.iter().all(|entry| entry.trash_abs.exists()));
+    }
 
+    let mut deleted_indices = Vec::new();
+    let mut deleted_entries = Vec::new();
+    for (idx, absolute) in planned {
+        let item = &state.items[idx];
+        if interrupted() {
+            let cause = "interrupted by Ctrl-C".to_string();
+            roll_back_deletions(state, &batch_id, deleted_indices, deleted_entries, cause)?;
+            return Ok(());
+        }
         match move_to_trash(&root, &state.trash_root, item, &absolute, &batch_id) {
             Ok(entry) => {
                 deleted_indices.push(idx);
                 deleted_entries.push(entry);
             }
-            Err(_) => failed += 1,
+            Err(err) => {
+                let cause = format!("{}: {err:#}", item.rel_path);
+                roll_back_deletions(state, &batch_id, deleted_indices, deleted_entries, cause)?;
+                return Ok(());
+            }
         }
     }
-
-    deleted_indices.sort_unstable();
-    deleted_indices.dedup();
     for idx in deleted_indices.iter().copied() {
         if let Some(item) = state.items.get_mut(idx) {
             item.state = CandidateState::Deleted;
@@ -559,15 +2195,178 @@ fn apply_selected_deletions(state: &mut DeleteState) -> Result<()> {
     clamp_delete_cursor(state);
 
     let deleted = deleted_indices.len();
+    let session_name = Some(state.pending_session_name.trim())
+        .filter(|name| !name.is_empty())
+        .map(|name| name.to_string());
     if !deleted_entries.is_empty() {
-        write_delete_log(&state.trash_root, "delete", &batch_id, &deleted_entries)?;
+        if let Some(name) = &session_name {
+            write_session_meta(&state.trash_root, &batch_id, name, deleted_entries.len())?;
+        }
+        write_delete_log(
+            &state.trash_root,
+            "delete",
+            &batch_id,
+            session_name.as_deref(),
+            &deleted_entries,
+        )?;
         state.undo_stack.push(deleted_entries);
     }
-    state.message = format!("Deleted {deleted} files. Failed: {failed}. Press 'u' to undo.");
+    refresh_trash_index(state);
+    state.message = match &session_name {
+        Some(name) => {
+            format!("Deleted {deleted} files into session '{name}'. Press 'u' to undo.")
+        }
+        None => format!("Deleted {deleted} files. Press 'u' to undo."),
+    };
+    if expired > 0 {
+        state.message.push_str(&format!(
+            " Emptied {expired} trash sessions older than --trash-max-age."
+        ));
+    }
+
+    Ok(())
+}
+
+/// Deletes the `planned` files of the selection with `--trash system` or `none`, without
+/// touching `.haadi_trash`. A batch moved to the system trash is put back when one file
+/// fails, as with `.haadi_trash`; removed files cannot be, so removal stops at the first
+/// failure. Disposed files leave the list, since undo and restore cannot reach them.
+fn dispose_outside_trash(state: &mut DeleteState, planned: Vec<(usize, PathBuf)>) {
+    let system = state.trash.mode == TrashMode::System;
+    let mut disposed: Vec<(usize, PathBuf, Option<PathBuf>)> = Vec::new();
+    let mut failure = None;
+    for (idx, absolute) in planned {
+        if interrupted() {
+            failure = Some("interrupted by Ctrl-C".to_string());
+            break;
+        }
+        let outcome = if system {
+            move_to_system_trash(&absolute).map(Some)
+        } else {
+            fs::remove_file(&absolute)
+                .map(|_| None)
+                .map_err(anyhow::Error::from)
+        };
+        match outcome {
+            Ok(trashed) => disposed.push((idx, absolute, trashed)),
+            Err(err) => {
+                failure = Some(format!("{}: {err:#}", state.items[idx].rel_path));
+                break;
+            }
+        }
+    }
+
+    state.message = match (&failure, system) {
+        (None, true) => format!("Moved {} files to the system trash.", disposed.len()),
+        (None, false) => format!("Removed {} files for good (--trash none).", disposed.len()),
+        (Some(cause), true) => {
+            let mut stuck = Vec::new();
+            for (idx, original, trashed) in std::mem::take(&mut disposed).into_iter().rev() {
+                if let Some(trashed) = &trashed
+                    && let Err(err) = put_back_from_system_trash(trashed, &original)
+                {
+                    stuck.push(format!("{}: {err}", state.items[idx].rel_path));
+                    disposed.push((idx, original, None));
+                }
+            }
+            let mut message = format!(
+                "Delete failed and was rolled back. {}",
+                summarize_failures(std::slice::from_ref(cause))
+            );
+            if !stuck.is_empty() {
+                message.push_str(&format!(
+                    " Left in the system trash: {}",
+                    summarize_failures(&stuck)
+                ));
+            }
+            message
+        }
+        (Some(cause), false) => format!(
+            "Removed {} files for good, then stopped. {}",
+            disposed.len(),
+            summarize_failures(std::slice::from_ref(cause))
+        ),
+    };
+
+    let gone: BTreeSet<usize> = disposed.iter().map(|(idx, _, _)| *idx).collect();
+    let mut idx = 0;
+    state.items.retain(|_| {
+        let keep = !gone.contains(&idx);
+        idx += 1;
+        keep
+    });
+    state.selected.clear();
+    clamp_delete_cursor(state);
+}
+
+/// Puts the files of a failed batch back where they were. Files that cannot be moved back
+/// stay in the trash as a regular batch, so `u` can retry them.
+fn roll_back_deletions(
+    state: &mut DeleteState,
+    batch_id: &str,
+    moved_indices: Vec<usize>,
+    moved_entries: Vec<DeletedEntry>,
+    cause: String,
+) -> Result<()> {
+    info!(%cause, "delete batch failed; rolling back");
+    let mut rolled_back = Vec::new();
+    let mut stuck_indices = Vec::new();
+    let mut stuck_entries = Vec::new();
+    let mut stuck_causes = Vec::new();
+    for (idx, entry) in moved_indices.into_iter().zip(moved_entries).rev() {
+        match fs::rename(&entry.trash_abs, &entry.original_abs) {
+            Ok(_) => rolled_back.push(entry),
+            Err(err) => {
+                stuck_causes.push(format!("{}: {err}", entry.candidate.rel_path));
+                stuck_indices.push(idx);
+                stuck_entries.push(entry);
+            }
+        }
+    }
 
+    if !rolled_back.is_empty() {
+        let _ = write_delete_log(&state.trash_root, "rollback", batch_id, None, &rolled_back);
+    }
+    state.message = format!(
+        "Delete failed and was rolled back ({} file(s) put back). {}",
+        rolled_back.len(),
+        summarize_failures(&[cause])
+    );
+    if !stuck_entries.is_empty() {
+        for idx in stuck_indices {
+            if let Some(item) = state.items.get_mut(idx) {
+                item.state = CandidateState::Deleted;
+                state.selected.remove(&idx);
+            }
+        }
+        write_delete_log(&state.trash_root, "delete", batch_id, None, &stuck_entries)?;
+        state.undo_stack.push(stuck_entries);
+        state.message.push_str(&format!(
+            " Could not put back: {} Press 'u' to retry.",
+            summarize_failures(&stuck_causes)
+        ));
+    }
+    let _ = prune_empty_trash_sessions(&state.trash_root);
+    refresh_trash_index(state);
     Ok(())
 }
 
+/// The first few `path: cause` lines of a failed operation, for the status line.
+fn summarize_failures(failures: &[String]) -> String {
+    const SHOWN: usize = 3;
+    let mut summary = failures
+        .iter()
+        .take(SHOWN)
+        .cloned()
+        .collect::<Vec<_>>()
+        .join("; ");
+    if failures.len() > SHOWN {
+        summary.push_str(&format!("; and {} more", failures.len() - SHOWN));
+    }
+    summary.push('.');
+    summary
+}
+
 fn undo_last_deletion(state: &mut DeleteState) -> Result<()> {
     let Some(mut last_batch) = state.undo_stack.pop() else {
         state.message = "Nothing to undo.".to_string();
@@ -576,24 +2375,27 @@ fn undo_last_deletion(state: &mut DeleteState) -> Result<()> {
 
     let mut restored = 0usize;
     let mut failed = 0usize;
+    let mut conflicts = 0usize;
     let mut restored_candidates = Vec::new();
     let mut restored_entries = Vec::new();
     let batch_id = generate_batch_id();
 
-    for entry in last_batch.drain(..) {
+    for mut entry in last_batch.drain(..) {
         if let Some(parent) = entry.original_abs.parent() {
             fs::create_dir_all(parent)?;
         }
 
-        if entry.original_abs.exists() {
-            failed += 1;
+        let Some(destination) =
+            restore_destination(&entry.original_abs, state.on_conflict, &mut conflicts)
+        else {
             continue;
-        }
+        };
 
-        match fs::rename(&entry.trash_abs, &entry.original_abs) {
+        match fs::rename(&entry.trash_abs, &destination) {
             Ok(_) => {
                 restored += 1;
                 restored_candidates.push(entry.candidate.clone());
+                entry.original_abs = destination;
                 restored_entries.push(entry);
             }
             Err(_) => failed += 1,
@@ -614,12 +2416,23 @@ fn undo_last_deletion(state: &mut DeleteState) -> Result<()> {
     state.selected.clear();
     clamp_delete_cursor(state);
 
-    state.message = format!("Restored {restored} files. Failed: {failed}.");
+    state.message = format!(
+        "Restored {restored} files. Failed: {failed}.{}",
+        conflict_note(state.on_conflict, conflicts)
+    );
 
     // Undo log records are informational and should not block UX.
     if !restored_entries.is_empty() {
-        let _ = write_delete_log(&state.trash_root, "undo", &batch_id, &restored_entries);
+        let _ = write_delete_log(
+            &state.trash_root,
+            "undo",
+            &batch_id,
+            None,
+            &restored_entries,
+        );
     }
+    let _ = prune_empty_trash_sessions(&state.trash_root);
+    refresh_trash_index(state);
 
     Ok(())
 }
@@ -649,6 +2462,7 @@ fn write_delete_log(
     trash_root: &Path,
     action: &'static str,
     batch_id: &str,
+    session_name: Option<&str>,
     entries: &[DeletedEntry],
 ) -> Result<()> {
     fs::create_dir_all(trash_root)?;
@@ -660,6 +2474,7 @@ fn write_delete_log(
         let record = DeleteLogRecord {
             action,
             batch_id: batch_id.to_string(),
+            session_name: session_name.map(|v| v.to_string()),
             kind: String::new(),
             rel_path: String::new(),
             original_abs: String::new(),
@@ -673,10 +2488,11 @@ fn write_delete_log(
             let record = DeleteLogRecord {
                 action,
                 batch_id: batch_id.to_string(),
+                session_name: session_name.map(|v| v.to_string()),
                 kind: entry.candidate.kind.to_string(),
                 rel_path: entry.candidate.rel_path.clone(),
-                original_abs: entry.original_abs.display().to_string(),
-                trash_abs: entry.trash_abs.display().to_string(),
+                original_abs: shown_path(&entry.original_abs),
+                trash_abs: shown_path(&entry.trash_abs),
                 ts_unix_ms: ts,
             };
             payload.push_str(&serde_json::to_string(&record)?);
@@ -711,10 +2527,16 @@ fn empty_trash(state: &mut DeleteState) -> Result<()> {
         }
     }
 
+    let meta = state.trash_root.join("meta");
+    if meta.exists() {
+        fs::remove_dir_all(&meta)?;
+    }
+
     state.undo_stack.clear();
+    refresh_trash_index(state);
     state.message = format!("Trash emptied. Removed {removed} session entries.");
     let batch_id = generate_batch_id();
-    let _ = write_delete_log(&state.trash_root, "empty_trash", &batch_id, &[]);
+    let _ = write_delete_log(&state.trash_root, "empty_trash", &batch_id, None, &[]);
     Ok(())
 }
 
@@ -771,26 +2593,29 @@ fn restore_all_sessions(state: &mut DeleteState) -> Result<()> {
 
     let mut total_restored = 0usize;
     let mut total_failed = 0usize;
+    let mut total_conflicts = 0usize;
     let mut restored_session_count = 0usize;
 
     for (_session_id, session_path) in sessions {
-        let (restored, failed) =
+        let (restored, failed, conflicts) =
             restore_session_path_counts(state, &session_path, "restore_all_sessions")?;
         if restored > 0 || failed > 0 {
             restored_session_count += 1;
         }
         total_restored += restored;
         total_failed += failed;
+        total_conflicts += conflicts;
     }
 
+    let note = conflict_note(state.on_conflict, total_conflicts);
     state.message = if total_failed > 0 {
         format!(
-            "Restored {} files from {} session(s). Failed: {} (kept in trash).",
+            "Restored {} files from {} session(s). Failed: {} (kept in trash).{note}",
             total_restored, restored_session_count, total_failed
         )
     } else {
         format!(
-            "Restored {} files from {} session(s). Failed: {}.",
+            "Restored {} files from {} session(s). Failed: {}.{note}",
             total_restored, restored_session_count, total_failed
         )
     };
@@ -872,6 +2697,7 @@ where
 
     let mut restored = 0usize;
     let mut failed = 0usize;
+    let mut conflicts = 0usize;
     let mut restored_entries = Vec::new();
 
     for (rel_path, trash_abs) in matches {
@@ -880,10 +2706,9 @@ where
             failed += 1;
             continue;
         }
-        if target.exists() {
-            failed += 1;
+        let Some(target) = restore_destination(&target, state.on_conflict, &mut conflicts) else {
             continue;
-        }
+        };
         if let Some(parent) = target.parent() {
             fs::create_dir_all(parent)?;
         }
@@ -918,6 +2743,7 @@ where
     }
 
     let _ = prune_empty_trash_sessions(&state.trash_root);
+    refresh_trash_index(state);
     state
         .items
         .sort_by(|a, b| a.rel_path.cmp(&b.rel_path).then_with(|| a.kind.cmp(b.kind)));
@@ -926,9 +2752,18 @@ where
 
     if !restored_entries.is_empty() {
         let batch_id = generate_batch_id();
-        let _ = write_delete_log(&state.trash_root, log_action, &batch_id, &restored_entries);
+        let _ = write_delete_log(
+            &state.trash_root,
+            log_action,
+            &batch_id,
+            None,
+            &restored_entries,
+        );
     }
-    state.message = format!("Restored {restored} {scope} match(es). Failed: {failed}.");
+    state.message = format!(
+        "Restored {restored} {scope} match(es). Failed: {failed}.{}",
+        conflict_note(state.on_conflict, conflicts)
+    );
 
     Ok(())
 }
@@ -956,7 +2791,10 @@ fn latest_trashed_entries(trash_root: &Path) -> Result<BTreeMap<String, PathBuf>
             continue;
         };
 
-        for walked in WalkDir::new(&session_path).into_iter().filter_map(|e| e.ok()) {
+        for walked in WalkDir::new(&session_path)
+            .into_iter()
+            .filter_map(|e| e.ok())
+        {
             let file = walked.path();
             if !file.is_file() {
                 continue;
@@ -998,15 +2836,54 @@ fn upsert_candidate_state(
             state,
         });
     }
-}
-
-fn normalized_rel_query(input: &str) -> String {
-    input
-        .trim()
-        .replace('\\', "/")
-        .trim_start_matches("./")
-        .trim_matches('/')
-        .to_string()
+}
+
+fn normalized_rel_query(input: &str) -> String {
+    input
+        .trim()
+        .replace('\\', "/")
+        .trim_start_matches("./")
+        .trim_matches('/')
+        .to_string()
+}
+
+/// Removes the sessions of `.haadi_trash` trashed more than `max_age` ago, with their names,
+/// and returns how many went. A session's age comes from its `batch-<ms>` ID.
+fn empty_sessions_older_than(trash_root: &Path, max_age: std::time::Duration) -> Result<usize> {
+    let Ok(entries) = fs::read_dir(trash_root.join("sessions")) else {
+        return Ok(0);
+    };
+    let now = now_unix_ms();
+    let mut removed = 0usize;
+    for entry in entries.filter_map(|e| e.ok()) {
+        let session_path = entry.path();
+        let Some(trashed_ms) = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.strip_prefix("batch-"))
+            .and_then(|ms| ms.parse::<u128>().ok())
+        else {
+            continue;
+        };
+        if now.saturating_sub(trashed_ms) <= max_age.as_millis() || !session_path.is_dir() {
+            continue;
+        }
+        fs::remove_dir_all(&session_path).with_context(|| {
+            format!(
+                "Failed to empty trash session: {}",
+                shown_path(&session_path)
+            )
+        })?;
+        let mut meta_name = entry.file_name();
+        meta_name.push(".json");
+        let _ = fs::remove_file(trash_root.join("meta").join(meta_name));
+        removed += 1;
+    }
+    if removed > 0 {
+        info!(removed, "emptied trash sessions older than --trash-max-age");
+        let _ = write_delete_log(trash_root, "expire_trash", &generate_batch_id(), None, &[]);
+    }
+    Ok(removed)
 }
 
 fn prune_empty_trash_sessions(trash_root: &Path) -> Result<usize> {
@@ -1030,27 +2907,112 @@ fn prune_empty_trash_sessions(trash_root: &Path) -> Result<usize> {
             .any(|e| e.path().is_file());
         if !has_files {
             let _ = fs::remove_dir_all(&session_path);
+            if let Some(session_id) = session_path.file_name() {
+                let mut meta_name = session_id.to_os_string();
+                meta_name.push(".json");
+                let _ = fs::remove_file(trash_root.join("meta").join(meta_name));
+            }
             removed += 1;
         }
     }
     Ok(removed)
 }
 
+fn write_session_meta(trash_root: &Path, batch_id: &str, name: &str, files: usize) -> Result<()> {
+    let meta_dir = trash_root.join("meta");
+    fs::create_dir_all(&meta_dir)?;
+    let meta = SessionMeta {
+        batch_id: batch_id.to_string(),
+        name: name.to_string(),
+        created_unix_ms: now_unix_ms(),
+        files,
+    };
+    fs::write(
+        meta_dir.join(format!("{batch_id}.json")),
+        serde_json::to_string_pretty(&meta)?,
+    )?;
+    Ok(())
+}
+
+fn read_session_names(trash_root: &Path) -> BTreeMap<String, String> {
+    let mut names = BTreeMap::new();
+    let Ok(entries) = fs::read_dir(trash_root.join("meta")) else {
+        return names;
+    };
+
+    for entry in entries.filter_map(|e| e.ok()) {
+        let raw = fs::read_to_string(entry.path()).unwrap_or_default();
+        if let Ok(meta) = serde_json::from_str::<SessionMeta>(&raw) {
+            names.insert(meta.batch_id, meta.name);
+        }
+    }
+    names
+}
+
+/// Session directories under `.haadi_trash/sessions`, oldest first.
+fn list_trash_sessions(trash_root: &Path) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = fs::read_dir(trash_root.join("sessions")) else {
+        return Vec::new();
+    };
+
+    let mut sessions: Vec<(String, PathBuf)> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .filter_map(|path| {
+            let name = path.file_name()?.to_str()?.to_string();
+            Some((name, path))
+        })
+        .collect();
+    sessions.sort_by(|a, b| a.0.cmp(&b.0));
+    sessions
+}
+
+/// Rebuilds the rel_path -> session and session -> name lookups used for display.
+fn refresh_trash_index(state: &mut DeleteState) {
+    let sessions_root = state.trash_root.join("sessions");
+    state.trashed_sessions = latest_trashed_entries(&state.trash_root)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(rel, path)| {
+            let session = path
+                .strip_prefix(&sessions_root)
+                .ok()?
+                .components()
+                .next()?
+                .as_os_str()
+                .to_str()?
+                .to_string();
+            Some((rel, session))
+        })
+        .collect();
+    state.session_names = read_session_names(&state.trash_root);
+}
+
+fn session_label(state: &DeleteState, session_id: &str) -> String {
+    match state.session_names.get(session_id) {
+        Some(name) => format!("{session_id} ('{name}')"),
+        None => session_id.to_string(),
+    }
+}
+
 fn restore_session_path(
     state: &mut DeleteState,
     session_id: &str,
     session_path: &Path,
 ) -> Result<()> {
-    let (restored, failed) =
+    let session_id = &session_label(state, session_id);
+    let (restored, failed, conflicts) =
         restore_session_path_counts(state, session_path, "restore_previous_session")?;
+    let note = conflict_note(state.on_conflict, conflicts);
     state.message = if failed > 0 {
         format!(
-            "Restored {} files from session {}. Failed: {} (kept in trash).",
+            "Restored {} files from session {}. Failed: {} (kept in trash).{note}",
             restored, session_id, failed
         )
     } else {
         format!(
-            "Restored {} files from session {}. Failed: {}.",
+            "Restored {} files from session {}. Failed: {}.{note}",
             restored, session_id, failed
         )
     };
@@ -1061,13 +3023,14 @@ fn restore_session_path_counts(
     state: &mut DeleteState,
     session_path: &Path,
     log_action: &'static str,
-) -> Result<(usize, usize)> {
+) -> Result<(usize, usize, usize)> {
     let root = fs::canonicalize(&state.root).unwrap_or_else(|_| state.root.clone());
     let mut restored = 0usize;
     let mut failed = 0usize;
+    let mut conflicts = 0usize;
     let mut restored_entries = Vec::new();
 
-    for entry in WalkDir::new(&session_path)
+    for entry in WalkDir::new(session_path)
         .into_iter()
         .filter_map(|e| e.ok())
     {
@@ -1076,7 +3039,7 @@ fn restore_session_path_counts(
             continue;
         }
 
-        let Ok(rel) = trash_file.strip_prefix(&session_path) else {
+        let Ok(rel) = trash_file.strip_prefix(session_path) else {
             failed += 1;
             continue;
         };
@@ -1086,10 +3049,9 @@ fn restore_session_path_counts(
             failed += 1;
             continue;
         }
-        if target.exists() {
-            failed += 1;
+        let Some(target) = restore_destination(&target, state.on_conflict, &mut conflicts) else {
             continue;
-        }
+        };
         if let Some(parent) = target.parent() {
             fs::create_dir_all(parent)?;
         }
@@ -1132,12 +3094,76 @@ fn restore_session_path_counts(
 
     // Do not delete the whole session blindly: failed files must remain recoverable.
     let _ = prune_empty_trash_sessions(&state.trash_root);
+    refresh_trash_index(state);
     let batch_id = generate_batch_id();
     if !restored_entries.is_empty() {
-        let _ = write_delete_log(&state.trash_root, log_action, &batch_id, &restored_entries);
+        let _ = write_delete_log(
+            &state.trash_root,
+            log_action,
+            &batch_id,
+            None,
+            &restored_entries,
+        );
+    }
+
+    Ok((restored, failed, conflicts))
+}
+
+/// Where a trashed file goes back to: `target` itself when it is free, otherwise what
+/// `on_conflict` asks for (`None` skips the file, leaving it in the trash). Conflicts are
+/// counted in `conflicts`.
+fn restore_destination(
+    target: &Path,
+    on_conflict: OnConflict,
+    conflicts: &mut usize,
+) -> Option<PathBuf> {
+    if !target.exists() {
+        return Some(target.to_path_buf());
+    }
+    *conflicts += 1;
+    match on_conflict {
+        OnConflict::Skip => None,
+        // Directories are never replaced by a file.
+        OnConflict::Overwrite => target.is_file().then(|| target.to_path_buf()),
+        OnConflict::Alongside => Some(alongside_path(target)),
     }
+}
+
+/// First free `name.restored.ext` (then `name.restored-2.ext`, ...) next to `target`.
+fn alongside_path(target: &Path) -> PathBuf {
+    let stem = target
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let extension = target
+        .extension()
+        .map(|ext| format!(".{}", ext.to_string_lossy()))
+        .unwrap_or_default();
+    let mut counter = 1;
+    loop {
+        let suffix = if counter == 1 {
+            "restored".to_string()
+        } else {
+            format!("restored-{counter}")
+        };
+        let candidate = target.with_file_name(format!("{stem}.{suffix}{extension}"));
+        if !candidate.exists() {
+            return candidate;
+        }
+        counter += 1;
+    }
+}
 
-    Ok((restored, failed))
+fn conflict_note(on_conflict: OnConflict, conflicts: usize) -> String {
+    if conflicts == 0 {
+        return String::new();
+    }
+    let outcome = match on_conflict {
+        OnConflict::Skip => "skipped (kept in trash)",
+        OnConflict::Overwrite => "overwritten",
+        OnConflict::Alongside => "restored alongside as *.restored.*",
+    };
+    format!(" {conflicts} path(s) already existed: {outcome}.")
 }
 
 fn generate_batch_id() -> String {
@@ -1184,13 +3210,87 @@ fn hydrate_deleted_candidates_from_trash(state: &mut DeleteState) -> Result<()>
     Ok(())
 }
 
+#[cfg(feature = "tui")]
 fn draw_page(frame: &mut Frame, report: &Report, state: &TuiState) {
     match state.page {
         TuiPage::Summary => draw_summary_page(frame, report),
         TuiPage::Delete => draw_delete_page(frame, report, state),
+        TuiPage::CommentedCode => draw_commented_code_page(frame, report, state),
+        TuiPage::Dependencies => draw_dependencies_page(frame, report, state),
+    }
+    if state.show_help {
+        draw_help_overlay(frame, state.page);
+    }
+}
+
+#[cfg(feature = "tui")]
+fn draw_help_overlay(frame: &mut Frame, page: TuiPage) {
+    let (title, keys) = match page {
+        TuiPage::Summary => ("Help: summary page", SUMMARY_KEYS),
+        TuiPage::Delete => ("Help: delete page", DELETE_KEYS),
+        TuiPage::CommentedCode => ("Help: commented-out code page", COMMENTED_CODE_KEYS),
+        TuiPage::Dependencies => ("Help: dependencies page", DEPENDENCY_KEYS),
+    };
+    let key_width = keys.iter().map(|(key, _)| key.width()).max().unwrap_or(0);
+    let mut lines: Vec<Line> = keys
+        .iter()
+        .map(|(key, description)| Line::from(format!("{key:<key_width$}  {description}")))
+        .collect();
+    if page == TuiPage::Delete {
+        lines.push(Line::from(""));
+        lines.push(Line::from(
+            "Deleted files move to .haadi_trash, stay listed in red, and can be restored.",
+        ));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from("Press any key to close."));
+
+    let area = centered_rect(70, 70, frame.area());
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .wrap(Wrap { trim: false }),
+        area,
+    );
+}
+
+/// Short hints for the keys that matter in the delete page's current state; the full list is
+/// behind `?`.
+fn delete_page_hints(state: &DeleteState) -> String {
+    if state.show_folders {
+        return "j/k pick folder | l/h expand/collapse | t/Enter back to list | ? help | q quit"
+            .to_string();
     }
+    let mut hints = vec![
+        "j/k move",
+        "space select",
+        "/ search",
+        "f filter",
+        "t folders",
+    ];
+    if !state.selected.is_empty() {
+        hints.push("x delete");
+    }
+    if !state.undo_stack.is_empty() {
+        hints.push("u undo");
+    }
+    if state
+        .items
+        .iter()
+        .any(|item| item.state == CandidateState::Deleted)
+    {
+        hints.push("i restore");
+    }
+    if !state.search_query.is_empty() || state.filter != DeleteFilter::All || state.scope.is_some()
+    {
+        hints.push("g reset");
+    }
+    hints.extend(["? help", "b back", "q quit"]);
+    hints.join(" | ")
 }
 
+#[cfg(feature = "tui")]
 fn draw_summary_page(frame: &mut Frame, report: &Report) {
     let root_chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -1203,7 +3303,7 @@ fn draw_summary_page(frame: &mut Frame, report: &Report) {
         .split(frame.area());
 
     let title = Paragraph::new(format!(
-        "haadi summary | {} | d delete page | q quit",
+        "haadi summary | {} | d delete page | u dependencies | c commented code | ? help | q quit",
         report.root
     ))
     .block(Block::default().borders(Borders::ALL).title("Report"));
@@ -1353,11 +3453,155 @@ fn draw_summary_page(frame: &mut Frame, report: &Report) {
     );
 }
 
+#[cfg(feature = "tui")]
+fn draw_dependencies_page(frame: &mut Frame, report: &Report, state: &TuiState) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(8)])
+        .split(frame.area());
+    frame.render_widget(
+        Paragraph::new("j/k move | ? help | b back | q quit").block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Dependencies: who uses what"),
+        ),
+        chunks[0],
+    );
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Min(20)])
+        .split(chunks[1]);
+
+    let deps = &report.dependency_usage;
+    let window = (columns[0].height.saturating_sub(2) as usize).max(1);
+    let width = columns[0].width.saturating_sub(2) as usize;
+    let start = state
+        .dependency_cursor
+        .saturating_sub(window.saturating_sub(1));
+    let end = (start + window).min(deps.len());
+    let mut rows: Vec<ListItem> = deps[start..end]
+        .iter()
+        .enumerate()
+        .map(|(visual_idx, usage)| {
+            let marker = if start + visual_idx == state.dependency_cursor {
+                ">"
+            } else {
+                " "
+            };
+            let uses = if !usage.files.is_empty() {
+                format!("{} files", usage.files.len())
+            } else if usage.used {
+                usage.other_uses.join(", ")
+            } else {
+                "unused".to_string()
+            };
+            let row = ListItem::new(truncate_middle(
+                &format!("{marker} {} ({uses})", usage.name),
+                width,
+            ));
+            if usage.used {
+                row
+            } else {
+                row.style(Style::default().fg(Color::Yellow))
+            }
+        })
+        .collect();
+    if rows.is_empty() {
+        rows.push(ListItem::new("No dependencies in package.json."));
+    }
+    frame.render_widget(
+        List::new(rows).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Dependencies {}", deps.len())),
+        ),
+        columns[0],
+    );
+
+    let lines: Vec<Line> = state
+        .dependency_details
+        .iter()
+        .flat_map(|(_, lines)| lines)
+        .map(|line| Line::from(line.as_str()))
+        .collect();
+    let title = deps
+        .get(state.dependency_cursor)
+        .map_or_else(String::new, |usage| usage.name.clone());
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .wrap(Wrap { trim: false }),
+        columns[1],
+    );
+}
+
+#[cfg(feature = "tui")]
+fn draw_commented_code_page(frame: &mut Frame, report: &Report, state: &TuiState) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(4),
+            Constraint::Min(8),
+            Constraint::Length(3),
+        ])
+        .split(frame.area());
+
+    let header = Paragraph::new(vec![
+        Line::from("Commented-out code blocks with import/export statements"),
+        Line::from("j/k move | Enter open in editor | ? help | b back | q quit"),
+    ])
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Commented-out code"),
+    )
+    .wrap(Wrap { trim: true });
+    frame.render_widget(header, chunks[0]);
+
+    let blocks = &report.commented_code;
+    let mut rows = Vec::new();
+    if blocks.is_empty() {
+        rows.push(ListItem::new(
+            "No commented-out code blocks (the check runs with --commented-code).",
+        ));
+    } else {
+        let window = (chunks[1].height.saturating_sub(2) as usize).max(1);
+        let width = chunks[1].width.saturating_sub(2) as usize;
+        let start = state.code_cursor.saturating_sub(window.saturating_sub(1));
+        let end = (start + window).min(blocks.len());
+        for (visual_idx, item) in blocks[start..end].iter().enumerate() {
+            let marker = if start + visual_idx == state.code_cursor {
+                ">"
+            } else {
+                " "
+            };
+            let location = format!("{}:{}-{}", item.file, item.start_line, item.end_line);
+            let text = format!("{marker} {location}  {}", item.statement);
+            rows.push(ListItem::new(truncate_middle(&text, width)));
+        }
+    }
+    frame.render_widget(
+        List::new(rows).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Blocks {}", blocks.len())),
+        ),
+        chunks[1],
+    );
+
+    frame.render_widget(
+        Paragraph::new(state.code_message.as_str())
+            .block(Block::default().borders(Borders::ALL).title("Status")),
+        chunks[2],
+    );
+}
+
+#[cfg(feature = "tui")]
 fn draw_delete_page(frame: &mut Frame, _report: &Report, state: &TuiState) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(5),
+            Constraint::Length(4),
             Constraint::Min(8),
             Constraint::Length(4),
         ])
@@ -1365,19 +3609,31 @@ fn draw_delete_page(frame: &mut Frame, _report: &Report, state: &TuiState) {
 
     let header = Paragraph::new(vec![
         Line::from("Delete page: select unused files/assets only"),
-        Line::from("Controls: j/k move | space toggle | a all | c clear | f filter | / search | g reset search+filter | x delete | u undo | i restore file (search) | o restore folder (search) | r restore prev | R restore all | z empty trash | y approve | b back | q quit"),
-        Line::from("Deleted files are shown in red and remain searchable for restore."),
+        Line::from(delete_page_hints(&state.delete)),
     ])
     .block(Block::default().borders(Borders::ALL).title("Delete mode"))
     .wrap(Wrap { trim: true });
     frame.render_widget(header, chunks[0]);
 
+    let list_area = if state.delete.show_folders {
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(30), Constraint::Min(20)])
+            .split(chunks[1]);
+        draw_folder_sidebar(frame, columns[0], &state.delete);
+        columns[1]
+    } else {
+        chunks[1]
+    };
+
     let filtered = filtered_indices(&state.delete);
+    let range = active_range(&state.delete);
     let mut rows = Vec::new();
     if filtered.is_empty() {
         rows.push(ListItem::new("No delete candidates."));
     } else {
-        let list_height = chunks[1].height.saturating_sub(2) as usize;
+        let list_height = list_area.height.saturating_sub(2) as usize;
+        let list_width = list_area.width.saturating_sub(2) as usize;
         let window = list_height.max(1);
         let start = state.delete.cursor.saturating_sub(window.saturating_sub(1));
         let end = (start + window).min(filtered.len());
@@ -1395,41 +3651,56 @@ fn draw_delete_page(frame: &mut Frame, _report: &Report, state: &TuiState) {
             } else {
                 "[ ]"
             };
-            let text = format!(
-                "{marker} {selected} ({}) {}",
+            let label = format!(
+                "({})",
                 if item.state == CandidateState::Deleted {
                     "deleted"
                 } else {
                     item.kind
-                },
-                item.rel_path
+                }
             );
+            // Pad the label so paths line up regardless of row state.
+            let prefix = format!("{marker} {selected} {label:<9} ");
+            let path_width = list_width.saturating_sub(prefix.width());
+            let text = format!("{prefix}{}", truncate_middle(&item.rel_path, path_width));
             let mut row = ListItem::new(text);
             if item.state == CandidateState::Deleted {
                 row = row.style(Style::default().fg(Color::Red));
             }
+            if range.as_ref().is_some_and(|r| r.contains(&cursor_idx)) {
+                row = row.style(Style::default().bg(Color::DarkGray));
+            }
             rows.push(row);
         }
     }
 
     frame.render_widget(
         List::new(rows).block(Block::default().borders(Borders::ALL).title(format!(
-            "Candidates {} | filter={} | search='{}'",
+            "Candidates {} | filter={} | search='{}'{}",
             filtered.len(),
             state.delete.filter.label(),
             if state.delete.search_query.is_empty() {
                 "(none)"
             } else {
                 state.delete.search_query.as_str()
+            },
+            match &state.delete.scope {
+                Some(scope) => format!(" | folder={scope}/"),
+                None => String::new(),
             }
         ))),
-        chunks[1],
+        list_area,
     );
 
     let mut footer_lines = vec![Line::from(state.delete.message.as_str())];
-    if state.delete.confirm_delete {
+    if state.delete.editing_session_name {
+        footer_lines.push(Line::from(format!(
+            "Batch name: {}",
+            state.delete.session_name_input
+        )));
+    } else if state.delete.confirm_delete {
         footer_lines.push(Line::from(
-            "Approve delete: press y to confirm, n/Esc to cancel.",
+            "Approve delete: press y to confirm, m to name the batch, n/Esc to cancel.",
         ));
     } else if state.delete.confirm_empty_trash {
         footer_lines.push(Line::from(
@@ -1443,6 +3714,11 @@ fn draw_delete_page(frame: &mut Frame, _report: &Report, state: &TuiState) {
         footer_lines.push(Line::from(
             "Approve restore ALL sessions: press y to confirm, n/Esc to cancel.",
         ));
+    } else if let Some(range) = &range {
+        footer_lines.push(Line::from(format!(
+            "Range: {} items | space toggle range | Esc cancel",
+            range.clone().count()
+        )));
     } else if state.delete.editing_search {
         footer_lines.push(Line::from(format!(
             "Search input: {}",
@@ -1459,8 +3735,254 @@ fn draw_delete_page(frame: &mut Frame, _report: &Report, state: &TuiState) {
         .block(Block::default().borders(Borders::ALL).title("Status"))
         .wrap(Wrap { trim: true });
     frame.render_widget(footer, chunks[2]);
+
+    if state.delete.show_detail {
+        draw_detail_popup(frame, &state.delete, &filtered);
+    }
+    if is_confirming(&state.delete) {
+        draw_confirm_popup(frame, &state.delete);
+    }
+}
+
+#[cfg(feature = "tui")]
+fn draw_folder_sidebar(frame: &mut Frame, area: Rect, state: &DeleteState) {
+    let folders = folder_rows(state);
+    let height = (area.height.saturating_sub(2) as usize).max(1);
+    let width = area.width.saturating_sub(2) as usize;
+    let start = state.folder_cursor.saturating_sub(height.saturating_sub(1));
+    let end = (start + height).min(folders.len());
+
+    let rows: Vec<ListItem> = folders[start..end]
+        .iter()
+        .enumerate()
+        .map(|(visual_idx, folder)| {
+            let marker = if start + visual_idx == state.folder_cursor {
+                ">"
+            } else {
+                " "
+            };
+            let toggle = if !folder.has_children || folder.path.is_empty() {
+                " "
+            } else if state.expanded_folders.contains(&folder.path) {
+                "-"
+            } else {
+                "+"
+            };
+            let name = match folder.path.rsplit_once('/') {
+                _ if folder.path.is_empty() => "(all)".to_string(),
+                Some((_, name)) => format!("{name}/"),
+                None => format!("{}/", folder.path),
+            };
+            let indent = "  ".repeat(folder.depth.saturating_sub(1));
+            let text = format!("{marker} {indent}{toggle} {name} ({})", folder.candidates);
+            let mut row = ListItem::new(truncate_middle(&text, width));
+            if state.scope.as_deref().unwrap_or_default() == folder.path {
+                row = row.style(Style::default().add_modifier(Modifier::BOLD));
+            }
+            row
+        })
+        .collect();
+
+    frame.render_widget(
+        List::new(rows).block(Block::default().borders(Borders::ALL).title("Folders")),
+        area,
+    );
+}
+
+#[cfg(feature = "tui")]
+fn draw_confirm_popup(frame: &mut Frame, state: &DeleteState) {
+    let (title, keys) = if state.confirm_delete {
+        let title = match state.trash.mode {
+            TrashMode::Repo => "Move to trash",
+            TrashMode::System => "Move to the system trash",
+            TrashMode::None => "Permanently delete (--trash none)",
+        };
+        (title, "y confirm | m name batch | n/Esc cancel")
+    } else if state.confirm_empty_trash {
+        ("Permanently delete from trash", "y confirm | n/Esc cancel")
+    } else if state.confirm_restore_previous {
+        (
+            "Restore previous session",
+            "y confirm | C change | n/Esc cancel",
+        )
+    } else {
+        (
+            "Restore ALL sessions",
+            "y confirm | C change | n/Esc cancel",
+        )
+    };
+    let keys = if state.confirm_restore_previous || state.confirm_restore_all {
+        format!("on conflict: {} | {keys}", state.on_conflict.label())
+    } else {
+        keys.to_string()
+    };
+    let total = state.confirm_paths.len();
+    // Shrink the dialog to short lists, up to 60% of the screen height.
+    let max_area = centered_rect(80, 60, frame.area());
+    let height = (total.max(1) as u16).saturating_add(4).min(max_area.height);
+    let area = Rect {
+        y: max_area.y + (max_area.height - height) / 2,
+        height,
+        ..max_area
+    };
+    let width = area.width.saturating_sub(2) as usize;
+    let visible = (area.height.saturating_sub(4) as usize).max(1);
+    state.confirm_visible.set(visible);
+    let start = state.confirm_scroll.min(total.saturating_sub(visible));
+    let end = (start + visible).min(total);
+
+    let mut lines: Vec<Line> = if total == 0 {
+        vec![Line::from("(no files)")]
+    } else {
+        state.confirm_paths[start..end]
+            .iter()
+            .map(|path| Line::from(truncate_middle(path, width)))
+            .collect()
+    };
+    lines.resize(visible, Line::from(""));
+    lines.push(Line::from(""));
+    lines.push(Line::from(if total > visible {
+        format!("{}-{} of {total} | j/k scroll | {keys}", start + 1, end)
+    } else {
+        keys.to_string()
+    }));
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("{title}: {total} files")),
+        ),
+        area,
+    );
+}
+
+#[cfg(feature = "tui")]
+fn draw_detail_popup(frame: &mut Frame, state: &DeleteState, filtered: &[usize]) {
+    let area = centered_rect(70, 40, frame.area());
+    let lines = match filtered
+        .get(state.cursor)
+        .and_then(|idx| state.items.get(*idx))
+    {
+        Some(item) => vec![
+            Line::from(format!("path: {}", item.rel_path)),
+            Line::from(format!("kind: {}", item.kind)),
+            Line::from(format!(
+                "finding id: {}",
+                finding_id(
+                    if item.kind == "asset" {
+                        UNUSED_ASSET
+                    } else {
+                        UNUSED_FILE
+                    },
+                    &item.rel_path,
+                    None
+                )
+            )),
+            Line::from(format!(
+                "state: {}",
+                if item.state == CandidateState::Deleted {
+                    "deleted (in trash)"
+                } else {
+                    "active"
+                }
+            )),
+            Line::from(format!(
+                "trash session: {}",
+                match state.trashed_sessions.get(&item.rel_path) {
+                    Some(id) if item.state == CandidateState::Deleted => session_label(state, id),
+                    _ => "-".to_string(),
+                }
+            )),
+            Line::from(format!(
+                "selected: {}",
+                state.selected.contains(&filtered[state.cursor])
+            )),
+            Line::from(""),
+            Line::from("p/Esc close"),
+        ],
+        None => vec![
+            Line::from("No item highlighted."),
+            Line::from("p/Esc close"),
+        ],
+    };
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(lines)
+            .block(Block::default().borders(Borders::ALL).title("Details"))
+            .wrap(Wrap { trim: false }),
+        area,
+    );
+}
+
+#[cfg(feature = "tui")]
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(vertical[1])[1]
+}
+
+/// Shortens `text` to at most `max_width` terminal columns by replacing the middle with `…`,
+/// keeping both the leading directories and the file name visible. Wide (CJK/emoji)
+/// characters are measured by display width, not by byte or char count.
+fn truncate_middle(text: &str, max_width: usize) -> String {
+    if text.width() <= max_width {
+        return text.to_string();
+    }
+    if max_width == 0 {
+        return String::new();
+    }
+    if max_width == 1 {
+        return "…".to_string();
+    }
+
+    let budget = max_width - 1;
+    let tail_budget = budget / 2;
+    let head_budget = budget - tail_budget;
+
+    let mut head = String::new();
+    let mut head_width = 0;
+    for ch in text.chars() {
+        let w = ch.width().unwrap_or(0);
+        if head_width + w > head_budget {
+            break;
+        }
+        head.push(ch);
+        head_width += w;
+    }
+
+    let mut tail = Vec::new();
+    let mut tail_width = 0;
+    for ch in text.chars().rev() {
+        let w = ch.width().unwrap_or(0);
+        if tail_width + w > tail_budget {
+            break;
+        }
+        tail.push(ch);
+        tail_width += w;
+    }
+    tail.reverse();
+
+    format!("{head}…{}", tail.into_iter().collect::<String>())
 }
 
+#[cfg(feature = "tui")]
 fn top_items(items: &[String], limit: usize) -> Vec<ListItem<'_>> {
     if items.is_empty() {
         return vec![ListItem::new("(none)")];
@@ -1497,6 +4019,16 @@ fn build_delete_candidates(report: &Report) -> Vec<DeleteCandidate> {
 }
 
 fn filtered_indices(state: &DeleteState) -> Vec<usize> {
+    let mut indices = unscoped_indices(state);
+    if let Some(scope) = &state.scope {
+        let prefix = format!("{scope}/");
+        indices.retain(|idx| state.items[*idx].rel_path.starts_with(&prefix));
+    }
+    indices
+}
+
+/// Candidates passing the kind filter and the search, in any folder.
+fn unscoped_indices(state: &DeleteState) -> Vec<usize> {
     let query = state.search_query.trim();
     let matcher = build_search_matcher(query);
     state
@@ -1547,9 +4079,12 @@ fn glob_like_to_regex(glob: &str) -> String {
 }
 
 fn looks_like_regex(query: &str) -> bool {
-    query
-        .chars()
-        .any(|c| matches!(c, '[' | ']' | '(' | ')' | '|' | '+' | '^' | '$' | '{' | '}' | '\\' | '.'))
+    query.chars().any(|c| {
+        matches!(
+            c,
+            '[' | ']' | '(' | ')' | '|' | '+' | '^' | '$' | '{' | '}' | '\\' | '.'
+        )
+    })
 }
 
 enum SearchMatcher {
@@ -1596,16 +4131,18 @@ fn build_search_matcher(query: &str) -> SearchMatcher {
         return SearchMatcher::Substring(q.to_ascii_lowercase());
     }
 
-    if looks_like_regex(q) {
-        if let Some(re) = compile_case_insensitive_regex(q) {
-            return SearchMatcher::Regex(re);
-        }
+    if looks_like_regex(q)
+        && let Some(re) = compile_case_insensitive_regex(q)
+    {
+        return SearchMatcher::Regex(re);
     }
 
     SearchMatcher::Substring(q.to_ascii_lowercase())
 }
 
 fn clamp_delete_cursor(state: &mut DeleteState) {
+    // Any change to the visible list invalidates range positions.
+    state.range_anchor = None;
     let len = filtered_indices(state).len();
     if len == 0 {
         state.cursor = 0;
@@ -1,107 +1,525 @@
 use super::*;
+use std::time::SystemTime;
+
+/// Parsed modules kept across `--watch` re-runs. A file is parsed again only when its
+/// modification time or size changed, and only the files of the latest run stay cached.
+#[derive(Debug, Default)]
+pub(crate) struct ParseCache {
+    modules: HashMap<PathBuf, (Option<SystemTime>, u64, ModuleInfo)>,
+    hits: usize,
+    misses: usize,
+    parsed_bytes: u64,
+}
+
+impl ParseCache {
+    /// Parses `files`, reusing cached modules. Fresh parses run on `threads` worker threads
+    /// (all cores when `None` or 0; sequential without the `parallel` feature), and
+    /// `on_parsed` runs once per file from whichever thread handled it.
+    pub(crate) fn parse_all<'a>(
+        &mut self,
+        files: impl IntoIterator<Item = &'a PathBuf>,
+        threads: Option<usize>,
+        on_parsed: impl Fn() + Sync,
+    ) -> Result<HashMap<PathBuf, ModuleInfo>> {
+        let mut modules = HashMap::new();
+        let mut stale = Vec::new();
+        for file in files {
+            let metadata = fs::metadata(file)
+                .with_context(|| format!("Failed to read source file: {}", shown_path(file)))?;
+            let modified = metadata.modified().ok();
+            if let Some((cached_modified, cached_len, module)) = self.modules.get(file)
+                && modified.is_some()
+                && *cached_modified == modified
+                && *cached_len == metadata.len()
+            {
+                self.hits += 1;
+                trace!(file = %shown_path(file), "reused parsed module");
+                modules.insert(file.clone(), module.clone());
+                on_parsed();
+            } else {
+                stale.push((file, modified, metadata.len()));
+            }
+        }
+
+        let parse = |&(file, modified, len): &(&'a PathBuf, Option<SystemTime>, u64)| {
+            check_interrupted()?;
+            let module = parse_module(file)?;
+            trace!(
+                file = %shown_path(file),
+                imports = module.imports.len(),
+                exports = module.exports.len(),
+                "parsed module"
+            );
+            on_parsed();
+            Ok((file, modified, len, module))
+        };
+        let parsed: Vec<_> = parse_in_parallel(&stale, threads, parse)?;
+
+        self.misses += parsed.len();
+        self.parsed_bytes += parsed.iter().map(|(_, _, len, _)| len).sum::<u64>();
+        for (file, modified, len, module) in parsed {
+            self.modules
+                .insert(file.clone(), (modified, len, module.clone()));
+            modules.insert(file.clone(), module);
+        }
+        // Files deleted or renamed since the last run would otherwise stay cached for the
+        // whole `--watch`, `lsp`, or `mcp` session.
+        self.modules.retain(|file, _| modules.contains_key(file));
+        Ok(modules)
+    }
+
+    /// Cache hits and fresh parses since the last call.
+    pub(crate) fn take_counts(&mut self) -> (usize, usize) {
+        self.parsed_bytes = 0;
+        (
+            std::mem::take(&mut self.hits),
+            std::mem::take(&mut self.misses),
+        )
+    }
+
+    /// Size of the files parsed fresh since the last [`Self::take_counts`].
+    pub(crate) fn parsed_bytes(&self) -> u64 {
+        self.parsed_bytes
+    }
+}
+
+/// Maps `parse` over `items` on a rayon pool of `threads` workers, or the global pool (sized
+/// by `RAYON_NUM_THREADS`, else the core count) when `threads` is `None` or 0.
+#[cfg(feature = "parallel")]
+fn parse_in_parallel<T: Sync, R: Send>(
+    items: &[T],
+    threads: Option<usize>,
+    parse: impl Fn(&T) -> Result<R> + Sync,
+) -> Result<Vec<R>> {
+    use rayon::prelude::*;
+    let run = || items.par_iter().map(&parse).collect();
+    match threads.filter(|&threads| threads > 0) {
+        Some(threads) => rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .context("Failed to start parser threads")?
+            .install(run),
+        None => run(),
+    }
+}
+
+#[cfg(not(feature = "parallel"))]
+fn parse_in_parallel<T, R>(
+    items: &[T],
+    _threads: Option<usize>,
+    parse: impl Fn(&T) -> Result<R>,
+) -> Result<Vec<R>> {
+    items.iter().map(parse).collect()
+}
+
 pub(crate) fn parse_module(file: &Path) -> Result<ModuleInfo> {
     let source = fs::read_to_string(file)
-        .with_context(|| format!("Failed to read source file: {}", file.display()))?;
-    let source = strip_comments(&source);
+        .with_context(|| format!("Failed to read source file: {}", shown_path(file)))?;
+    let mut info = parse_source(&source);
+    info.imports.extend(gatsby_template_imports(file, &source));
+    Ok(info)
+}
+
+/// Parses the imports and exports of one module's source text.
+pub(crate) fn parse_source(raw: &str) -> ModuleInfo {
+    let source = strip_comments(raw);
+    let starts = StatementStarts::scan(&source);
 
     let mut info = ModuleInfo::default();
 
-    for caps in IMPORT_FROM_RE.captures_iter(&source) {
+    let import_statements = captures_at(&IMPORT_FROM_AT_RE, &source, &starts.imports);
+    for (base, caps) in &import_statements {
         let clause = caps.get(1).map(|m| m.as_str()).unwrap_or_default();
         let specifier = caps.get(2).map(|m| m.as_str()).unwrap_or_default();
 
         let mut record = ImportRecord {
             specifier: specifier.to_string(),
+            line: starts.line(*base),
             ..Default::default()
         };
         parse_import_clause(clause, &mut record);
         info.imports.push(record);
     }
 
-    for caps in IMPORT_SIDE_EFFECT_RE.captures_iter(&source) {
+    for (base, caps) in captures_at(&IMPORT_SIDE_EFFECT_AT_RE, &source, &starts.imports) {
         let specifier = caps.get(1).map(|m| m.as_str()).unwrap_or_default();
         info.imports.push(ImportRecord {
             specifier: specifier.to_string(),
+            line: starts.line(base),
             side_effect_only: true,
             ..Default::default()
         });
     }
 
-    for caps in REQUIRE_RE.captures_iter(&source) {
-        let specifier = caps.get(1).map(|m| m.as_str()).unwrap_or_default();
-        info.imports.push(ImportRecord {
-            specifier: specifier.to_string(),
-            uses_namespace: true,
-            ..Default::default()
-        });
-    }
+    // Literal checks skip the unanchored call patterns in files without such calls.
+    if source.contains("require(") {
+        for caps in REQUIRE_RE.captures_iter(&source) {
+            let specifier = caps.get(1).map(|m| m.as_str()).unwrap_or_default();
+            info.imports.push(ImportRecord {
+                specifier: specifier.to_string(),
+                line: starts.line(caps.get(0).map_or(0, |m| m.start())),
+                uses_namespace: true,
+                ..Default::default()
+            });
+        }
 
-    for caps in DESTRUCTURE_REQUIRE_RE.captures_iter(&source) {
-        let names = caps.get(1).map(|m| m.as_str()).unwrap_or_default();
-        let specifier = caps.get(2).map(|m| m.as_str()).unwrap_or_default();
-        let mut record = ImportRecord {
-            specifier: specifier.to_string(),
-            ..Default::default()
-        };
-        for name in parse_destructured_names(names) {
-            record.names.insert(name);
+        for caps in DESTRUCTURE_REQUIRE_RE.captures_iter(&source) {
+            let names = caps.get(1).map(|m| m.as_str()).unwrap_or_default();
+            let specifier = caps.get(2).map(|m| m.as_str()).unwrap_or_default();
+            let mut record = ImportRecord {
+                specifier: specifier.to_string(),
+                line: starts.line(caps.get(0).map_or(0, |m| m.start())),
+                ..Default::default()
+            };
+            for name in parse_destructured_names(names) {
+                record.names.insert(name);
+            }
+            record.bindings = parse_destructured_bindings(names);
+            info.imports.push(record);
         }
-        info.imports.push(record);
     }
 
-    for caps in DYN_IMPORT_RE.captures_iter(&source) {
-        let specifier = caps.get(1).map(|m| m.as_str()).unwrap_or_default();
-        info.imports.push(ImportRecord {
-            specifier: specifier.to_string(),
-            uses_namespace: true,
-            ..Default::default()
-        });
+    if source.contains("import(") {
+        for caps in DYN_IMPORT_RE.captures_iter(&source) {
+            let specifier = caps.get(1).map(|m| m.as_str()).unwrap_or_default();
+            info.imports.push(ImportRecord {
+                specifier: specifier.to_string(),
+                line: starts.line(caps.get(0).map_or(0, |m| m.start())),
+                uses_namespace: true,
+                ..Default::default()
+            });
+        }
     }
 
-    for caps in EXPORT_DECL_RE.captures_iter(&source) {
-        let name = caps.get(1).map(|m| m.as_str()).unwrap_or_default();
-        if !name.is_empty() {
-            info.exports.insert(name.to_string());
-        }
+    for (base, caps) in captures_at(&EXPORT_DECL_AT_RE, &source, &starts.exports) {
+        let Some(name) = caps.get(1).filter(|m| !m.as_str().is_empty()) else {
+            continue;
+        };
+        info.exports.insert(name.as_str().to_string());
+        info.export_positions
+            .entry(name.as_str().to_string())
+            .or_insert_with(|| starts.line_col(&source, base + name.start()));
     }
 
-    for caps in EXPORT_LIST_RE.captures_iter(&source) {
+    for (base, caps) in captures_at(&EXPORT_LIST_AT_RE, &source, &starts.exports) {
         let names = caps.get(1).map(|m| m.as_str()).unwrap_or_default();
         let src = caps.get(2).map(|m| m.as_str());
 
         if let Some(specifier) = src {
             let mut record = ImportRecord {
                 specifier: specifier.to_string(),
+                line: starts.line(base),
                 is_reexport: true,
                 ..Default::default()
             };
             parse_export_list_as_import(names, &mut record);
             info.imports.push(record);
         } else {
+            let list_start = base + caps.get(1).map_or(0, |m| m.start());
             for name in parse_export_names(names) {
+                // `a as b` exports `b`, the last matching token in the list.
+                let offset = IDENT_TOKEN_RE
+                    .find_iter(names)
+                    .filter(|token| token.as_str() == name)
+                    .last()
+                    .map_or(list_start, |token| list_start + token.start());
+                info.export_positions
+                    .entry(name.clone())
+                    .or_insert_with(|| starts.line_col(&source, offset));
                 info.exports.insert(name);
             }
         }
     }
 
-    if EXPORT_DEFAULT_RE.is_match(&source) {
+    if let Some(&start) = starts
+        .exports
+        .iter()
+        .find(|&&start| EXPORT_DEFAULT_AT_RE.is_match(&source[start..]))
+    {
         info.has_default_export = true;
+        info.export_positions
+            .entry("default".to_string())
+            .or_insert_with(|| starts.line_col(&source, start));
     }
 
-    for caps in EXPORT_ALL_RE.captures_iter(&source) {
-        info.has_export_all = true;
-        let specifier = caps.get(1).map(|m| m.as_str()).unwrap_or_default();
-        info.imports.push(ImportRecord {
+    for (base, caps) in captures_at(&EXPORT_ALL_AT_RE, &source, &starts.exports) {
+        let specifier = caps.get(2).map(|m| m.as_str()).unwrap_or_default();
+        let mut record = ImportRecord {
             specifier: specifier.to_string(),
+            line: starts.line(base),
             uses_namespace: true,
             is_reexport: true,
             ..Default::default()
-        });
+        };
+        // `export * as ns from` exports one named binding; only a bare `export *` forwards
+        // names this module does not list.
+        match caps.get(1) {
+            Some(alias) => {
+                let name = alias.as_str().to_string();
+                info.export_positions
+                    .entry(name.clone())
+                    .or_insert_with(|| starts.line_col(&source, base + alias.start()));
+                info.exports.insert(name.clone());
+                record.bindings.insert(name, "*".to_string());
+            }
+            None => info.has_export_all = true,
+        }
+        info.imports.push(record);
     }
 
-    Ok(info)
+    for caps in JSX_ELEMENT_RE.captures_iter(&source) {
+        info.jsx_elements.insert(caps[1].to_string());
+    }
+    let statements: Vec<(usize, usize)> = import_statements
+        .iter()
+        .map(|(base, caps)| (*base, base + caps.get(0).map_or(0, |m| m.end())))
+        .collect();
+    info.referenced_bindings = referenced_bindings(&source, &statements, &info.imports);
+    if raw.len() != source.len() || raw != source {
+        let comments = comment_text(raw, &source);
+        info.commented_imports = commented_out_imports(&comments);
+        info.commented_code = commented_code_blocks(raw, &source, &comments);
+    }
+
+    info
+}
+
+/// Byte offsets of the `import` and `export` keywords that begin a line, collected in one pass
+/// over the source, so the statement patterns run anchored at those offsets instead of each
+/// scanning the whole file. The pass also records where each line starts, for positions.
+#[derive(Debug, Default)]
+struct StatementStarts {
+    imports: Vec<usize>,
+    exports: Vec<usize>,
+    lines: Vec<usize>,
 }
 
+impl StatementStarts {
+    fn scan(source: &str) -> Self {
+        let mut starts = Self::default();
+        let mut offset = 0;
+        for line in source.split_inclusive('\n') {
+            starts.lines.push(offset);
+            let keyword = line.trim_start();
+            let start = offset + line.len() - keyword.len();
+            if keyword.starts_with("import") {
+                starts.imports.push(start);
+            } else if keyword.starts_with("export") {
+                starts.exports.push(start);
+            }
+            offset += line.len();
+        }
+        starts
+    }
+
+    /// 1-based line of `offset`.
+    fn line(&self, offset: usize) -> usize {
+        self.lines.partition_point(|&start| start <= offset).max(1)
+    }
+
+    /// [`line_col`] from the recorded line starts, without rescanning the text before `offset`.
+    fn line_col(&self, source: &str, offset: usize) -> (usize, usize) {
+        let line = self.line(offset);
+        let line_start = self.lines.get(line - 1).copied().unwrap_or(0);
+        (line, source[line_start..offset].chars().count() + 1)
+    }
+}
+
+/// Captures of the anchored `re` at each of `starts`, with the offset they are relative to.
+/// Starts inside an earlier match are skipped, as `captures_iter` over the whole text would.
+fn captures_at<'s>(
+    re: &Regex,
+    source: &'s str,
+    starts: &[usize],
+) -> Vec<(usize, regex::Captures<'s>)> {
+    let mut out = Vec::new();
+    let mut end = 0;
+    for &start in starts {
+        if start < end {
+            continue;
+        }
+        if let Some(caps) = re.captures(&source[start..]) {
+            end = start + caps.get(0).map_or(0, |m| m.end());
+            out.push((start, caps));
+        }
+    }
+    out
+}
+
+/// Specifiers of `import` statements and `require` calls that only appear inside comments,
+/// with their 1-based line: `// import { debounce } from 'lodash'`.
+/// `comments` is the [`comment_text`] of the module.
+fn commented_out_imports(comments: &str) -> Vec<(String, usize)> {
+    let mut out: Vec<(usize, String)> = Vec::new();
+    for caps in IMPORT_FROM_RE.captures_iter(comments) {
+        // Prose that happens to say "import ... from '...'" has more than bindings in between.
+        let clause = caps.get(1).map_or("", |m| m.as_str());
+        if !clause
+            .chars()
+            .all(|c| c.is_alphanumeric() || "_$,{}* \t\n".contains(c))
+        {
+            continue;
+        }
+        out.push((caps.get(2).map_or(0, |m| m.start()), caps[2].to_string()));
+    }
+    for re in [&*IMPORT_SIDE_EFFECT_RE, &*REQUIRE_RE] {
+        for caps in re.captures_iter(comments) {
+            out.push((caps.get(1).map_or(0, |m| m.start()), caps[1].to_string()));
+        }
+    }
+    out.sort();
+    out.into_iter()
+        .map(|(offset, specifier)| (specifier, line_col(comments, offset).0))
+        .collect()
+}
+
+/// Shortest run of comment-only lines reported as commented-out code.
+const MIN_COMMENTED_CODE_LINES: usize = 3;
+
+/// Runs of at least [`MIN_COMMENTED_CODE_LINES`] comment-only lines that contain an `import`
+/// or `export` statement, as 1-based first and last line plus that statement. Doc comments
+/// (`/** ... */`) are skipped, since their examples often show imports.
+fn commented_code_blocks(raw: &str, stripped: &str, comments: &str) -> Vec<(usize, usize, String)> {
+    let comment_lines: Vec<&str> = comments.lines().collect();
+    let is_comment_only: Vec<bool> = raw
+        .lines()
+        .zip(stripped.lines())
+        .map(|(raw, stripped)| stripped.trim().is_empty() && !raw.trim().is_empty())
+        .collect();
+
+    let raw_lines: Vec<&str> = raw.lines().collect();
+    let mut out = Vec::new();
+    let mut line = 0;
+    while line < is_comment_only.len() {
+        if !is_comment_only[line] {
+            line += 1;
+            continue;
+        }
+        // A `//` run and an adjacent `/* */` comment are separate blocks.
+        let is_line_comment = |line: usize| raw_lines[line].trim_start().starts_with("//");
+        let start = line;
+        while line < is_comment_only.len()
+            && is_comment_only[line]
+            && is_line_comment(line) == is_line_comment(start)
+        {
+            line += 1;
+        }
+        if line - start < MIN_COMMENTED_CODE_LINES
+            || raw_lines[start].trim_start().starts_with("/**")
+        {
+            continue;
+        }
+        let statement = comment_lines[start..line.min(comment_lines.len())]
+            .iter()
+            .find(|text| COMMENTED_STATEMENT_RE.is_match(text));
+        if let Some(statement) = statement {
+            out.push((start + 1, line, statement.to_string()));
+        }
+    }
+    out
+}
+
+/// The text of every comment in `raw`, at its original line, with comment markers (`//`,
+/// `/*`, `*/`, leading `*`) removed. Code outside comments is blanked.
+pub(crate) fn comment_text(raw: &str, stripped: &str) -> String {
+    let blanked: String = raw
+        .chars()
+        .zip(stripped.chars())
+        .map(|(original, kept)| match (original, kept) {
+            ('\n', _) => '\n',
+            (original, kept) if original != kept => original,
+            _ => ' ',
+        })
+        .collect();
+    blanked
+        .lines()
+        .map(|line| {
+            let line = line.trim();
+            let line = line.strip_suffix("*/").unwrap_or(line);
+            line.trim_start_matches(['/', '*']).trim()
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Import bindings whose name appears in the source outside the `import ... from` statements,
+/// given as byte ranges.
+fn referenced_bindings(
+    source: &str,
+    statements: &[(usize, usize)],
+    imports: &[ImportRecord],
+) -> HashSet<String> {
+    let locals: HashSet<&str> = imports
+        .iter()
+        .filter(|import| !import.is_reexport)
+        .flat_map(|import| import.bindings.keys().map(String::as_str))
+        .collect();
+    if locals.is_empty() {
+        return HashSet::new();
+    }
+    ident_tokens(source)
+        .filter(|(_, token)| locals.contains(token))
+        .filter(|(offset, _)| {
+            !statements
+                .iter()
+                .any(|(start, end)| (*start..*end).contains(offset))
+        })
+        .map(|(_, token)| token.to_string())
+        .collect()
+}
+
+/// Identifier tokens (ECMAScript IdentifierNames, as [`IDENT_TOKEN_RE`] matches them) and
+/// their byte offsets. Scanned by hand rather than with a regex, since it runs over every file
+/// while parsing and again while counting export names; only non-ASCII characters go through
+/// the Unicode tables.
+pub(crate) fn ident_tokens(source: &str) -> impl Iterator<Item = (usize, &str)> {
+    let mut chars = source.char_indices().peekable();
+    std::iter::from_fn(move || {
+        while let Some((start, c)) = chars.next() {
+            if is_ident_start(c) {
+                let mut end = start + c.len_utf8();
+                while let Some(&(idx, next)) = chars.peek()
+                    && is_ident_continue(next)
+                {
+                    end = idx + next.len_utf8();
+                    chars.next();
+                }
+                return Some((start, &source[start..end]));
+            }
+        }
+        None
+    })
+}
+
+fn is_ident_start(c: char) -> bool {
+    if c.is_ascii() {
+        c.is_ascii_alphabetic() || c == '_' || c == '$'
+    } else {
+        unicode_ident::is_xid_start(c)
+    }
+}
+
+fn is_ident_continue(c: char) -> bool {
+    if c.is_ascii() {
+        c.is_ascii_alphanumeric() || c == '_' || c == '$'
+    } else {
+        unicode_ident::is_xid_continue(c) || c == '\u{200C}' || c == '\u{200D}'
+    }
+}
+
+/// 1-based line and column (in characters) of a byte offset. [`strip_comments`] blanks
+/// comments out instead of removing them, so positions in stripped source match the file.
+pub(crate) fn line_col(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..offset.min(source.len())];
+    let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+    (
+        before.matches('\n').count() + 1,
+        before[line_start..].chars().count() + 1,
+    )
+}
+
+/// Replaces `//` and `/* */` comments (outside string literals) with spaces, keeping line
+/// breaks, so character positions are unchanged.
 pub(crate) fn strip_comments(source: &str) -> String {
     let mut out = String::with_capacity(source.len());
     let chars: Vec<char> = source.chars().collect();
@@ -132,27 +550,23 @@ pub(crate) fn strip_comments(source: &str) -> String {
 
         if c == '/' && i + 1 < chars.len() {
             if chars[i + 1] == '/' {
-                i += 2;
                 while i < chars.len() && chars[i] != '\n' {
-                    i += 1;
-                }
-                if i < chars.len() {
-                    out.push('\n');
+                    out.push(' ');
                     i += 1;
                 }
                 continue;
             }
 
             if chars[i + 1] == '*' {
+                out.push_str("  ");
                 i += 2;
-                while i + 1 < chars.len() {
-                    if chars[i] == '*' && chars[i + 1] == '/' {
+                while i < chars.len() {
+                    if chars[i] == '*' && i + 1 < chars.len() && chars[i + 1] == '/' {
+                        out.push_str("  ");
                         i += 2;
                         break;
                     }
-                    if chars[i] == '\n' {
-                        out.push('\n');
-                    }
+                    out.push(if chars[i] == '\n' { '\n' } else { ' ' });
                     i += 1;
                 }
                 continue;
@@ -168,7 +582,13 @@ pub(crate) fn strip_comments(source: &str) -> String {
 
 fn parse_import_clause(clause: &str, record: &mut ImportRecord) {
     let cleaned = clause.trim();
-    let cleaned = cleaned.strip_prefix("type ").unwrap_or(cleaned).trim();
+    let cleaned = match cleaned.strip_prefix("type ") {
+        Some(rest) => {
+            record.type_only = true;
+            rest.trim()
+        }
+        None => cleaned,
+    };
 
     if cleaned.contains("* as") {
         record.uses_namespace = true;
@@ -176,29 +596,70 @@ fn parse_import_clause(clause: &str, record: &mut ImportRecord) {
 
     if cleaned.starts_with('{') {
         record.names.extend(parse_export_names(cleaned));
+        record.bindings.extend(parse_named_bindings(cleaned));
         return;
     }
 
     if let Some((first, rest)) = cleaned.split_once(',') {
         if !first.trim().is_empty() {
             record.uses_default = true;
+            record
+                .bindings
+                .insert(first.trim().to_string(), "default".to_string());
         }
         if rest.contains('*') {
             record.uses_namespace = true;
         }
         if rest.contains('{') {
             record.names.extend(parse_export_names(rest));
+            record.bindings.extend(parse_named_bindings(rest));
         }
         return;
     }
 
     if cleaned.contains('{') {
         record.names.extend(parse_export_names(cleaned));
+        record.bindings.extend(parse_named_bindings(cleaned));
     } else if !cleaned.is_empty() {
         record.uses_default = true;
+        if !cleaned.contains('*') {
+            record
+                .bindings
+                .insert(cleaned.to_string(), "default".to_string());
+        }
     }
 }
 
+/// `(local, imported)` pairs of a `{ a, b as c, type D }` list; for re-export lists the
+/// local name is the exported one.
+fn parse_named_bindings(list: &str) -> HashMap<String, String> {
+    let trimmed = list.trim().trim_start_matches('{').trim_end_matches('}');
+    trimmed
+        .split(',')
+        .filter_map(|raw| {
+            let part = raw.trim().trim_start_matches("type ").trim();
+            let (imported, local) = part.split_once(" as ").unwrap_or((part, part));
+            let (imported, local) = (imported.trim(), local.trim());
+            (!local.is_empty() && !imported.is_empty() && !imported.starts_with('*'))
+                .then(|| (local.to_string(), imported.to_string()))
+        })
+        .collect()
+}
+
+/// `(local, property)` pairs of a `{ a, b: c }` destructuring pattern.
+fn parse_destructured_bindings(names: &str) -> HashMap<String, String> {
+    names
+        .split(',')
+        .filter_map(|raw| {
+            let item = raw.trim();
+            let (property, local) = item.split_once(':').unwrap_or((item, item));
+            let (property, local) = (property.trim(), local.trim());
+            (!local.is_empty() && !property.is_empty())
+                .then(|| (local.to_string(), property.to_string()))
+        })
+        .collect()
+}
+
 fn parse_export_list_as_import(names: &str, record: &mut ImportRecord) {
     for raw in names.split(',') {
         let part = raw.trim();
@@ -227,6 +688,7 @@ fn parse_export_list_as_import(names: &str, record: &mut ImportRecord) {
             record.names.insert(import_name.to_string());
         }
     }
+    record.bindings.extend(parse_named_bindings(names));
 }
 
 fn parse_export_names(names: &str) -> HashSet<String> {
@@ -259,7 +721,7 @@ fn parse_export_names(names: &str) -> HashSet<String> {
     out
 }
 
-fn parse_destructured_names(names: &str) -> HashSet<String> {
+pub(crate) fn parse_destructured_names(names: &str) -> HashSet<String> {
     let mut out = HashSet::new();
 
     for raw in names.split(',') {
@@ -281,3 +743,45 @@ fn parse_destructured_names(names: &str) -> HashSet<String> {
 
     out
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn import_of<'a>(info: &'a ModuleInfo, specifier: &str) -> &'a ImportRecord {
+        info.imports
+            .iter()
+            .find(|record| record.specifier == specifier)
+            .unwrap_or_else(|| panic!("no import of {specifier}: {:?}", info.imports))
+    }
+
+    #[test]
+    fn multi_line_import_from() {
+        let info =
+            parse_source("const x = 1;\nimport {\n  a,\n  b as c,\n} from './mod';\nuse(c);\n");
+        let record = import_of(&info, "./mod");
+        assert_eq!(record.line, 2);
+        assert_eq!(record.bindings.get("a").map(String::as_str), Some("a"));
+        assert_eq!(record.bindings.get("c").map(String::as_str), Some("b"));
+    }
+
+    #[test]
+    fn export_list_with_alias() {
+        let info = parse_source("const a = 1;\n  export { a as b };\n");
+        assert!(info.exports.contains("b"));
+        assert!(!info.exports.contains("a"));
+        assert_eq!(info.export_positions.get("b"), Some(&(2, 17)));
+    }
+
+    #[test]
+    fn export_star_as_namespace() {
+        let info = parse_source("export * as ns from './mod';\nexport * from './all';\n");
+        assert!(info.exports.contains("ns"));
+        assert!(info.has_export_all);
+
+        let named = import_of(&info, "./mod");
+        assert!(named.is_reexport && named.uses_namespace);
+        assert_eq!(named.bindings.get("ns").map(String::as_str), Some("*"));
+        assert_eq!(import_of(&info, "./all").line, 2);
+    }
+}
@@ -0,0 +1,1172 @@
+use super::*;
+use crate::config::ProjectConfig;
+use serde::de::DeserializeOwned;
+use std::hash::{Hash, Hasher};
+
+/// Where stage checkpoints go, relative to the root.
+const CHECKPOINT_DIR: &str = ".haadi/checkpoints";
+
+/// The stages of an analysis, in the order they run. Each one produces an artifact that
+/// `--stop-after` saves as a checkpoint and `--resume-from` reads back.
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Stage {
+    /// Walk the root for source files, assets, and declaration files
+    Scan,
+    /// Parse every source and declaration file
+    Parse,
+    /// Discover the entry files
+    Resolve,
+    /// Follow imports from the entries: reachable and unused files, unresolved and broken imports
+    Reach,
+    /// Unused, missing, and misplaced dependencies
+    Deps,
+    /// Used, unused, and duplicated assets
+    Assets,
+    /// Unused exports
+    Exports,
+}
+
+impl Stage {
+    fn label(self) -> &'static str {
+        match self {
+            Stage::Scan => "scan",
+            Stage::Parse => "parse",
+            Stage::Resolve => "resolve",
+            Stage::Reach => "reach",
+            Stage::Deps => "deps",
+            Stage::Assets => "assets",
+            Stage::Exports => "exports",
+        }
+    }
+}
+
+/// How far [`run_pipeline`] got.
+pub(crate) enum PipelineOutcome {
+    /// The stage of `--stop-after` finished; its artifact, as JSON.
+    Stopped(serde_json::Value),
+    Finished(Box<(Report, Option<ModuleGraph>)>),
+}
+
+/// A checkpoint file: the artifact of one stage, the haadi that wrote it, and the
+/// [`checkpoint_fingerprint`] of the run.
+#[derive(Serialize, Deserialize)]
+struct Checkpoint<T> {
+    haadi_version: String,
+    /// Empty in checkpoints written before fingerprints, which never match.
+    #[serde(default)]
+    fingerprint: String,
+    stage: Stage,
+    artifact: T,
+}
+
+/// Which stages a run reads from `.haadi/checkpoints` and whether it writes the ones it runs.
+struct Checkpoints {
+    dir: PathBuf,
+    resume_from: Option<Stage>,
+    save: bool,
+    fingerprint: String,
+}
+
+/// Hash of what the stage artifacts depend on: the canonical root, the analysis options, and
+/// the project config. Options that only change what happens after the stages, or how they
+/// run, are left out, so `--threads` or `--timing` do not invalidate checkpoints.
+fn checkpoint_fingerprint(
+    root: &Path,
+    options: &AnalysisOptions,
+    config: &ProjectConfig,
+) -> String {
+    let options = AnalysisOptions {
+        root: root.to_path_buf(),
+        threads: None,
+        fix: None,
+        stats: false,
+        timing: false,
+        resume_from: None,
+        save_graph: None,
+        progress: false,
+        ..options.clone()
+    };
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    format!("{options:?}\n{config:?}").hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+impl Checkpoints {
+    /// The artifact of `stage`: read back when the run resumes from a later stage, else made
+    /// by `run` and, when stopping early, saved.
+    fn stage<T: Serialize + DeserializeOwned>(
+        &self,
+        stage: Stage,
+        run: impl FnOnce() -> Result<T>,
+    ) -> Result<T> {
+        if self.resume_from.is_some_and(|from| stage < from) {
+            return self.load(stage);
+        }
+        let artifact = run()?;
+        if self.save {
+            self.write(stage, &artifact)?;
+        }
+        Ok(artifact)
+    }
+
+    fn path(&self, stage: Stage) -> PathBuf {
+        self.dir.join(format!("{}.json", stage.label()))
+    }
+
+    fn load<T: DeserializeOwned>(&self, stage: Stage) -> Result<T> {
+        let path = self.path(stage);
+        let raw = fs::read(&path).with_context(|| {
+            format!(
+                "Failed to read checkpoint: {} (run with --stop-after {} first)",
+                shown_path(&path),
+                stage.label()
+            )
+        })?;
+        let checkpoint: Checkpoint<T> = serde_json::from_slice(&raw)
+            .with_context(|| format!("Failed to parse checkpoint: {}", shown_path(&path)))?;
+        if checkpoint.haadi_version != env!("CARGO_PKG_VERSION") || checkpoint.stage != stage {
+            anyhow::bail!(
+                "Checkpoint {} was written by haadi {}; run with --stop-after {} again",
+                shown_path(&path),
+                checkpoint.haadi_version,
+                stage.label()
+            );
+        }
+        if checkpoint.fingerprint != self.fingerprint {
+            anyhow::bail!(
+                "Checkpoint {} was saved for another root, other analysis options, or another project config; run with --stop-after {} and the same options again",
+                shown_path(&path),
+                stage.label()
+            );
+        }
+        debug!(stage = stage.label(), "resumed from checkpoint");
+        Ok(checkpoint.artifact)
+    }
+
+    fn write<T: Serialize>(&self, stage: Stage, artifact: &T) -> Result<()> {
+        let path = self.path(stage);
+        fs::create_dir_all(&self.dir).with_context(|| {
+            format!(
+                "Failed to create checkpoint directory: {}",
+                shown_path(&self.dir)
+            )
+        })?;
+        let checkpoint = Checkpoint {
+            haadi_version: env!("CARGO_PKG_VERSION").to_string(),
+            fingerprint: self.fingerprint.clone(),
+            stage,
+            artifact,
+        };
+        fs::write(&path, serde_json::to_vec(&checkpoint)?)
+            .with_context(|| format!("Failed to write checkpoint: {}", shown_path(&path)))?;
+        debug!(stage = stage.label(), "saved checkpoint");
+        Ok(())
+    }
+}
+
+/// What the scan found under the root.
+#[derive(Debug, Serialize, Deserialize)]
+struct ScanArtifact {
+    /// Every file and directory, so a resumed run resolves imports without walking again.
+    path_index: PathIndex,
+    all_files: HashSet<PathBuf>,
+    /// `all_files` inside the tsconfig scope under `--tsconfig-scope`: the files findings cover.
+    files: HashSet<PathBuf>,
+    all_assets: HashSet<PathBuf>,
+    /// `all_assets` under `--asset-roots`.
+    assets: HashSet<PathBuf>,
+    declaration_files: HashSet<PathBuf>,
+    case_insensitive: bool,
+    warnings: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ParseArtifact {
+    modules: HashMap<PathBuf, ModuleInfo>,
+    parsed_bytes: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ResolveArtifact {
+    /// Entry files and who runs them.
+    scoped_entries: BTreeMap<PathBuf, EntryScope>,
+    export_entries: Vec<ExportEntry>,
+    warnings: Vec<String>,
+}
+
+impl ResolveArtifact {
+    fn entries(&self) -> Vec<PathBuf> {
+        self.scoped_entries.keys().cloned().collect()
+    }
+
+    fn has_prod_entries(&self) -> bool {
+        self.scoped_entries
+            .values()
+            .any(|scope| *scope == EntryScope::Prod)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ReachArtifact {
+    /// Files the entries and declaration files reach.
+    reachable: HashSet<PathBuf>,
+    /// What the prod entries reach, when some entries are not prod.
+    prod_reachable: Option<HashSet<PathBuf>>,
+    /// What all but the stories reach, when there are both prod entries and stories.
+    non_story_reachable: Option<HashSet<PathBuf>>,
+    unresolved: Vec<UnresolvedImport>,
+    maybe_used_from_unresolved: HashSet<PathBuf>,
+    high_confidence_graph: bool,
+    broken_imports: Vec<BrokenImport>,
+    broken_asset_references: Vec<BrokenAssetReference>,
+    commented_out_imports: Vec<CommentedOutImport>,
+    commented_code: Vec<CommentedCode>,
+    unused_files: Vec<String>,
+    tooling_only_files: Vec<String>,
+    storybook_only_files: Vec<String>,
+    warnings: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct DepsArtifact {
+    declared_deps: HashMap<String, DepKind>,
+    dependency_usage: Vec<DependencyUsage>,
+    unused_dependencies: Vec<String>,
+    kept_dependencies: Vec<KeptDependency>,
+    orphaned_types_packages: Vec<String>,
+    missing_dependencies: Vec<MissingDependency>,
+    misplaced_dependencies: Vec<MisplacedDependency>,
+    dev_dependencies_in_production: Vec<DevDependencyInProduction>,
+    warnings: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct AssetsArtifact {
+    used_assets: Vec<String>,
+    unused_assets: Vec<String>,
+    unused_licensed_assets: Vec<LicensedAsset>,
+    unlicensed_used_assets: Vec<String>,
+    duplicate_assets: Vec<DuplicateAsset>,
+    warnings: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ExportsArtifact {
+    unused_exports: Vec<UnusedExport>,
+    storybook_only_exports: Vec<UnusedExport>,
+    warnings: Vec<String>,
+}
+
+/// Runs the stages of an analysis of `options.root` as if the files in `deleted` did not
+/// exist, then builds the report from their artifacts. With `stop_after`, returns that stage's
+/// artifact instead and saves a checkpoint of every stage that ran; `options.resume_from`
+/// reads the stages before it from those checkpoints.
+pub(crate) fn run_pipeline(
+    options: &AnalysisOptions,
+    parse_cache: &mut ParseCache,
+    with_graph: bool,
+    deleted: &HashSet<PathBuf>,
+    stop_after: Option<Stage>,
+) -> Result<PipelineOutcome> {
+    let started = std::time::Instant::now();
+    let mut timer = PhaseTimer::start();
+    let _interruptible = Interruptible::enter();
+    parse_cache.take_counts();
+    let root = fs::canonicalize(&options.root)
+        .with_context(|| format!("Failed to access root: {}", shown_path(&options.root)))?;
+
+    info!(root = %shown_path(&root), "starting analysis");
+    let progress = Progress::new(options.progress);
+    let config = load_project_config(&root)?;
+    let checkpoints = Checkpoints {
+        dir: root.join(CHECKPOINT_DIR),
+        resume_from: options.resume_from,
+        save: stop_after.is_some(),
+        fingerprint: checkpoint_fingerprint(&root, options, &config),
+    };
+    timer.timings.other_ms += timer.lap();
+
+    macro_rules! stop_after {
+        ($stage:expr, $artifact:expr) => {
+            if stop_after == Some($stage) {
+                progress.finish();
+                return Ok(PipelineOutcome::Stopped(serde_json::to_value(&$artifact)?));
+            }
+            check_interrupted()?;
+        };
+    }
+
+    let mut scan = checkpoints.stage(Stage::Scan, || {
+        scan_stage(&root, options, deleted, &progress)
+    })?;
+    timer.timings.scan_ms += timer.lap();
+    stop_after!(Stage::Scan, scan);
+
+    let parse = checkpoints.stage(Stage::Parse, || {
+        parse_stage(options, parse_cache, &scan, &progress)
+    })?;
+    timer.timings.parse_ms += timer.lap();
+    timer.timings.parsed_bytes = parse.parsed_bytes;
+    stop_after!(Stage::Parse, parse);
+
+    // The resolver holds config files and the path index rather than results, so it is built
+    // again on every run, from the scan artifact when resuming.
+    let mut resolver = build_resolver(
+        &root,
+        &scan.all_files,
+        &merge_list(&options.resolve_extensions, &config.resolve_extensions),
+        &merge_list(&options.platforms, &config.platforms),
+        scan.case_insensitive,
+        std::mem::take(&mut scan.path_index),
+    )?;
+    resolver.ambient_modules = collect_ambient_modules(&scan.declaration_files);
+    let resolve = checkpoints.stage(Stage::Resolve, || {
+        resolve_stage(&root, options, &config, &scan, &resolver, &progress)
+    })?;
+    timer.timings.resolve_ms += timer.lap();
+    stop_after!(Stage::Resolve, resolve);
+
+    let reach = checkpoints.stage(Stage::Reach, || {
+        reach_stage(&root, options, &scan, &parse, &resolve, &resolver)
+    })?;
+    timer.timings.reachability_ms += timer.lap();
+    stop_after!(Stage::Reach, reach);
+
+    let deps = checkpoints.stage(Stage::Deps, || {
+        deps_stage(&root, options, &scan, &parse, &resolve, &reach, &resolver)
+    })?;
+    timer.timings.other_ms += timer.lap();
+    stop_after!(Stage::Deps, deps);
+
+    let assets = checkpoints.stage(Stage::Assets, || {
+        assets_stage(&root, options, &scan, &reach, &deps, &resolver, &progress)
+    })?;
+    timer.timings.assets_ms += timer.lap();
+    stop_after!(Stage::Assets, assets);
+
+    let exports = checkpoints.stage(Stage::Exports, || {
+        exports_stage(
+            &root, options, &scan, &parse, &resolve, &reach, &resolver, &progress,
+        )
+    })?;
+    timer.timings.exports_ms += timer.lap();
+    stop_after!(Stage::Exports, exports);
+
+    let ScanArtifact {
+        all_files,
+        files,
+        assets: asset_files,
+        declaration_files,
+        warnings: scan_warnings,
+        ..
+    } = scan;
+    let modules = parse.modules;
+    let entries = resolve.entries();
+    let ReachArtifact {
+        mut reachable,
+        unresolved,
+        high_confidence_graph,
+        broken_imports,
+        broken_asset_references,
+        commented_out_imports,
+        commented_code,
+        unused_files,
+        tooling_only_files,
+        storybook_only_files,
+        warnings: reach_warnings,
+        ..
+    } = reach;
+    let DepsArtifact {
+        dependency_usage,
+        unused_dependencies,
+        mut kept_dependencies,
+        orphaned_types_packages,
+        missing_dependencies,
+        misplaced_dependencies,
+        dev_dependencies_in_production,
+        warnings: deps_warnings,
+        ..
+    } = deps;
+    let AssetsArtifact {
+        used_assets,
+        unused_assets,
+        unused_licensed_assets,
+        unlicensed_used_assets,
+        duplicate_assets,
+        warnings: assets_warnings,
+    } = assets;
+    let ExportsArtifact {
+        unused_exports,
+        storybook_only_exports,
+        warnings: exports_warnings,
+    } = exports;
+    let mut warnings =
+        vec!["Analysis is conservative by default to minimize false positives.".to_string()];
+    warnings.extend(resolver.config_warnings.iter().cloned());
+    for stage_warnings in [
+        scan_warnings,
+        resolve.warnings,
+        reach_warnings,
+        deps_warnings,
+        assets_warnings,
+        exports_warnings,
+    ] {
+        warnings.extend(stage_warnings);
+    }
+
+    let mut packages = if options.workspaces {
+        build_package_reports(
+            &root,
+            &resolver,
+            &modules,
+            &files,
+            &reachable,
+            &entries,
+            &unused_files,
+            &unused_assets,
+            &unused_exports,
+            options.include_non_prod_deps,
+        )?
+    } else {
+        Vec::new()
+    };
+
+    for package in &mut packages {
+        kept_dependencies.extend(apply_kept_dependencies(
+            &root,
+            &root.join(&package.dir),
+            false,
+            &mut package.unused_dependencies,
+        )?);
+        package.summary.unused_dependencies_count = package.unused_dependencies.len();
+    }
+
+    let unused_scripts = if options.unused_scripts {
+        find_unused_scripts(&root, &resolver)?
+    } else {
+        Vec::new()
+    };
+
+    let (unused_env_vars, undeclared_env_vars) = if options.check_env {
+        analyze_env_vars(&root, &files)?
+    } else {
+        (Vec::new(), Vec::new())
+    };
+
+    // Declaration files only contributed usage; they are not part of the source totals.
+    for file in &declaration_files {
+        reachable.remove(file);
+    }
+
+    let entry_reachability = if options.per_entry {
+        compute_entry_reachability(&root, &files, &resolve.scoped_entries, &modules, &resolver)?
+    } else {
+        Vec::new()
+    };
+
+    let export_fixes = if options.fix == Some(FixKind::Exports) {
+        plan_export_fixes(&root, &all_files, &unused_exports)
+    } else {
+        Vec::new()
+    };
+
+    let import_costs = if options.import_cost {
+        compute_import_costs(&root, &reachable, &modules, &resolver)?
+    } else {
+        Vec::new()
+    };
+
+    let total_asset_files = asset_files.len();
+    let unused_assets_count = unused_assets.len();
+    let used_assets_count = total_asset_files.saturating_sub(unused_assets_count);
+
+    let summary = ReportSummary {
+        total_source_files: files.len(),
+        total_asset_files,
+        total_reachable_files: reachable.len(),
+        total_entries: entries.len(),
+        unresolved_local_imports: unresolved.len(),
+        high_confidence_graph,
+        omitted_risky_findings: !(high_confidence_graph || options.include_low_confidence),
+        unused_files_count: unused_files.len(),
+        tooling_only_files_count: tooling_only_files.len(),
+        storybook_only_files_count: storybook_only_files.len(),
+        used_assets_count,
+        unused_assets_count,
+        asset_usage_coverage_pct: if total_asset_files == 0 {
+            0.0
+        } else {
+            (used_assets_count as f64 * 100.0) / total_asset_files as f64
+        },
+        unused_dependencies_count: unused_dependencies.len(),
+        missing_dependencies_count: missing_dependencies.len(),
+        misplaced_dependencies_count: misplaced_dependencies.len(),
+        dev_dependencies_in_production_count: dev_dependencies_in_production.len(),
+        orphaned_types_packages_count: orphaned_types_packages.len(),
+        unused_exports_count: unused_exports.len(),
+        storybook_only_exports_count: storybook_only_exports.len(),
+        duplicate_assets_count: duplicate_assets.len(),
+        unused_licensed_assets_count: unused_licensed_assets.len(),
+        unlicensed_used_assets_count: unlicensed_used_assets.len(),
+        packages: packages.iter().map(|p| p.summary.clone()).collect(),
+        unused_env_vars_count: unused_env_vars.len(),
+        undeclared_env_vars_count: undeclared_env_vars.len(),
+        broken_imports_count: broken_imports.len(),
+        broken_asset_references_count: broken_asset_references.len(),
+        commented_out_imports_count: commented_out_imports.len(),
+        commented_code_count: commented_code.len(),
+        unused_scripts_count: unused_scripts.len(),
+        sample_pct: None,
+        timing: None,
+    };
+
+    let mut report = Report {
+        root: root.display().to_string(),
+        summary,
+        entries: entries
+            .iter()
+            .map(|entry| relative_display(&root, entry))
+            .collect(),
+        export_entries: resolve.export_entries,
+        warnings,
+        unused_files,
+        tooling_only_files,
+        storybook_only_files,
+        used_assets,
+        unused_assets,
+        unused_dependencies,
+        missing_dependencies,
+        misplaced_dependencies,
+        dev_dependencies_in_production,
+        orphaned_types_packages,
+        kept_dependencies,
+        dependency_usage,
+        unused_exports,
+        storybook_only_exports,
+        route_groups: Vec::new(),
+        duplicate_assets,
+        unused_licensed_assets,
+        unlicensed_used_assets,
+        packages,
+        unused_env_vars,
+        undeclared_env_vars,
+        broken_imports,
+        broken_asset_references,
+        commented_out_imports,
+        commented_code,
+        unused_scripts,
+        import_costs,
+        entry_reachability,
+        export_fixes,
+        findings: Vec::new(),
+        comparison: None,
+        cascade_waves: Vec::new(),
+    };
+    if let Some(percent) = options.sample {
+        sample_report(&mut report, percent);
+    }
+    let package_dirs: Vec<PathBuf> = resolver
+        .workspace_packages
+        .iter()
+        .map(|package| package.dir.clone())
+        .collect();
+    report.route_groups = group_by_route(
+        &root,
+        &package_dirs,
+        &files,
+        &asset_files,
+        &report.unused_files,
+        &report.unused_assets,
+    );
+    report.findings = collect_findings(&report, |subject| fs::read(root.join(subject)).ok());
+    if options.timing {
+        report.summary.timing = Some(timer.finish());
+    }
+    progress.finish();
+    let (cache_hits, parsed_files) = parse_cache.take_counts();
+    info!(
+        elapsed_ms = started.elapsed().as_millis() as u64,
+        parsed_files,
+        cache_hits,
+        reachable_files = report.summary.total_reachable_files,
+        findings = report.findings.len(),
+        "analysis finished"
+    );
+    if options.cascade {
+        report.cascade_waves = cascade_waves(options, parse_cache, &root, &report)?;
+    }
+    if options.stats {
+        record_run_stats(
+            &root,
+            &report,
+            started.elapsed(),
+            (cache_hits, parsed_files),
+        )?;
+    }
+
+    let save_path = options.save_graph.as_ref();
+    let graph = if with_graph || save_path.is_some() {
+        Some(build_module_graph(
+            &root, &entries, &modules, &reachable, &resolver, &report,
+        )?)
+    } else {
+        None
+    };
+    if let (Some(path), Some(graph)) = (save_path, &graph) {
+        save_graph(path, graph)?;
+        eprintln!("Saved module graph to {}", shown_path(path));
+    }
+
+    Ok(PipelineOutcome::Finished(Box::new((report, graph))))
+}
+
+fn scan_stage(
+    root: &Path,
+    options: &AnalysisOptions,
+    deleted: &HashSet<PathBuf>,
+    progress: &Progress,
+) -> Result<ScanArtifact> {
+    check_root_before_scan(root, options.force)?;
+    progress.phase("Discovering files", None);
+    let mut path_index = PathIndex::build(root, options.follow_symlinks, options.max_files)?;
+    let mut all_files = collect_source_files(&path_index)?;
+    check_root_after_scan(root, all_files.len(), options.force)?;
+    let mut all_assets = collect_asset_files(&path_index)?;
+    if !deleted.is_empty() {
+        all_files.retain(|path| !deleted.contains(path));
+        all_assets.retain(|path| !deleted.contains(path));
+    }
+    let assets = filter_assets_by_roots(root, &all_assets, &options.asset_roots);
+    let declaration_files = collect_declaration_files(&path_index)?;
+    let case_insensitive = options.case_insensitive_fs || detect_case_insensitive_fs(&all_files);
+    path_index.case_insensitive = case_insensitive;
+    info!(
+        source_files = all_files.len(),
+        assets = all_assets.len(),
+        declaration_files = declaration_files.len(),
+        case_insensitive,
+        "scanned project files"
+    );
+
+    let mut warnings = Vec::new();
+    if !options.asset_roots.is_empty() && assets.is_empty() {
+        warnings.push(
+            "No assets matched --asset-roots filter; asset findings may be empty.".to_string(),
+        );
+    }
+    // Out-of-scope files still resolve and stay traversable, as tsc pulls in imported files
+    // even when `exclude` matches them; they are only left out of findings and totals.
+    let files = if options.tsconfig_scope {
+        match filter_files_by_tsconfig(root, &all_files)? {
+            Some(scoped) => scoped,
+            None => {
+                warnings.push(
+                    "--tsconfig-scope was set but no tsconfig.json or jsconfig.json was found."
+                        .to_string(),
+                );
+                all_files.clone()
+            }
+        }
+    } else {
+        all_files.clone()
+    };
+    Ok(ScanArtifact {
+        path_index,
+        all_files,
+        files,
+        all_assets,
+        assets,
+        declaration_files,
+        case_insensitive,
+        warnings,
+    })
+}
+
+fn parse_stage(
+    options: &AnalysisOptions,
+    parse_cache: &mut ParseCache,
+    scan: &ScanArtifact,
+    progress: &Progress,
+) -> Result<ParseArtifact> {
+    progress.phase(
+        "Parsing",
+        Some(scan.all_files.len() + scan.declaration_files.len()),
+    );
+    let modules = parse_cache.parse_all(
+        scan.all_files.iter().chain(&scan.declaration_files),
+        options.threads,
+        || progress.inc(),
+    )?;
+    Ok(ParseArtifact {
+        modules,
+        parsed_bytes: parse_cache.parsed_bytes(),
+    })
+}
+
+fn resolve_stage(
+    root: &Path,
+    options: &AnalysisOptions,
+    config: &ProjectConfig,
+    scan: &ScanArtifact,
+    resolver: &Resolver,
+    progress: &Progress,
+) -> Result<ResolveArtifact> {
+    progress.phase("Resolving imports", None);
+    let files = &scan.files;
+    let mut warnings = Vec::new();
+    let dist_mappings =
+        parse_dist_mappings(&merge_list(&options.dist_mappings, &config.dist_mappings))?;
+    let mut scoped_entries =
+        discover_entries(root, files, resolver, &options.entries, &dist_mappings)?;
+    if options.workspaces {
+        if resolver.workspace_packages.is_empty() {
+            warnings.push(
+                "--workspaces was set but no workspace packages were found (pnpm-workspace.yaml or package.json workspaces)."
+                    .to_string(),
+            );
+        }
+        scoped_entries =
+            discover_workspace_entries(resolver, files, scoped_entries, &dist_mappings)?;
+    }
+    // Like the `*.stories.*` convention, story globs only apply when no --entry is given.
+    let story_globs = merge_list(&options.stories, &config.stories);
+    if options.entries.is_empty() && !story_globs.is_empty() {
+        let patterns = story_globs
+            .iter()
+            .map(|glob| {
+                Regex::new(&glob_path_pattern_to_regex(glob.trim_start_matches("./")))
+                    .with_context(|| format!("Failed to parse story glob: {glob}"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        for file in files {
+            let rel = relative_display(root, file);
+            if patterns.iter().any(|re| re.is_match(&rel)) {
+                add_entry(&mut scoped_entries, file.clone(), EntryScope::Story);
+            }
+        }
+    }
+    info!(entries = scoped_entries.len(), "discovered entries");
+    for (entry, scope) in &scoped_entries {
+        debug!(entry = %relative_display(root, entry), ?scope, "entry");
+    }
+    let export_entries: Vec<ExportEntry> = package_export_entries(root, resolver)?
+        .into_iter()
+        .filter(|(path, _)| scoped_entries.contains_key(path))
+        .map(|(_, entry)| entry)
+        .collect();
+    if scoped_entries.is_empty() {
+        warnings.push(
+            "No entry files discovered. Pass --entry to improve unused file accuracy.".to_string(),
+        );
+    }
+    Ok(ResolveArtifact {
+        scoped_entries,
+        export_entries,
+        warnings,
+    })
+}
+
+fn reach_stage(
+    root: &Path,
+    options: &AnalysisOptions,
+    scan: &ScanArtifact,
+    parse: &ParseArtifact,
+    resolve: &ResolveArtifact,
+    resolver: &Resolver,
+) -> Result<ReachArtifact> {
+    let files = &scan.files;
+    let modules = &parse.modules;
+    let scoped_entries = &resolve.scoped_entries;
+    let entries = resolve.entries();
+    let mut warnings = Vec::new();
+
+    // Hand-written declaration files are never imported, but the files and packages they
+    // import are in use.
+    let mut graph_roots = entries.clone();
+    graph_roots.extend(scan.declaration_files.iter().cloned());
+    let reachable = reachable_files(&graph_roots, modules, resolver)?;
+    // Scope propagates along imports: whatever prod entries cannot reach is used only through
+    // dev entries. Skipped when every entry has the same scope.
+    let prod_entries: Vec<&PathBuf> = scoped_entries
+        .iter()
+        .filter(|(_, scope)| **scope == EntryScope::Prod)
+        .map(|(path, _)| path)
+        .collect();
+    let has_prod_entries = !prod_entries.is_empty();
+    let prod_reachable = if has_prod_entries && prod_entries.len() < entries.len() {
+        let mut prod_roots: Vec<PathBuf> = prod_entries.into_iter().cloned().collect();
+        prod_roots.extend(scan.declaration_files.iter().cloned());
+        Some(reachable_files(&prod_roots, modules, resolver)?)
+    } else {
+        None
+    };
+    // Stories are dev entries of their own: what only they reach is "used only by Storybook"
+    // rather than tooling.
+    let has_story_entries = scoped_entries
+        .values()
+        .any(|scope| *scope == EntryScope::Story);
+    let non_story_reachable = if has_prod_entries && has_story_entries {
+        let mut roots: Vec<PathBuf> = scoped_entries
+            .iter()
+            .filter(|(_, scope)| **scope != EntryScope::Story)
+            .map(|(path, _)| path.clone())
+            .collect();
+        roots.extend(scan.declaration_files.iter().cloned());
+        Some(reachable_files(&roots, modules, resolver)?)
+    } else {
+        None
+    };
+
+    let LocalImportIssues {
+        unresolved,
+        broken,
+        missing_assets,
+    } = collect_unresolved_local_imports(&reachable, modules, resolver)?;
+    for item in &unresolved {
+        debug!(
+            file = %relative_display(root, &item.from_file),
+            specifier = %item.specifier,
+            "unresolved local import"
+        );
+    }
+    let broken_imports = describe_broken_imports(root, files, resolver, &broken);
+    let mut broken_asset_references: Vec<BrokenAssetReference> = missing_assets
+        .iter()
+        .map(|item| BrokenAssetReference {
+            file: relative_display(root, &item.from_file),
+            specifier: item.specifier.clone(),
+        })
+        .collect();
+    broken_asset_references.sort_by(|a, b| (&a.file, &a.specifier).cmp(&(&b.file, &b.specifier)));
+    let mut commented_out_imports: Vec<CommentedOutImport> = files
+        .iter()
+        .filter_map(|file| Some((file, modules.get(file)?)))
+        .flat_map(|(file, module)| {
+            module
+                .commented_imports
+                .iter()
+                .map(|(specifier, line)| CommentedOutImport {
+                    file: relative_display(root, file),
+                    line: *line,
+                    specifier: specifier.clone(),
+                })
+        })
+        .collect();
+    commented_out_imports.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+    let mut commented_code: Vec<CommentedCode> = if options.commented_code {
+        files
+            .iter()
+            .filter_map(|file| Some((file, modules.get(file)?)))
+            .flat_map(|(file, module)| {
+                module
+                    .commented_code
+                    .iter()
+                    .map(|(start_line, end_line, statement)| CommentedCode {
+                        file: relative_display(root, file),
+                        start_line: *start_line,
+                        end_line: *end_line,
+                        statement: statement.clone(),
+                    })
+            })
+            .collect()
+    } else {
+        Vec::new()
+    };
+    commented_code.sort_by(|a, b| (&a.file, a.start_line).cmp(&(&b.file, b.start_line)));
+    let maybe_used_from_unresolved =
+        infer_potentially_used_files_from_unresolved(files, &unresolved, root);
+    let high_confidence_graph = unresolved.is_empty();
+    if !broken_imports.is_empty() {
+        warnings.push(format!(
+            "{} imports point at files that do not exist (see broken imports); files they were meant to reach may be reported unused.",
+            broken_imports.len()
+        ));
+    }
+    if !unresolved.is_empty() {
+        warnings.push(format!(
+            "Skipped high-risk findings because {} local/alias imports could not be resolved.",
+            unresolved.len()
+        ));
+        if !maybe_used_from_unresolved.is_empty() {
+            warnings.push(format!(
+                "Suppressed unused-export findings for {} files potentially referenced by unresolved imports.",
+                maybe_used_from_unresolved.len()
+            ));
+        }
+    }
+
+    let mut unused_files = Vec::new();
+    let mut tooling_only_files = Vec::new();
+    let mut storybook_only_files = Vec::new();
+    if high_confidence_graph || options.include_low_confidence {
+        unused_files = files
+            .difference(&reachable)
+            .filter(|path| {
+                !is_test_like_file(path)
+                    && !is_declaration_file(path)
+                    && !is_common_config_file(path)
+            })
+            .map(|path| relative_display(root, path))
+            .collect();
+        unused_files.sort();
+        if let Some(prod_reachable) = &prod_reachable {
+            tooling_only_files = reachable
+                .difference(prod_reachable)
+                .filter(|path| {
+                    files.contains(*path)
+                        && !scoped_entries.contains_key(*path)
+                        && !is_test_like_file(path)
+                        && !is_story_file(path)
+                        && !is_declaration_file(path)
+                        && !is_common_config_file(path)
+                        && non_story_reachable
+                            .as_ref()
+                            .is_none_or(|non_story| non_story.contains(*path))
+                })
+                .map(|path| relative_display(root, path))
+                .collect();
+            tooling_only_files.sort();
+        }
+        if let Some(non_story_reachable) = &non_story_reachable {
+            storybook_only_files = reachable
+                .difference(non_story_reachable)
+                .filter(|path| {
+                    files.contains(*path)
+                        && !scoped_entries.contains_key(*path)
+                        && !is_test_like_file(path)
+                        && !is_declaration_file(path)
+                        && !is_common_config_file(path)
+                })
+                .map(|path| relative_display(root, path))
+                .collect();
+            storybook_only_files.sort();
+        }
+    }
+    Ok(ReachArtifact {
+        reachable,
+        prod_reachable,
+        non_story_reachable,
+        unresolved,
+        maybe_used_from_unresolved,
+        high_confidence_graph,
+        broken_imports,
+        broken_asset_references,
+        commented_out_imports,
+        commented_code,
+        unused_files,
+        tooling_only_files,
+        storybook_only_files,
+        warnings,
+    })
+}
+
+fn deps_stage(
+    root: &Path,
+    options: &AnalysisOptions,
+    scan: &ScanArtifact,
+    parse: &ParseArtifact,
+    resolve: &ResolveArtifact,
+    reach: &ReachArtifact,
+    resolver: &Resolver,
+) -> Result<DepsArtifact> {
+    let modules = &parse.modules;
+    let reachable = &reach.reachable;
+    let mut warnings = Vec::new();
+    let mut used_packages = collect_used_packages(reachable, modules, resolver)?;
+    let declared_deps = collect_declared_dependencies(root)?;
+    let script_packages = script_binary_packages(root, root, &declared_deps)?;
+    let config_packages = config_referenced_packages(root, modules, resolver, &declared_deps)?;
+    used_packages.extend(script_packages.iter().cloned());
+    used_packages.extend(config_packages.iter().cloned());
+    let mut unused_dependencies = find_unused_dependencies(
+        &declared_deps,
+        &used_packages,
+        options.include_non_prod_deps,
+    );
+    let kept_dependencies = apply_kept_dependencies(root, root, true, &mut unused_dependencies)?;
+    let dependency_usage = dependency_usage(
+        root,
+        &declared_deps,
+        &scan.files,
+        reachable,
+        &used_packages,
+        &unused_dependencies,
+        &kept_dependencies,
+        modules,
+        resolver,
+        &script_packages,
+        &config_packages,
+        &reach.commented_out_imports,
+        reach.unresolved.len(),
+    )?;
+    let orphaned_types_packages = if options.orphaned_types {
+        find_orphaned_types_packages(&declared_deps, &used_packages, &resolver.tsconfig_types)
+    } else {
+        Vec::new()
+    };
+    let missing_dependencies = find_missing_dependencies(root, reachable, modules, resolver)?;
+    // Without prod entries every file looks dev-only. An incomplete graph may hide the prod
+    // import that keeps a dependency in `dependencies`.
+    let (misplaced_dependencies, dev_dependencies_in_production) = if resolve.has_prod_entries() {
+        find_misplaced_dependencies(
+            root,
+            &scan.files,
+            reachable,
+            reach.prod_reachable.as_ref().unwrap_or(reachable),
+            modules,
+            resolver,
+            reach.high_confidence_graph || options.include_low_confidence,
+        )?
+    } else {
+        (Vec::new(), Vec::new())
+    };
+    if !dev_dependencies_in_production.is_empty() {
+        warnings.push(format!(
+            "{} packages declared only in devDependencies are imported by production code and will be missing from a production install.",
+            dev_dependencies_in_production.len()
+        ));
+    }
+    Ok(DepsArtifact {
+        declared_deps,
+        dependency_usage,
+        unused_dependencies,
+        kept_dependencies,
+        orphaned_types_packages,
+        missing_dependencies,
+        misplaced_dependencies,
+        dev_dependencies_in_production,
+        warnings,
+    })
+}
+
+fn assets_stage(
+    root: &Path,
+    options: &AnalysisOptions,
+    scan: &ScanArtifact,
+    reach: &ReachArtifact,
+    deps: &DepsArtifact,
+    resolver: &Resolver,
+    progress: &Progress,
+) -> Result<AssetsArtifact> {
+    let assets = &scan.assets;
+    let mut warnings = Vec::new();
+    let mut used_assets = Vec::new();
+    let mut unused_assets = Vec::new();
+    let mut unused_licensed_assets = Vec::new();
+    let mut unlicensed_used_assets = Vec::new();
+    if reach.high_confidence_graph || options.include_low_confidence {
+        progress.phase("Scanning assets", None);
+        let mut used_asset_paths = collect_used_assets(root, &scan.files, assets)?;
+        used_asset_paths.extend(
+            angular_styles(root)
+                .into_iter()
+                .filter(|path| assets.contains(path)),
+        );
+        used_asset_paths.extend(astro_used_assets(root, assets)?);
+        used_assets = used_asset_paths
+            .iter()
+            .map(|path| relative_display(root, path))
+            .collect();
+        used_assets.sort();
+        let mut unused_asset_paths: Vec<&PathBuf> = assets
+            .difference(&used_asset_paths)
+            .filter(|path| !is_public_asset(path))
+            .collect();
+        if let Some(max_age) = options.ignore_newer_than
+            && !unused_asset_paths.is_empty()
+        {
+            let recent = recently_added_assets(root, &unused_asset_paths, max_age);
+            if !recent.is_empty() {
+                unused_asset_paths.retain(|path| !recent.contains(*path));
+                warnings.push(format!(
+                    "Skipped {} unused assets added recently (--ignore-newer-than).",
+                    recent.len()
+                ));
+            }
+        }
+        unused_assets = unused_asset_paths
+            .iter()
+            .map(|path| relative_display(root, path))
+            .collect();
+        unused_assets.sort();
+        if options.audit_asset_licenses {
+            (unused_licensed_assets, unlicensed_used_assets) = audit_asset_licenses(
+                root,
+                &resolver.path_index,
+                &used_asset_paths,
+                &unused_asset_paths,
+            );
+        }
+    } else {
+        warnings.push(
+            "unused_files and unused_exports omitted (use --include-low-confidence to force)."
+                .to_string(),
+        );
+        warnings.push(
+            "unused_assets omitted because graph confidence is low (use --include-low-confidence to force)."
+                .to_string(),
+        );
+    }
+    let duplicate_assets = if options.find_duplicate_assets {
+        find_assets_duplicated_in_dependencies(root, assets, deps.declared_deps.keys())?
+    } else {
+        Vec::new()
+    };
+    Ok(AssetsArtifact {
+        used_assets,
+        unused_assets,
+        unused_licensed_assets,
+        unlicensed_used_assets,
+        duplicate_assets,
+        warnings,
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
+fn exports_stage(
+    root: &Path,
+    options: &AnalysisOptions,
+    scan: &ScanArtifact,
+    parse: &ParseArtifact,
+    resolve: &ResolveArtifact,
+    reach: &ReachArtifact,
+    resolver: &Resolver,
+    progress: &Progress,
+) -> Result<ExportsArtifact> {
+    let mut warnings = Vec::new();
+    if !(reach.high_confidence_graph || options.include_low_confidence) {
+        return Ok(ExportsArtifact {
+            unused_exports: Vec::new(),
+            storybook_only_exports: Vec::new(),
+            warnings,
+        });
+    }
+    let files = &scan.files;
+    let entries = resolve.entries();
+    progress.phase("Counting export tokens", Some(files.len()));
+    let side_effects = package_side_effects(root, read_root_package_json(root)?.as_ref(), resolver);
+    // Exports that only stories import come out of the same pass: unused once everything only
+    // stories reach is taken out of the project.
+    let (unused_exports, storybook_only_exports) = find_unused_exports(
+        root,
+        files,
+        &reach.reachable,
+        &entries,
+        &parse.modules,
+        resolver,
+        &reach.maybe_used_from_unresolved,
+        &side_effects,
+        reach.non_story_reachable.as_ref(),
+        |file| {
+            progress.inc();
+            fs::read_to_string(file).unwrap_or_default()
+        },
+        &mut warnings,
+    )?;
+    Ok(ExportsArtifact {
+        unused_exports,
+        storybook_only_exports,
+        warnings,
+    })
+}
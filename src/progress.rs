@@ -0,0 +1,74 @@
+#[cfg(feature = "progress")]
+use indicatif::{ProgressBar, ProgressDrawTarget, ProgressStyle};
+#[cfg(feature = "progress")]
+use std::time::Duration;
+
+/// Progress display on stderr for the analysis phases (discovery, parsing, asset scanning,
+/// export token counting). Disabled instances, and builds without the `progress` feature,
+/// draw nothing.
+pub(crate) struct Progress {
+    #[cfg(feature = "progress")]
+    bar: Option<ProgressBar>,
+}
+
+#[cfg(feature = "progress")]
+impl Progress {
+    pub(crate) fn new(enabled: bool) -> Self {
+        let bar = enabled.then(|| {
+            let bar = ProgressBar::with_draw_target(None, ProgressDrawTarget::stderr());
+            bar.enable_steady_tick(Duration::from_millis(100));
+            bar
+        });
+        Self { bar }
+    }
+
+    /// Starts a phase: a counted bar when `len` is known, a spinner otherwise.
+    pub(crate) fn phase(&self, message: &'static str, len: Option<usize>) {
+        let Some(bar) = &self.bar else {
+            return;
+        };
+        let style = match len {
+            Some(_) => ProgressStyle::with_template("{spinner} {msg:<20} [{bar:30}] {pos}/{len}")
+                .map(|style| style.progress_chars("=> ")),
+            None => ProgressStyle::with_template("{spinner} {msg}"),
+        };
+        if let Ok(style) = style {
+            bar.set_style(style);
+        }
+        bar.set_length(len.unwrap_or(0) as u64);
+        bar.set_position(0);
+        bar.set_message(message);
+    }
+
+    pub(crate) fn inc(&self) {
+        if let Some(bar) = &self.bar {
+            bar.inc(1);
+        }
+    }
+
+    pub(crate) fn finish(&self) {
+        if let Some(bar) = &self.bar {
+            bar.finish_and_clear();
+        }
+    }
+}
+
+#[cfg(not(feature = "progress"))]
+impl Progress {
+    pub(crate) fn new(_enabled: bool) -> Self {
+        Self {}
+    }
+
+    pub(crate) fn phase(&self, _message: &'static str, _len: Option<usize>) {}
+
+    pub(crate) fn inc(&self) {}
+
+    pub(crate) fn finish(&self) {}
+}
+
+impl Drop for Progress {
+    /// Clears the bar when the analysis returns early with an error.
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
@@ -0,0 +1,225 @@
+use super::*;
+use crate::findings::{
+    BROKEN_ASSET_REFERENCE, BROKEN_IMPORT, COMMENTED_CODE, DEV_DEPENDENCY_IN_PRODUCTION,
+    DUPLICATE_ASSET, MISPLACED_DEPENDENCY, MISSING_DEPENDENCY, ORPHANED_TYPES_PACKAGE,
+    STORYBOOK_ONLY_EXPORT, STORYBOOK_ONLY_FILE, TOOLING_ONLY_FILE, UNDECLARED_ENV_VAR,
+    UNUSED_ASSET, UNUSED_DEPENDENCY, UNUSED_ENV_VAR, UNUSED_EXPORT, UNUSED_FILE, UNUSED_SCRIPT,
+};
+
+/// `--format vimgrep` / `--format compile`: one `file:line:col` line per finding, for Vim's
+/// quickfix list (`:cexpr system('haadi --format vimgrep')`) and Emacs compilation-mode.
+/// File-level findings point at line 1; findings about names (dependencies, env variables,
+/// broken specifiers) point at the first line mentioning them, or line 1 when none does.
+pub(crate) fn print_quickfix_report(report: &Report, format: OutputFormat) {
+    let root = Path::new(&report.root);
+    let mut sources: HashMap<String, String> = HashMap::new();
+    let mut locate = |file: &str, needle: &str| -> (usize, usize) {
+        let source = sources
+            .entry(file.to_string())
+            .or_insert_with(|| fs::read_to_string(root.join(file)).unwrap_or_default());
+        match source.find(needle) {
+            Some(offset) if !needle.is_empty() => line_col(source, offset),
+            _ => (1, 1),
+        }
+    };
+
+    let mut lines: Vec<(String, (usize, usize), String, String)> = Vec::new();
+    let mut push = |file: &str, position, message: String, id: String| {
+        lines.push((file.replace('\\', "/"), position, message, id));
+    };
+
+    for file in &report.unused_files {
+        push(
+            file,
+            (1, 1),
+            "unused file: not reachable from any entry".to_string(),
+            finding_id(UNUSED_FILE, file, None),
+        );
+    }
+    for file in &report.tooling_only_files {
+        push(
+            file,
+            (1, 1),
+            "only used by tests, stories, or tooling scripts".to_string(),
+            finding_id(TOOLING_ONLY_FILE, file, None),
+        );
+    }
+    for file in &report.storybook_only_files {
+        push(
+            file,
+            (1, 1),
+            "only used by Storybook stories".to_string(),
+            finding_id(STORYBOOK_ONLY_FILE, file, None),
+        );
+    }
+    for file in &report.unused_assets {
+        push(
+            file,
+            (1, 1),
+            "unused asset".to_string(),
+            finding_id(UNUSED_ASSET, file, None),
+        );
+    }
+    for dep in &report.unused_dependencies {
+        push(
+            "package.json",
+            locate("package.json", &format!("\"{dep}\"")),
+            format!("unused dependency '{dep}'"),
+            finding_id(UNUSED_DEPENDENCY, dep, None),
+        );
+    }
+    for name in &report.orphaned_types_packages {
+        push(
+            "package.json",
+            locate("package.json", &format!("\"{name}\"")),
+            format!("'{name}' types a package that is neither declared nor imported"),
+            finding_id(ORPHANED_TYPES_PACKAGE, name, None),
+        );
+    }
+    for item in &report.missing_dependencies {
+        for file in &item.files {
+            push(
+                file,
+                locate(file, &item.name),
+                format!(
+                    "'{}' is imported but not declared in package.json",
+                    item.name
+                ),
+                finding_id(MISSING_DEPENDENCY, &item.name, None),
+            );
+        }
+    }
+    for item in &report.misplaced_dependencies {
+        push(
+            &item.package_json,
+            locate(&item.package_json, &format!("\"{}\"", item.name)),
+            format!(
+                "'{}' is in {} but belongs in {}",
+                item.name, item.declared_in, item.move_to
+            ),
+            finding_id(MISPLACED_DEPENDENCY, &item.name, None),
+        );
+    }
+    for item in &report.dev_dependencies_in_production {
+        for file in &item.files {
+            push(
+                file,
+                locate(file, &item.name),
+                format!(
+                    "'{}' is only a devDependency but production code imports it",
+                    item.name
+                ),
+                finding_id(DEV_DEPENDENCY_IN_PRODUCTION, &item.name, None),
+            );
+        }
+    }
+    for item in &report.unused_exports {
+        let position = if item.line == 0 {
+            (1, 1)
+        } else {
+            (item.line, item.column)
+        };
+        push(
+            &item.file,
+            position,
+            format!("unused export '{}'", item.export),
+            finding_id(UNUSED_EXPORT, &item.file, Some(&item.export)),
+        );
+    }
+    for item in &report.storybook_only_exports {
+        let position = if item.line == 0 {
+            (1, 1)
+        } else {
+            (item.line, item.column)
+        };
+        push(
+            &item.file,
+            position,
+            format!(
+                "export '{}' is only imported by Storybook stories",
+                item.export
+            ),
+            finding_id(STORYBOOK_ONLY_EXPORT, &item.file, Some(&item.export)),
+        );
+    }
+    for item in &report.duplicate_assets {
+        push(
+            &item.file,
+            (1, 1),
+            format!("duplicate of {} ({})", item.package_file, item.package),
+            finding_id(DUPLICATE_ASSET, &item.file, None),
+        );
+    }
+    for item in &report.unused_env_vars {
+        let Some(env_file) = item.env_files.first() else {
+            continue;
+        };
+        push(
+            env_file,
+            locate(env_file, &item.name),
+            format!("unused env var '{}'", item.name),
+            finding_id(UNUSED_ENV_VAR, &item.name, None),
+        );
+    }
+    for item in &report.undeclared_env_vars {
+        for file in &item.files {
+            push(
+                file,
+                locate(file, &item.name),
+                format!("env var '{}' is not declared in any .env file", item.name),
+                finding_id(UNDECLARED_ENV_VAR, &item.name, None),
+            );
+        }
+    }
+    for item in &report.broken_imports {
+        let message = match &item.suggestion {
+            Some(suggestion) => format!(
+                "broken import '{}' (did you mean {suggestion}?)",
+                item.specifier
+            ),
+            None => format!("broken import '{}'", item.specifier),
+        };
+        push(
+            &item.file,
+            locate(&item.file, &item.specifier),
+            message,
+            finding_id(BROKEN_IMPORT, &item.file, Some(&item.specifier)),
+        );
+    }
+    for item in &report.broken_asset_references {
+        push(
+            &item.file,
+            locate(&item.file, &item.specifier),
+            format!("missing asset '{}'", item.specifier),
+            finding_id(BROKEN_ASSET_REFERENCE, &item.file, Some(&item.specifier)),
+        );
+    }
+    for item in &report.commented_code {
+        push(
+            &item.file,
+            (item.start_line, 1),
+            format!(
+                "commented-out code (lines {}-{})",
+                item.start_line, item.end_line
+            ),
+            finding_id(COMMENTED_CODE, &item.file, Some(&item.statement)),
+        );
+    }
+    for item in &report.unused_scripts {
+        push(
+            &item.package_json,
+            locate(&item.package_json, &format!("\"{}\"", item.name)),
+            format!("unused script '{}'", item.name),
+            finding_id(UNUSED_SCRIPT, &item.package_json, Some(&item.name)),
+        );
+    }
+
+    for (file, (line, column), message, id) in lines {
+        match format {
+            OutputFormat::Vimgrep => println!("{file}:{line}:{column}:{message}"),
+            OutputFormat::Compile => {
+                println!("{file}:{line}:{column}: warning: {message} [{id}]")
+            }
+        }
+    }
+}
@@ -0,0 +1,56 @@
+use super::*;
+
+/// Default `--max-files`: far above any real project, far below a home directory.
+pub(crate) const DEFAULT_MAX_FILES: usize = 500_000;
+/// Source files a root without any project file may hold before `--force` is needed.
+const UNMARKED_ROOT_MAX_SOURCE_FILES: usize = 20_000;
+/// Files that make a folder look like a JS/TS project.
+const PROJECT_MARKERS: &[&str] = &[
+    "package.json",
+    "tsconfig.json",
+    "jsconfig.json",
+    "haadi.json",
+    "haadi.toml",
+];
+
+/// Refuses, before any scanning, a root that is the filesystem root or the home directory and
+/// has no project file, unless `force` is set.
+pub(crate) fn check_root_before_scan(root: &Path, force: bool) -> Result<()> {
+    if force || has_project_marker(root) {
+        return Ok(());
+    }
+    let home = ["HOME", "USERPROFILE"]
+        .into_iter()
+        .filter_map(std::env::var_os)
+        .filter_map(|dir| fs::canonicalize(dir).ok())
+        .any(|home| home == root);
+    let what = if root.parent().is_none() {
+        "the filesystem root"
+    } else if home {
+        "your home directory"
+    } else {
+        return Ok(());
+    };
+    anyhow::bail!(
+        "Refusing to analyze {} ({what}): it has no package.json or tsconfig.json. Point --root at a project, or pass --force to scan it anyway.",
+        shown_path(root)
+    )
+}
+
+/// Refuses a root with no project file and more than [`UNMARKED_ROOT_MAX_SOURCE_FILES`] source
+/// files, which is usually a folder of many projects rather than one, unless `force` is set.
+pub(crate) fn check_root_after_scan(root: &Path, source_files: usize, force: bool) -> Result<()> {
+    if force || source_files <= UNMARKED_ROOT_MAX_SOURCE_FILES || has_project_marker(root) {
+        return Ok(());
+    }
+    anyhow::bail!(
+        "Refusing to analyze {}: it has no package.json or tsconfig.json but holds {source_files} source files, so it looks like a folder of projects rather than one. Point --root at a project, or pass --force to analyze it anyway.",
+        shown_path(root)
+    )
+}
+
+fn has_project_marker(root: &Path) -> bool {
+    PROJECT_MARKERS
+        .iter()
+        .any(|marker| root.join(marker).is_file())
+}
@@ -0,0 +1,140 @@
+use super::*;
+
+/// Route directories, most specific first: Remix / React Router file routes, then the Next.js
+/// `app` and `pages` routers, with or without `src/`.
+const ROUTE_ROOTS: &[&str] = &[
+    "src/app/routes",
+    "app/routes",
+    "src/routes",
+    "src/app",
+    "app",
+    "src/pages",
+    "pages",
+];
+
+/// Packages whose presence means `app/`, `pages/`, and `routes/` folders hold file routes:
+/// Next.js, and React Router (or Remix) in framework mode.
+const FILE_ROUTE_PACKAGES: &[&str] = &["next", "@react-router/dev", "@remix-run/dev"];
+
+/// Groups unused files and assets by the route subtree they sit in, for the root and each
+/// workspace package that uses file routes (see [`uses_file_routes`]). A subtree is the first segment under a route directory:
+/// `app/marketing/about/hero.tsx` belongs to `app/marketing`, `pages/blog.tsx` to
+/// `pages/blog`, and the flat route `app/routes/admin.users.tsx` to `app/routes/admin`. Files
+/// directly in `app/` belong to no route. Routes without unused files or assets are left out.
+pub(crate) fn group_by_route(
+    root: &Path,
+    package_dirs: &[PathBuf],
+    files: &HashSet<PathBuf>,
+    assets: &HashSet<PathBuf>,
+    unused_files: &[String],
+    unused_assets: &[String],
+) -> Vec<RouteGroup> {
+    let mut bases: Vec<(String, bool)> = package_dirs
+        .iter()
+        .filter_map(|dir| {
+            let rel = dir.strip_prefix(root).ok()?;
+            Some((
+                format!("{}/", rel.to_string_lossy().replace('\\', "/")),
+                uses_file_routes(dir),
+            ))
+        })
+        .filter(|(base, _)| base != "/")
+        .collect();
+    // Deeper packages first, so `apps/web/` wins over the root.
+    bases.sort_by_key(|(base, _)| std::cmp::Reverse(base.len()));
+    bases.push((String::new(), uses_file_routes(root)));
+    let route_of = |rel: &str| route_subtree(&bases, rel);
+
+    let mut groups: BTreeMap<String, RouteGroup> = BTreeMap::new();
+    for file in unused_files {
+        if let Some(route) = route_of(file) {
+            let group = groups.entry(route.clone()).or_insert_with(|| RouteGroup {
+                route,
+                ..Default::default()
+            });
+            group.unused_files.push(file.clone());
+        }
+    }
+    for asset in unused_assets {
+        if let Some(route) = route_of(asset) {
+            let group = groups.entry(route.clone()).or_insert_with(|| RouteGroup {
+                route,
+                ..Default::default()
+            });
+            group.unused_assets.push(asset.clone());
+        }
+    }
+    for file in files {
+        if let Some(group) =
+            route_of(&relative_display(root, file)).and_then(|r| groups.get_mut(&r))
+        {
+            group.total_files += 1;
+        }
+    }
+    for asset in assets {
+        if let Some(group) =
+            route_of(&relative_display(root, asset)).and_then(|r| groups.get_mut(&r))
+        {
+            group.total_assets += 1;
+        }
+    }
+    groups.into_values().collect()
+}
+
+/// The route subtree holding `rel`, a root-relative path, if it is under a route directory of
+/// the package whose directory (`base`, with a trailing slash) prefixes it, and that package
+/// uses file routes.
+fn route_subtree(bases: &[(String, bool)], rel: &str) -> Option<String> {
+    let rel = rel.replace('\\', "/");
+    let (base, file_routes) = bases
+        .iter()
+        .find(|(base, _)| rel.starts_with(base.as_str()))?;
+    if !file_routes {
+        return None;
+    }
+    let inner = &rel[base.len()..];
+    let route_root = ROUTE_ROOTS
+        .iter()
+        .find(|route_root| inner.starts_with(&format!("{route_root}/")))?;
+    let rest = &inner[route_root.len() + 1..];
+    let segment = match rest.split_once('/') {
+        Some((dir, _)) => dir,
+        None if route_root.ends_with("pages") => {
+            rest.rsplit_once('.').map_or(rest, |(stem, _)| stem)
+        }
+        None if route_root.ends_with("routes") => rest,
+        // Shared files at the top of `app/` belong to no single route.
+        None => return None,
+    };
+    // Flat routes nest with dots: `admin.users.tsx` and `admin.users/route.tsx` are `admin`.
+    let segment = if route_root.ends_with("routes") {
+        segment.split('.').next().unwrap_or(segment)
+    } else {
+        segment
+    };
+    Some(format!("{base}{route_root}/{segment}"))
+}
+
+/// Whether the package in `dir` routes by file: it has a `next.config.*`, or its
+/// `package.json` declares one of [`FILE_ROUTE_PACKAGES`]. Elsewhere an `app/` or `pages/`
+/// folder is just a folder.
+fn uses_file_routes(dir: &Path) -> bool {
+    let has_next_config = fs::read_dir(dir).is_ok_and(|entries| {
+        entries.filter_map(|entry| entry.ok()).any(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| name.starts_with("next.config."))
+        })
+    });
+    has_next_config
+        || read_root_package_json(dir)
+            .ok()
+            .flatten()
+            .is_some_and(|manifest| {
+                let declared = declared_dependencies(&manifest);
+                FILE_ROUTE_PACKAGES
+                    .iter()
+                    .any(|package| declared.contains_key(*package))
+            })
+}
@@ -0,0 +1,123 @@
+use super::*;
+use crate::findings::fnv1a;
+
+/// Parses `--sample`: a percentage such as `10%`, `10`, or `2.5%`, above 0 and at most 100.
+pub(crate) fn parse_sample(raw: &str) -> std::result::Result<f64, String> {
+    let number = raw.trim().trim_end_matches('%').trim();
+    let percent: f64 = number
+        .parse()
+        .map_err(|_| format!("invalid percentage '{raw}' (use e.g. 10%)"))?;
+    if !(percent > 0.0 && percent <= 100.0) {
+        return Err(format!("sample '{raw}' must be above 0% and at most 100%"));
+    }
+    Ok(percent)
+}
+
+/// Whether findings about `subject` (a path, dependency, or variable name) fall in a
+/// `percent` sample. The bucket comes from a hash of the subject alone, so a file keeps its
+/// place across runs and machines, all findings about it stay together, and raising the
+/// percentage only adds subjects.
+pub(crate) fn in_sample(subject: &str, percent: f64) -> bool {
+    let bucket = fnv1a(subject.replace('\\', "/").as_bytes()) % 10_000;
+    (bucket as f64) < percent * 100.0
+}
+
+/// Drops the findings outside a `percent` sample from every finding category, workspace
+/// packages included, and updates the counts to match.
+pub(crate) fn sample_report(report: &mut Report, percent: f64) {
+    let before = count_sampled(report);
+    let keep = |subject: &str| in_sample(subject, percent);
+
+    report.unused_files.retain(|file| keep(file));
+    report.tooling_only_files.retain(|file| keep(file));
+    report.storybook_only_files.retain(|file| keep(file));
+    report.unused_assets.retain(|file| keep(file));
+    report.unused_dependencies.retain(|name| keep(name));
+    report.missing_dependencies.retain(|item| keep(&item.name));
+    report
+        .misplaced_dependencies
+        .retain(|item| keep(&item.name));
+    report
+        .dev_dependencies_in_production
+        .retain(|item| keep(&item.name));
+    report.orphaned_types_packages.retain(|name| keep(name));
+    report.unused_exports.retain(|item| keep(&item.file));
+    report
+        .storybook_only_exports
+        .retain(|item| keep(&item.file));
+    report.duplicate_assets.retain(|item| keep(&item.file));
+    report.unused_env_vars.retain(|item| keep(&item.name));
+    report.undeclared_env_vars.retain(|item| keep(&item.name));
+    report.broken_imports.retain(|item| keep(&item.file));
+    report
+        .broken_asset_references
+        .retain(|item| keep(&item.file));
+    report.commented_code.retain(|item| keep(&item.file));
+    report
+        .unused_scripts
+        .retain(|item| keep(&item.package_json));
+    for package in &mut report.packages {
+        package.unused_files.retain(|file| keep(file));
+        package.unused_assets.retain(|file| keep(file));
+        package.unused_dependencies.retain(|name| keep(name));
+        package.unused_exports.retain(|item| keep(&item.file));
+        package.summary.unused_files_count = package.unused_files.len();
+        package.summary.unused_assets_count = package.unused_assets.len();
+        package.summary.unused_dependencies_count = package.unused_dependencies.len();
+        package.summary.unused_exports_count = package.unused_exports.len();
+    }
+
+    let summary = &mut report.summary;
+    summary.unused_files_count = report.unused_files.len();
+    summary.tooling_only_files_count = report.tooling_only_files.len();
+    summary.storybook_only_files_count = report.storybook_only_files.len();
+    summary.unused_assets_count = report.unused_assets.len();
+    summary.unused_dependencies_count = report.unused_dependencies.len();
+    summary.missing_dependencies_count = report.missing_dependencies.len();
+    summary.misplaced_dependencies_count = report.misplaced_dependencies.len();
+    summary.dev_dependencies_in_production_count = report.dev_dependencies_in_production.len();
+    summary.orphaned_types_packages_count = report.orphaned_types_packages.len();
+    summary.unused_exports_count = report.unused_exports.len();
+    summary.storybook_only_exports_count = report.storybook_only_exports.len();
+    summary.duplicate_assets_count = report.duplicate_assets.len();
+    summary.unused_env_vars_count = report.unused_env_vars.len();
+    summary.undeclared_env_vars_count = report.undeclared_env_vars.len();
+    summary.broken_imports_count = report.broken_imports.len();
+    summary.broken_asset_references_count = report.broken_asset_references.len();
+    summary.commented_code_count = report.commented_code.len();
+    summary.unused_scripts_count = report.unused_scripts.len();
+    summary.packages = report
+        .packages
+        .iter()
+        .map(|package| package.summary.clone())
+        .collect();
+    summary.sample_pct = Some(percent);
+
+    let after = count_sampled(report);
+    if after < before {
+        report.warnings.push(format!(
+            "--sample {percent}%: reporting {after} of {before} findings; the rest stay hidden until the sample grows."
+        ));
+    }
+}
+
+fn count_sampled(report: &Report) -> usize {
+    report.unused_files.len()
+        + report.tooling_only_files.len()
+        + report.storybook_only_files.len()
+        + report.unused_assets.len()
+        + report.unused_dependencies.len()
+        + report.missing_dependencies.len()
+        + report.misplaced_dependencies.len()
+        + report.dev_dependencies_in_production.len()
+        + report.orphaned_types_packages.len()
+        + report.unused_exports.len()
+        + report.storybook_only_exports.len()
+        + report.duplicate_assets.len()
+        + report.unused_env_vars.len()
+        + report.undeclared_env_vars.len()
+        + report.broken_imports.len()
+        + report.broken_asset_references.len()
+        + report.commented_code.len()
+        + report.unused_scripts.len()
+}
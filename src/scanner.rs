@@ -1,37 +1,249 @@
 use super::*;
+use std::hash::{Hash, Hasher};
 use walkdir::WalkDir;
-pub(crate) fn collect_source_files(root: &Path) -> Result<HashSet<PathBuf>> {
-    let mut files = HashSet::new();
+/// Every file and directory under the root (ignored directories excluded), gathered in a
+/// single walk. Source, asset, and declaration sets are derived from it, and the resolver
+/// checks candidate paths against it instead of probing the filesystem.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub(crate) struct PathIndex {
+    files: HashSet<PathBuf>,
+    dirs: HashSet<PathBuf>,
+    /// On case-insensitive filesystems a miss proves nothing, so lookups fall back to the
+    /// filesystem instead.
+    pub(crate) case_insensitive: bool,
+    /// Built from an in-memory snapshot ([`PathIndex::from_files`]): there is no filesystem to
+    /// fall back to, so every path outside the index is missing.
+    in_memory: bool,
+}
 
-    for entry in WalkDir::new(root)
-        .into_iter()
-        .filter_entry(|e| !is_ignored_dir(e.path()))
-        .filter_map(|e| e.ok())
-    {
-        let path = entry.path();
-        if path.is_file() && has_source_extension(path) {
+impl PathIndex {
+    /// Walks `root`, failing once more than `max_files` files are found (0: no limit).
+    pub(crate) fn build(root: &Path, follow_symlinks: bool, max_files: usize) -> Result<Self> {
+        let mut index = PathIndex::default();
+        for entry in walk_project(root, follow_symlinks) {
+            let path = normalize_path(entry.path().to_path_buf());
+            if entry.file_type().is_dir() {
+                index.dirs.insert(path);
+            } else if entry.path().is_file() {
+                index.files.insert(path);
+                if max_files > 0 && index.files.len() > max_files {
+                    anyhow::bail!(
+                        "Stopped scanning {}: it holds more than {max_files} files. Check that --root points at a project, or raise --max-files (0 for no limit).",
+                        shown_path(root)
+                    );
+                }
+            }
+        }
+        Ok(index)
+    }
+
+    /// Index of a virtual project given as file paths; their ancestor directories are added.
+    pub(crate) fn from_files(files: impl IntoIterator<Item = PathBuf>) -> Self {
+        let mut index = PathIndex {
+            in_memory: true,
+            ..PathIndex::default()
+        };
+        for file in files {
+            let file = normalize_path(file);
+            index
+                .dirs
+                .extend(file.ancestors().skip(1).map(Path::to_path_buf));
+            index.files.insert(file);
+        }
+        index
+    }
+
+    pub(crate) fn files(&self) -> impl Iterator<Item = &PathBuf> {
+        self.files.iter()
+    }
+
+    /// `Some(true)` for an indexed file, `Some(false)` when the path is known not to be a
+    /// file, and `None` outside the indexed tree (ignored directories, paths reached through
+    /// symlinks, files outside the root), where callers must ask the filesystem.
+    pub(crate) fn is_file(&self, path: &Path) -> Option<bool> {
+        let path = normalize_path(path.to_path_buf());
+        if self.files.contains(&path) {
+            return Some(true);
+        }
+        if self.dirs.contains(&path) {
+            return Some(false);
+        }
+        self.known_missing(&path).then_some(false)
+    }
+
+    /// Like [`Path::exists`], answered from the index when possible.
+    pub(crate) fn exists(&self, path: &Path) -> Option<bool> {
+        let path = normalize_path(path.to_path_buf());
+        if self.files.contains(&path) || self.dirs.contains(&path) {
+            return Some(true);
+        }
+        self.known_missing(&path).then_some(false)
+    }
+
+    /// A path is known to be missing when its directory was walked and it is neither an
+    /// indexed entry nor an ignored directory that the walk skipped on purpose.
+    fn known_missing(&self, path: &Path) -> bool {
+        if self.in_memory {
+            return true;
+        }
+        !self.case_insensitive
+            && !is_ignored_dir(path)
+            && path
+                .parent()
+                .is_some_and(|parent| self.dirs.contains(parent))
+    }
+
+    fn collect(&self, matches: impl Fn(&Path) -> bool) -> Result<HashSet<PathBuf>> {
+        let mut files = HashSet::new();
+        for path in self.files.iter().filter(|path| matches(path)) {
             files.insert(fs::canonicalize(path)?);
         }
+        Ok(files)
     }
+}
 
-    Ok(files)
+pub(crate) fn collect_source_files(index: &PathIndex) -> Result<HashSet<PathBuf>> {
+    index.collect(has_source_extension)
 }
 
-pub(crate) fn collect_asset_files(root: &Path) -> Result<HashSet<PathBuf>> {
-    let mut files = HashSet::new();
+/// Hand-written `.d.ts` files, which are kept out of the source set.
+pub(crate) fn collect_declaration_files(index: &PathIndex) -> Result<HashSet<PathBuf>> {
+    index.collect(is_declaration_file)
+}
 
-    for entry in WalkDir::new(root)
+/// Walks the project tree, skipping ignored directories. With `follow_symlinks`, symlinked
+/// directories are descended into once per real directory: a directory whose canonical path
+/// was already visited (a cycle, or a second link to the same folder) is skipped. Files are
+/// canonicalized by the callers, the same way the resolver canonicalizes import targets.
+fn walk_project(root: &Path, follow_symlinks: bool) -> impl Iterator<Item = walkdir::DirEntry> {
+    let mut visited_dirs: HashSet<PathBuf> = HashSet::new();
+    WalkDir::new(root)
+        .follow_links(follow_symlinks)
         .into_iter()
-        .filter_entry(|e| !is_ignored_dir(e.path()))
+        .filter_entry(move |e| {
+            if is_ignored_dir(e.path()) {
+                return false;
+            }
+            if !follow_symlinks || !e.file_type().is_dir() {
+                return true;
+            }
+            let canonical = fs::canonicalize(e.path()).unwrap_or_else(|_| e.path().to_path_buf());
+            visited_dirs.insert(canonical)
+        })
         .filter_map(|e| e.ok())
-    {
-        let path = entry.path();
-        if path.is_file() && has_asset_extension(path) {
-            files.insert(fs::canonicalize(path)?);
+}
+
+/// Narrows the source set to what the root `tsconfig.json` (or `jsconfig.json`) compiles:
+/// `files`, plus `include` minus `exclude`, each inherited through `extends` and resolved
+/// relative to the config that declares it. Returns `None` when there is no such config.
+pub(crate) fn filter_files_by_tsconfig(
+    root: &Path,
+    files: &HashSet<PathBuf>,
+) -> Result<Option<HashSet<PathBuf>>> {
+    let Some(seed) = ["tsconfig.json", "jsconfig.json"]
+        .iter()
+        .map(|name| root.join(name))
+        .find(|path| path.exists())
+    else {
+        return Ok(None);
+    };
+
+    let mut listed: Option<Vec<String>> = None;
+    let mut include: Option<Vec<String>> = None;
+    let mut exclude: Option<Vec<String>> = None;
+    let mut visiting = HashSet::new();
+    let mut next = Some(seed);
+    while let Some(config_path) = next.take() {
+        let canonical = fs::canonicalize(&config_path).unwrap_or(config_path);
+        if !visiting.insert(canonical.clone()) {
+            break;
+        }
+        let raw = fs::read_to_string(&canonical).unwrap_or_default();
+        let Ok(value) = serde_json::from_str::<serde_json::Value>(&sanitize_jsonc(&raw)) else {
+            break;
+        };
+        let config_dir = canonical.parent().unwrap_or(root);
+        let patterns = |key: &str| -> Option<Vec<String>> {
+            let items = value.get(key)?.as_array()?;
+            Some(
+                items
+                    .iter()
+                    .filter_map(|v| v.as_str())
+                    .filter_map(|p| to_rel_pattern(root, &config_dir.join(p)))
+                    .collect(),
+            )
+        };
+        // Each property is taken from the nearest config that sets it.
+        listed = listed.or_else(|| patterns("files"));
+        include = include.or_else(|| patterns("include"));
+        exclude = exclude.or_else(|| patterns("exclude"));
+
+        next = value
+            .get("extends")
+            .and_then(|v| v.as_str())
+            .and_then(|extends| resolve_tsconfig_reference_path(config_dir, extends));
+    }
+
+    let include = include.unwrap_or_else(|| {
+        if listed.is_some() {
+            Vec::new()
+        } else {
+            vec!["**/*".to_string()]
+        }
+    });
+    let to_regexes = |patterns: &[String]| -> Vec<Regex> {
+        patterns
+            .iter()
+            .filter_map(|p| Regex::new(&tsconfig_glob_to_regex(p)).ok())
+            .collect()
+    };
+    let include_res = to_regexes(&include);
+    let exclude_res = to_regexes(&exclude.unwrap_or_default());
+    let listed: HashSet<String> = listed.unwrap_or_default().into_iter().collect();
+
+    Ok(Some(
+        files
+            .iter()
+            .filter(|file| {
+                let rel = relative_display(root, file).replace('\\', "/");
+                listed.contains(&rel)
+                    || (include_res.iter().any(|re| re.is_match(&rel))
+                        && !exclude_res.iter().any(|re| re.is_match(&rel)))
+            })
+            .cloned()
+            .collect(),
+    ))
+}
+
+/// tsconfig globs: `**/` spans zero or more directories and a pattern without wildcards or an
+/// extension names a directory, so it also matches everything below it.
+fn tsconfig_glob_to_regex(pattern: &str) -> String {
+    let mut out = String::from("^");
+    let mut rest = pattern;
+    while let Some(ch) = rest.chars().next() {
+        if let Some(after) = rest.strip_prefix("**/") {
+            out.push_str("(?:.*/)?");
+            rest = after;
+            continue;
         }
+        if let Some(after) = rest.strip_prefix("**") {
+            out.push_str(".*");
+            rest = after;
+            continue;
+        }
+        match ch {
+            '*' => out.push_str("[^/]*"),
+            '?' => out.push_str("[^/]"),
+            _ => out.push_str(&regex::escape(&ch.to_string())),
+        }
+        rest = &rest[ch.len_utf8()..];
     }
+    out.push_str("(?:/.*)?$");
+    out
+}
 
-    Ok(files)
+pub(crate) fn collect_asset_files(index: &PathIndex) -> Result<HashSet<PathBuf>> {
+    index.collect(has_asset_extension)
 }
 
 pub(crate) fn collect_used_assets(
@@ -43,12 +255,17 @@ pub(crate) fn collect_used_assets(
     let mut string_literals = HashSet::new();
     let indexed_assets: Vec<(PathBuf, String)> = assets
         .iter()
-        .map(|asset| (asset.clone(), relative_display(root, asset).replace('\\', "/")))
+        .map(|asset| {
+            (
+                asset.clone(),
+                relative_display(root, asset).replace('\\', "/"),
+            )
+        })
         .collect();
 
     // Single-pass source scan: collect string literals, direct asset imports, and import.meta.glob usage.
     for source_file in source_files {
-        let source = fs::read_to_string(source_file).unwrap_or_default();
+        let source = strip_comments(&fs::read_to_string(source_file).unwrap_or_default());
 
         collect_literals_and_direct_asset_usages(
             root,
@@ -62,7 +279,7 @@ pub(crate) fn collect_used_assets(
     }
 
     for asset in assets {
-        if is_public_asset(asset) {
+        if is_public_asset(asset) || is_e2e_fixture(asset) {
             used.insert(asset.clone());
             continue;
         }
@@ -176,7 +393,7 @@ fn normalize_path_components(path: &Path) -> Vec<String> {
     out
 }
 
-fn glob_path_pattern_to_regex(glob: &str) -> String {
+pub(crate) fn glob_path_pattern_to_regex(glob: &str) -> String {
     let mut out = String::from("^");
     let mut chars = glob.chars().peekable();
 
@@ -233,7 +450,7 @@ fn collect_literals_and_direct_asset_usages(
     Ok(())
 }
 
-fn resolve_asset_specifier(
+pub(crate) fn resolve_asset_specifier(
     root: &Path,
     from_file: &Path,
     specifier: &str,
@@ -293,7 +510,7 @@ fn resolve_asset_candidate(
     Ok(None)
 }
 
-fn normalize_path(path: PathBuf) -> PathBuf {
+pub(crate) fn normalize_path(path: PathBuf) -> PathBuf {
     use std::path::Component;
 
     let mut out = PathBuf::new();
@@ -345,3 +562,216 @@ fn asset_reference_candidates(root: &Path, asset: &Path) -> Vec<String> {
 
     refs.into_iter().collect()
 }
+
+/// Compares project assets against asset files inside `node_modules/<dep>` for each declared
+/// dependency. Candidates are bucketed by size and content hash, then confirmed byte-for-byte.
+/// Package listings come from the global cache when the CLI enabled it.
+pub(crate) fn find_assets_duplicated_in_dependencies<'a>(
+    root: &Path,
+    assets: &HashSet<PathBuf>,
+    dependencies: impl Iterator<Item = &'a String>,
+) -> Result<Vec<DuplicateAsset>> {
+    let mut by_size: HashMap<u64, Vec<&PathBuf>> = HashMap::new();
+    for asset in assets {
+        if let Ok(meta) = fs::metadata(asset) {
+            by_size.entry(meta.len()).or_default().push(asset);
+        }
+    }
+    if by_size.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let cache_path = global_cache_dir().map(|dir| dir.join(PACKAGE_ASSETS_CACHE));
+    let mut cache = cache_path
+        .as_deref()
+        .map(load_package_asset_cache)
+        .unwrap_or_default();
+    let mut cache_changed = false;
+    let today = unix_day();
+    let mut local_hashes: HashMap<PathBuf, Option<u64>> = HashMap::new();
+    let mut found: BTreeMap<String, DuplicateAsset> = BTreeMap::new();
+
+    for dep in dependencies {
+        let package_dir = root.join("node_modules").join(dep);
+        if !package_dir.is_dir() {
+            continue;
+        }
+
+        let uncached;
+        let version = package_version(&package_dir).filter(|_| is_registry_install(&package_dir));
+        let listing = match version {
+            Some(version) if cache_path.is_some() => {
+                let entry = cache
+                    .packages
+                    .entry(format!("{dep}@{version}"))
+                    .or_insert_with(|| {
+                        cache_changed = true;
+                        CachedPackage {
+                            used: today,
+                            assets: list_package_assets(&package_dir),
+                        }
+                    });
+                if entry.used != today {
+                    entry.used = today;
+                    cache_changed = true;
+                }
+                &entry.assets
+            }
+            _ => {
+                uncached = list_package_assets(&package_dir);
+                &uncached
+            }
+        };
+
+        for (package_file, size) in listing {
+            let Some(candidates) = by_size.get(size) else {
+                continue;
+            };
+            let Ok(package_bytes) = fs::read(package_dir.join(package_file)) else {
+                continue;
+            };
+            let package_hash = content_hash(&package_bytes);
+
+            for local in candidates {
+                // Each local file is read and hashed once; only a hash match reads it again,
+                // to rule out a collision.
+                let local_hash = *local_hashes
+                    .entry((*local).clone())
+                    .or_insert_with(|| fs::read(local).ok().map(|bytes| content_hash(&bytes)));
+                if local_hash != Some(package_hash) {
+                    continue;
+                }
+                let Ok(local_bytes) = fs::read(local) else {
+                    continue;
+                };
+                if local_bytes != package_bytes {
+                    continue;
+                }
+
+                let file = relative_display(root, local);
+                found.entry(file.clone()).or_insert_with(|| DuplicateAsset {
+                    file,
+                    package: dep.clone(),
+                    package_file: package_file.clone(),
+                });
+            }
+        }
+    }
+
+    if cache_changed && let Some(path) = &cache_path {
+        evict_package_assets(&mut cache, today);
+        // The cache only saves walks of node_modules; failing to write it is not an error.
+        let _ = save_package_asset_cache(path, &cache);
+    }
+    Ok(found.into_values().collect())
+}
+
+const PACKAGE_ASSETS_CACHE: &str = "package-assets.json";
+
+/// Packages left unused this many days are dropped from the cache.
+const PACKAGE_ASSETS_MAX_AGE_DAYS: u64 = 90;
+/// At most this many packages are cached; the least recently used go first.
+const PACKAGE_ASSETS_MAX_PACKAGES: usize = 5000;
+
+/// Asset files of installed packages, shared by every project on the machine. Keyed by
+/// `name@version`, which only identifies the contents of packages installed from a registry,
+/// so workspace and linked packages are never cached. A different haadi version starts a
+/// fresh cache.
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct PackageAssetCache {
+    haadi_version: String,
+    packages: BTreeMap<String, CachedPackage>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedPackage {
+    /// Day (since the Unix epoch) a run last read this entry.
+    used: u64,
+    /// Paths inside the package (`/`-separated) with their sizes.
+    assets: Vec<(String, u64)>,
+}
+
+fn unix_day() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() / (24 * 60 * 60))
+}
+
+/// Drops entries unused for [`PACKAGE_ASSETS_MAX_AGE_DAYS`], then the least recently used
+/// beyond [`PACKAGE_ASSETS_MAX_PACKAGES`].
+fn evict_package_assets(cache: &mut PackageAssetCache, today: u64) {
+    cache
+        .packages
+        .retain(|_, package| today.saturating_sub(package.used) <= PACKAGE_ASSETS_MAX_AGE_DAYS);
+    if cache.packages.len() > PACKAGE_ASSETS_MAX_PACKAGES {
+        let mut by_use: Vec<(u64, String)> = cache
+            .packages
+            .iter()
+            .map(|(key, package)| (package.used, key.clone()))
+            .collect();
+        by_use.sort();
+        let excess = cache.packages.len() - PACKAGE_ASSETS_MAX_PACKAGES;
+        for (_, key) in by_use.into_iter().take(excess) {
+            cache.packages.remove(&key);
+        }
+    }
+}
+
+/// Whether `package_dir` really lives inside a `node_modules` folder (npm, yarn, or the pnpm
+/// store) rather than linking to a workspace package or a local folder, whose assets can
+/// change without a version bump.
+fn is_registry_install(package_dir: &Path) -> bool {
+    fs::canonicalize(package_dir).is_ok_and(|dir| {
+        dir.components()
+            .any(|component| component.as_os_str() == "node_modules")
+    })
+}
+
+fn load_package_asset_cache(path: &Path) -> PackageAssetCache {
+    fs::read(path)
+        .ok()
+        .and_then(|raw| serde_json::from_slice::<PackageAssetCache>(&raw).ok())
+        .filter(|cache| cache.haadi_version == env!("CARGO_PKG_VERSION"))
+        .unwrap_or_else(|| PackageAssetCache {
+            haadi_version: env!("CARGO_PKG_VERSION").to_string(),
+            packages: BTreeMap::new(),
+        })
+}
+
+/// Writes through a temporary file so concurrent runs never read a half-written cache.
+fn save_package_asset_cache(path: &Path, cache: &PackageAssetCache) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let temp = path.with_extension(format!("json.{}", std::process::id()));
+    fs::write(&temp, serde_json::to_vec(cache)?)?;
+    fs::rename(&temp, path)?;
+    Ok(())
+}
+
+fn package_version(package_dir: &Path) -> Option<String> {
+    let raw = fs::read(package_dir.join("package.json")).ok()?;
+    let manifest: serde_json::Value = serde_json::from_slice(&raw).ok()?;
+    manifest.get("version")?.as_str().map(str::to_string)
+}
+
+/// Asset files inside one package (nested `node_modules` excluded), with their sizes.
+fn list_package_assets(package_dir: &Path) -> Vec<(String, u64)> {
+    WalkDir::new(package_dir)
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || e.file_name() != "node_modules")
+        .filter_map(|e| e.ok())
+        .filter(|entry| entry.file_type().is_file() && has_asset_extension(entry.path()))
+        .filter_map(|entry| {
+            let size = entry.metadata().ok()?.len();
+            let rel = relative_display(package_dir, entry.path()).replace('\\', "/");
+            Some((rel, size))
+        })
+        .collect()
+}
+
+fn content_hash(bytes: &[u8]) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    bytes.hash(&mut hasher);
+    hasher.finish()
+}
@@ -0,0 +1,174 @@
+use super::*;
+
+/// Runners that take the executable to run as their next word.
+const EXEC_RUNNERS: &[&str] = &["npx", "pnpx", "bunx"];
+/// Package managers: `yarn eslint` runs a binary, unless the word names a script.
+const PACKAGE_MANAGERS: &[&str] = &["yarn", "pnpm", "bun"];
+/// Wrappers that are binaries themselves and run the command after their own arguments.
+const WRAPPERS: &[&str] = &["cross-env", "dotenv", "env-cmd"];
+
+static SCRIPT_SEPARATOR_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"&&|\|\||[;|&]").unwrap());
+
+/// Declared dependencies whose executables the `scripts` of the `package.json` in `dir` run,
+/// e.g. `eslint` for `"lint": "eslint ."` or `rimraf` for `"clean": "npx rimraf dist"`. An
+/// executable maps to its package through `node_modules/.bin`, then the `bin` field of each
+/// installed dependency, then a dependency with the same (unscoped) name. `node_modules` is
+/// looked up from `dir` up to the root, so hoisted installs are found.
+pub(crate) fn script_binary_packages(
+    root: &Path,
+    dir: &Path,
+    declared: &HashMap<String, DepKind>,
+) -> Result<HashSet<String>> {
+    let Some(package_json) = read_root_package_json(dir)? else {
+        return Ok(HashSet::new());
+    };
+    let Some(scripts) = package_json.get("scripts").and_then(|v| v.as_object()) else {
+        return Ok(HashSet::new());
+    };
+    let binaries: BTreeSet<String> = scripts
+        .values()
+        .filter_map(|script| script.as_str())
+        .flat_map(|script| script_executables(script, |name| scripts.contains_key(name)))
+        .collect();
+    if binaries.is_empty() {
+        return Ok(HashSet::new());
+    }
+
+    let node_modules: Vec<PathBuf> = dir
+        .ancestors()
+        .take_while(|ancestor| ancestor.starts_with(root))
+        .map(|ancestor| ancestor.join("node_modules"))
+        .filter(|path| path.is_dir())
+        .collect();
+    let installed_bins = installed_bin_names(&node_modules, declared);
+
+    let mut used = HashSet::new();
+    for binary in &binaries {
+        let package = linked_bin_package(&node_modules, binary)
+            .filter(|package| declared.contains_key(package))
+            .or_else(|| installed_bins.get(binary.as_str()).cloned())
+            .or_else(|| {
+                declared
+                    .keys()
+                    .find(|name| {
+                        *name == binary || name.rsplit_once('/').is_some_and(|(_, n)| n == binary)
+                    })
+                    .cloned()
+            });
+        if let Some(package) = package {
+            debug!(%binary, %package, "script binary");
+            used.insert(package);
+        }
+    }
+    Ok(used)
+}
+
+/// The executables one script runs: the first word of every command in a `&&` / `||` / `;` /
+/// `|` chain, after `VAR=value` assignments and runners (`npx`, `pnpm exec`, `yarn`,
+/// `cross-env`, ...). `npm run x` and `yarn x` for a script `x` run no executable.
+fn script_executables(script: &str, is_script: impl Fn(&str) -> bool) -> Vec<String> {
+    let mut out = Vec::new();
+    for command in SCRIPT_SEPARATOR_RE.split(script) {
+        let mut words = command
+            .split_whitespace()
+            .map(|word| word.trim_matches(|c| c == '"' || c == '\''))
+            .peekable();
+        while let Some(word) = words.next() {
+            if word.is_empty() || word.contains('=') || word == "--" {
+                continue;
+            }
+            if EXEC_RUNNERS.contains(&word) {
+                while words.peek().is_some_and(|next| next.starts_with('-')) {
+                    words.next();
+                }
+                continue;
+            }
+            if word == "npm" {
+                match words.next() {
+                    Some("exec" | "x") => continue,
+                    _ => break,
+                }
+            }
+            if PACKAGE_MANAGERS.contains(&word) {
+                match words.peek().copied() {
+                    Some("exec" | "dlx" | "x") => {
+                        words.next();
+                        continue;
+                    }
+                    Some("run" | "run-script") | None => break,
+                    Some(next) if is_script(next) || next.starts_with('-') => break,
+                    Some(_) => continue,
+                }
+            }
+            let binary = word.rsplit("node_modules/.bin/").next().unwrap_or(word);
+            out.push(binary.to_string());
+            if !WRAPPERS.contains(&binary) {
+                break;
+            }
+            // Skip the wrapper's own flags; `dotenv -e .env.test -- vitest` runs `vitest`.
+            while words
+                .peek()
+                .is_some_and(|next| next.starts_with('-') && *next != "--")
+            {
+                words.next();
+                if words.peek().is_some_and(|next| next.starts_with('.')) {
+                    words.next();
+                }
+            }
+        }
+    }
+    out
+}
+
+/// The package a `node_modules/.bin` link points into.
+fn linked_bin_package(node_modules: &[PathBuf], binary: &str) -> Option<String> {
+    node_modules.iter().find_map(|dir| {
+        let target = fs::canonicalize(dir.join(".bin").join(binary)).ok()?;
+        installed_package_name(&target)
+    })
+}
+
+/// Package name from a path inside `node_modules`, using the last `node_modules` component
+/// so pnpm's `.pnpm/<name>@<version>/node_modules/<name>` layout works too.
+fn installed_package_name(path: &Path) -> Option<String> {
+    let components: Vec<&str> = path
+        .components()
+        .filter_map(|component| component.as_os_str().to_str())
+        .collect();
+    let idx = components.iter().rposition(|c| *c == "node_modules")?;
+    let first = components.get(idx + 1)?;
+    if first.starts_with('@') {
+        Some(format!("{first}/{}", components.get(idx + 2)?))
+    } else {
+        Some(first.to_string())
+    }
+}
+
+/// Executable name -> declared package, from the `bin` field of each installed dependency.
+fn installed_bin_names(
+    node_modules: &[PathBuf],
+    declared: &HashMap<String, DepKind>,
+) -> HashMap<String, String> {
+    let mut bins = HashMap::new();
+    for package in declared.keys() {
+        let Some(manifest) = node_modules.iter().find_map(|dir| {
+            let raw = fs::read_to_string(dir.join(package).join("package.json")).ok()?;
+            serde_json::from_str::<serde_json::Value>(&raw).ok()
+        }) else {
+            continue;
+        };
+        match manifest.get("bin") {
+            Some(serde_json::Value::String(_)) => {
+                let name = package.rsplit('/').next().unwrap_or(package);
+                bins.insert(name.to_string(), package.clone());
+            }
+            Some(serde_json::Value::Object(map)) => {
+                for name in map.keys() {
+                    bins.insert(name.clone(), package.clone());
+                }
+            }
+            _ => {}
+        }
+    }
+    bins
+}
@@ -0,0 +1,76 @@
+use super::*;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+
+const REPORT_PAGE: &str = include_str!("report.html");
+/// How long a connection may stall before it is dropped. Requests are served one at a time,
+/// so a client that connects and sends nothing would otherwise block every other one.
+const STREAM_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// `--serve-report`: serves an interactive HTML view of the report and module graph on
+/// localhost until interrupted. The page loads `/graph.json`; `/report.json` holds the plain
+/// report for scripts. The data is a snapshot taken at startup.
+pub(crate) fn serve_report(graph: &ModuleGraph, port: u16) -> Result<()> {
+    let graph_json = serde_json::to_vec(graph)?;
+    let report_json = serde_json::to_vec_pretty(&graph.report)?;
+    let listener = TcpListener::bind(("127.0.0.1", port))
+        .with_context(|| format!("Failed to listen on port {port}"))?;
+    eprintln!("Serving report at http://127.0.0.1:{port}/ (Ctrl+C to stop)");
+
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else {
+            continue;
+        };
+        if stream.set_read_timeout(Some(STREAM_TIMEOUT)).is_err()
+            || stream.set_write_timeout(Some(STREAM_TIMEOUT)).is_err()
+        {
+            continue;
+        }
+        // A client that disconnects mid-request only loses its own response.
+        let _ = handle_request(stream, &graph_json, &report_json);
+    }
+    Ok(())
+}
+
+fn handle_request(mut stream: TcpStream, graph_json: &[u8], report_json: &[u8]) -> Result<()> {
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    // Drain the headers; no request body is expected.
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim_end().is_empty() {
+            break;
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or("");
+    let path = parts.next().unwrap_or("/");
+    let path = path.split('?').next().unwrap_or(path);
+
+    let (status, content_type, body) = match (method, path) {
+        ("GET" | "HEAD", "/" | "/index.html") => {
+            ("200 OK", "text/html; charset=utf-8", REPORT_PAGE.as_bytes())
+        }
+        ("GET" | "HEAD", "/graph.json") => ("200 OK", "application/json", graph_json),
+        ("GET" | "HEAD", "/report.json") => ("200 OK", "application/json", report_json),
+        ("GET" | "HEAD", _) => ("404 Not Found", "text/plain", b"Not found".as_slice()),
+        _ => (
+            "405 Method Not Allowed",
+            "text/plain",
+            b"Method not allowed".as_slice(),
+        ),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nCache-Control: no-store\r\nConnection: close\r\n\r\n",
+        body.len()
+    )?;
+    if method != "HEAD" {
+        stream.write_all(body)?;
+    }
+    stream.flush()?;
+    Ok(())
+}
@@ -0,0 +1,189 @@
+use super::*;
+
+/// Entries tried, relative to the root, when a snapshot names none and `package.json` has no
+/// usable `main` / `module`.
+const DEFAULT_SNAPSHOT_ENTRIES: &[&str] = &["src/index", "src/main", "index"];
+
+/// An in-memory project for [`analyze_snapshot`], e.g. from a browser playground or a bundler
+/// plugin. Paths are relative to the project root and use `/`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    /// File path -> file contents.
+    pub files: BTreeMap<String, String>,
+    /// Entry files. When empty, `package.json` `main` / `module`, `src/index.*`, `src/main.*`,
+    /// `index.*`, and test files are used.
+    #[serde(default)]
+    pub entries: Vec<String>,
+    /// Report unused files and exports even when some imports could not be resolved.
+    #[serde(default)]
+    pub include_low_confidence: bool,
+}
+
+/// Runs the graph analysis on a [`Snapshot`] without touching the filesystem, so it also runs
+/// on `wasm32`. Relative and root-absolute imports are resolved; tsconfig paths, bundler
+/// aliases, and workspaces are not read, so aliased imports count as unresolved. The report
+/// covers entries, unused files, unused exports, unused dependencies, and broken imports;
+/// asset and env findings stay empty.
+pub fn analyze_snapshot(snapshot: &Snapshot) -> Result<Report> {
+    let root = PathBuf::from("/");
+    let contents: HashMap<PathBuf, &str> = snapshot
+        .files
+        .iter()
+        .map(|(path, source)| {
+            let path = normalize_path(root.join(path.trim_start_matches("./")));
+            (path, source.as_str())
+        })
+        .collect();
+    let read_source = |file: &Path| contents.get(file).copied().unwrap_or_default().to_string();
+
+    let path_index = PathIndex::from_files(contents.keys().cloned());
+    let files: HashSet<PathBuf> = contents
+        .keys()
+        .filter(|path| has_source_extension(path) && !is_declaration_file(path))
+        .cloned()
+        .collect();
+    let declaration_files: Vec<PathBuf> = contents
+        .keys()
+        .filter(|path| is_declaration_file(path))
+        .cloned()
+        .collect();
+    let resolver = Resolver::new(&root, &files, &[], &[], false, path_index);
+
+    let mut modules: HashMap<PathBuf, ModuleInfo> = HashMap::new();
+    for file in files.iter().chain(&declaration_files) {
+        modules.insert(file.clone(), parse_source(&read_source(file)));
+    }
+
+    let mut warnings =
+        vec!["Analysis is conservative by default to minimize false positives.".to_string()];
+    let package_json: Option<serde_json::Value> = snapshot
+        .files
+        .get("package.json")
+        .and_then(|raw| serde_json::from_str(raw).ok());
+
+    // Entries resolve like imports from the root, so extensions and `index` files are tried.
+    let anchor = root.join("package.json");
+    let mut entries = BTreeSet::new();
+    for entry in &snapshot.entries {
+        let specifier = format!("./{}", entry.trim_start_matches("./"));
+        let resolved = resolver.resolve_specifier_all(&anchor, &specifier)?;
+        if resolved.is_empty() {
+            warnings.push(format!("Entry '{entry}' is not in the snapshot."));
+        }
+        entries.extend(resolved);
+    }
+    if snapshot.entries.is_empty() {
+        let manifest_entries = ["main", "module"].into_iter().filter_map(|field| {
+            package_json
+                .as_ref()?
+                .get(field)?
+                .as_str()
+                .map(str::to_string)
+        });
+        let defaults = DEFAULT_SNAPSHOT_ENTRIES
+            .iter()
+            .map(|entry| entry.to_string());
+        for entry in manifest_entries.chain(defaults) {
+            let specifier = format!("./{}", entry.trim_start_matches("./"));
+            entries.extend(resolver.resolve_specifier_all(&anchor, &specifier)?);
+        }
+        // Test files are entries too, as in a filesystem scan.
+        entries.extend(files.iter().filter(|file| is_test_like_file(file)).cloned());
+    }
+    let entries: Vec<PathBuf> = entries.into_iter().collect();
+    if entries.is_empty() {
+        warnings.push(
+            "No entry files found in the snapshot. Pass entries to improve unused file accuracy."
+                .to_string(),
+        );
+    }
+
+    let mut graph_roots = entries.clone();
+    graph_roots.extend(declaration_files.iter().cloned());
+    let mut reachable = reachable_files(&graph_roots, &modules, &resolver)?;
+    let LocalImportIssues {
+        unresolved, broken, ..
+    } = collect_unresolved_local_imports(&reachable, &modules, &resolver)?;
+    let broken_imports = describe_broken_imports(&root, &files, &resolver, &broken);
+    let high_confidence_graph = unresolved.is_empty();
+    if !high_confidence_graph {
+        warnings.push(format!(
+            "Skipped high-risk findings because {} local/alias imports could not be resolved.",
+            unresolved.len()
+        ));
+    }
+
+    let used_packages = collect_used_packages(&reachable, &modules, &resolver)?;
+    let declared_deps = package_json
+        .as_ref()
+        .map(declared_dependencies)
+        .unwrap_or_default();
+    let unused_dependencies = find_unused_dependencies(&declared_deps, &used_packages, false);
+
+    let mut unused_files = Vec::new();
+    let mut unused_exports = Vec::new();
+    if high_confidence_graph || snapshot.include_low_confidence {
+        unused_files = files
+            .difference(&reachable)
+            .filter(|path| !is_test_like_file(path) && !is_common_config_file(path))
+            .map(|path| relative_display(&root, path))
+            .collect();
+        unused_files.sort();
+        let maybe_used = infer_potentially_used_files_from_unresolved(&files, &unresolved, &root);
+        (unused_exports, _) = find_unused_exports(
+            &root,
+            &files,
+            &reachable,
+            &entries,
+            &modules,
+            &resolver,
+            &maybe_used,
+            &package_side_effects(&root, package_json.as_ref(), &resolver),
+            None,
+            read_source,
+            &mut warnings,
+        )?;
+    } else {
+        warnings.push(
+            "unused_files and unused_exports omitted (use include_low_confidence to force)."
+                .to_string(),
+        );
+    }
+    for file in &declaration_files {
+        reachable.remove(file);
+    }
+
+    let summary = ReportSummary {
+        total_source_files: files.len(),
+        total_reachable_files: reachable.len(),
+        total_entries: entries.len(),
+        unresolved_local_imports: unresolved.len(),
+        high_confidence_graph,
+        omitted_risky_findings: !(high_confidence_graph || snapshot.include_low_confidence),
+        unused_files_count: unused_files.len(),
+        unused_dependencies_count: unused_dependencies.len(),
+        unused_exports_count: unused_exports.len(),
+        broken_imports_count: broken_imports.len(),
+        ..ReportSummary::default()
+    };
+    let mut report = Report {
+        summary,
+        entries: entries
+            .iter()
+            .map(|entry| relative_display(&root, entry))
+            .collect(),
+        warnings,
+        unused_files,
+        unused_dependencies,
+        unused_exports,
+        broken_imports,
+        ..Report::default()
+    };
+    report.findings = collect_findings(&report, |subject| {
+        snapshot
+            .files
+            .get(subject)
+            .map(|source| source.as_bytes().to_vec())
+    });
+    Ok(report)
+}
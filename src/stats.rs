@@ -0,0 +1,82 @@
+use super::*;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+const STATS_PATH: &str = ".haadi/stats.json";
+const STATS_FORMAT_VERSION: u32 = 1;
+/// Oldest runs are dropped beyond this, so the file stays small in long watch sessions.
+const MAX_RECORDED_RUNS: usize = 1000;
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct StatsFile {
+    version: u32,
+    runs: Vec<RunStats>,
+}
+
+/// One analysis run, as appended by `--stats`.
+#[derive(Debug, Serialize, Deserialize)]
+struct RunStats {
+    /// Unix seconds at the end of the run.
+    timestamp: u64,
+    duration_ms: u64,
+    source_files: usize,
+    reachable_files: usize,
+    /// Files parsed from disk, and files whose parsed module was reused (`--watch`, `lsp`).
+    parsed_files: usize,
+    cache_hits: usize,
+    cache_hit_rate: f64,
+    findings: usize,
+    findings_by_category: BTreeMap<String, usize>,
+}
+
+/// `--stats`: appends this run to `<root>/.haadi/stats.json`. The file is local only; nothing
+/// is sent anywhere.
+pub(crate) fn record_run_stats(
+    root: &Path,
+    report: &Report,
+    duration: Duration,
+    (cache_hits, parsed_files): (usize, usize),
+) -> Result<()> {
+    let path = root.join(STATS_PATH);
+    let mut stats = match fs::read(&path) {
+        Ok(raw) => serde_json::from_slice::<StatsFile>(&raw)
+            .ok()
+            .filter(|stats| stats.version == STATS_FORMAT_VERSION)
+            .unwrap_or_default(),
+        Err(_) => StatsFile::default(),
+    };
+    stats.version = STATS_FORMAT_VERSION;
+
+    let mut findings_by_category = BTreeMap::new();
+    for finding in &report.findings {
+        *findings_by_category
+            .entry(finding.category.clone())
+            .or_insert(0) += 1;
+    }
+    let lookups = cache_hits + parsed_files;
+    stats.runs.push(RunStats {
+        timestamp: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs()),
+        duration_ms: duration.as_millis() as u64,
+        source_files: report.summary.total_source_files,
+        reachable_files: report.summary.total_reachable_files,
+        parsed_files,
+        cache_hits,
+        cache_hit_rate: if lookups == 0 {
+            0.0
+        } else {
+            cache_hits as f64 / lookups as f64
+        },
+        findings: report.findings.len(),
+        findings_by_category,
+    });
+    let overflow = stats.runs.len().saturating_sub(MAX_RECORDED_RUNS);
+    stats.runs.drain(..overflow);
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create directory: {}", shown_path(dir)))?;
+    }
+    fs::write(&path, serde_json::to_vec_pretty(&stats)?)
+        .with_context(|| format!("Failed to write stats: {}", shown_path(&path)))
+}
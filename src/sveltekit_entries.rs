@@ -0,0 +1,86 @@
+use super::*;
+use crate::config_aliases::{
+    AliasExtraction, find_config_files, has_js_config_extension, push_alias_rules,
+};
+
+/// Route files SvelteKit loads from `src/routes/**`, by stem. `+page.svelte` and friends are
+/// Svelte components, which stand in for entries through what they import.
+const SVELTEKIT_ROUTE_FILES: &[&str] = &[
+    "+page",
+    "+page.server",
+    "+layout",
+    "+layout.server",
+    "+server",
+];
+/// Files of `src/` SvelteKit loads by name, by stem.
+const SVELTEKIT_SRC_FILES: &[&str] = &["hooks", "hooks.server", "hooks.client", "service-worker"];
+
+/// Whether `root` holds a SvelteKit app, going by its `svelte.config.*`.
+fn is_sveltekit_app(root: &Path) -> bool {
+    !find_config_files(root, &[""], |name| {
+        name.starts_with("svelte.config.") && has_js_config_extension(name)
+    })
+    .is_empty()
+}
+
+/// Files a SvelteKit app in `root` loads by name, which nothing imports: the `+page`,
+/// `+layout` (each also as `.server`), and `+server` files of `src/routes/**`, the hooks
+/// (`src/hooks.server.*`, `src/hooks.client.*`, `src/hooks.*`), `src/service-worker.*` or
+/// `src/service-worker/index.*`, and the param matchers of `src/params/`. Svelte components
+/// are not parsed as modules, so every source file a `.svelte` file under `src/` imports is
+/// an entry too.
+pub(crate) fn sveltekit_entries(
+    root: &Path,
+    files: &HashSet<PathBuf>,
+    resolver: &Resolver,
+) -> Result<Vec<PathBuf>> {
+    if !is_sveltekit_app(root) {
+        return Ok(Vec::new());
+    }
+    let src = root.join("src");
+    let mut out: Vec<PathBuf> = files
+        .iter()
+        .filter(|file| {
+            let Ok(rel) = file.strip_prefix(&src) else {
+                return false;
+            };
+            let stem = file
+                .file_stem()
+                .and_then(|s| s.to_str())
+                .unwrap_or_default();
+            let depth = rel.components().count();
+            if rel.starts_with("routes") {
+                return SVELTEKIT_ROUTE_FILES.contains(&stem);
+            }
+            if rel.starts_with("params") {
+                return depth == 2;
+            }
+            (depth == 1 && SVELTEKIT_SRC_FILES.contains(&stem))
+                || (rel.starts_with("service-worker") && depth == 2 && stem == "index")
+        })
+        .cloned()
+        .collect();
+    for component in files_with_extension(&src, "svelte") {
+        out.extend(component_imports(&component, resolver)?);
+    }
+    out.sort();
+    out.dedup();
+    for path in &out {
+        debug!(entry = %relative_display(root, path), "sveltekit entry");
+    }
+    Ok(out)
+}
+
+/// Registers `$lib` for `src/lib`, which SvelteKit sets up in its generated
+/// `.svelte-kit/tsconfig.json`.
+pub(crate) fn apply_sveltekit_aliases(root: &Path, resolver: &mut Resolver) -> Result<()> {
+    if !is_sveltekit_app(root) {
+        return Ok(());
+    }
+    let extraction = AliasExtraction {
+        aliases: vec![("$lib".to_string(), root.join("src").join("lib"))],
+        ..AliasExtraction::default()
+    };
+    push_alias_rules(resolver, &extraction);
+    Ok(())
+}
@@ -0,0 +1,259 @@
+use super::*;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A trash directory [`move_to_system_trash`] can move a file into.
+#[derive(Debug)]
+pub(crate) struct SystemTrash {
+    files_dir: PathBuf,
+    /// Where `.trashinfo` records go; macOS keeps none.
+    info_dir: Option<PathBuf>,
+    /// Top directory of a per-volume trash, which records paths relative to it.
+    topdir: Option<PathBuf>,
+}
+
+/// The trash that takes `path`, created when missing. Files are moved by rename, so the trash
+/// has to be on the file's filesystem: the home trash (`~/.Trash` on macOS, else
+/// `$XDG_DATA_HOME/Trash`, by default `~/.local/share/Trash`) when it is, else the freedesktop
+/// per-volume trash `$topdir/.Trash-$uid` at the top of the file's filesystem. macOS has no
+/// such fallback, so a file on another volume is refused there.
+pub(crate) fn system_trash_for(path: &Path) -> Result<SystemTrash> {
+    if cfg!(windows) {
+        anyhow::bail!("--trash system is not supported on Windows; use --trash repo or none");
+    }
+    let home = std::env::var_os("HOME")
+        .map(PathBuf::from)
+        .filter(|home| home.is_absolute())
+        .context("Failed to find the system trash: HOME is not set")?;
+
+    let home_trash = if cfg!(target_os = "macos") {
+        home.join(".Trash")
+    } else {
+        std::env::var_os("XDG_DATA_HOME")
+            .map(PathBuf::from)
+            .filter(|dir| dir.is_absolute())
+            .unwrap_or_else(|| home.join(".local").join("share"))
+            .join("Trash")
+    };
+    let device = device_of(path);
+    let home_device = home_trash.ancestors().find_map(device_of);
+    if device.is_none() || device == home_device {
+        if cfg!(target_os = "macos") {
+            return Ok(SystemTrash {
+                files_dir: home_trash,
+                info_dir: None,
+                topdir: None,
+            });
+        }
+        let trash = SystemTrash {
+            files_dir: home_trash.join("files"),
+            info_dir: Some(home_trash.join("info")),
+            topdir: None,
+        };
+        create_trash_dirs(&trash, false)
+            .with_context(|| format!("Failed to create the trash: {}", shown_path(&home_trash)))?;
+        return Ok(trash);
+    }
+
+    if cfg!(target_os = "macos") {
+        anyhow::bail!(
+            "{} is on another volume than ~/.Trash; use --trash repo or none",
+            shown_path(path)
+        );
+    }
+    let topdir = path
+        .ancestors()
+        .skip(1)
+        .take_while(|dir| device_of(dir) == device)
+        .last()
+        .unwrap_or(path)
+        .to_path_buf();
+    let volume_trash = topdir.join(format!(".Trash-{}", current_uid()));
+    let trash = SystemTrash {
+        files_dir: volume_trash.join("files"),
+        info_dir: Some(volume_trash.join("info")),
+        topdir: Some(topdir),
+    };
+    create_trash_dirs(&trash, true).with_context(|| {
+        format!(
+            "Failed to create the trash on the filesystem of {}: {}",
+            shown_path(path),
+            shown_path(&volume_trash)
+        )
+    })?;
+    Ok(trash)
+}
+
+/// Moves `path` into its [`system_trash_for`] and returns where it went, with a `.trashinfo`
+/// record outside macOS so the file manager can put it back.
+pub(crate) fn move_to_system_trash(path: &Path) -> Result<PathBuf> {
+    let name = path
+        .file_name()
+        .with_context(|| format!("Failed to trash {}: no file name", shown_path(path)))?;
+    let trash = system_trash_for(path)?;
+    let destination = free_trash_path(&trash.files_dir, name);
+
+    let Some(info_dir) = &trash.info_dir else {
+        fs::rename(path, &destination)
+            .with_context(|| format!("Failed to move {} to the trash", shown_path(path)))?;
+        return Ok(destination);
+    };
+    let info_path = info_dir.join(format!(
+        "{}.trashinfo",
+        destination.file_name().unwrap_or(name).to_string_lossy()
+    ));
+    let recorded = trash
+        .topdir
+        .as_deref()
+        .and_then(|topdir| path.strip_prefix(topdir).ok())
+        .unwrap_or(path);
+    fs::write(
+        &info_path,
+        format!(
+            "[Trash Info]\nPath={}\nDeletionDate={}\n",
+            percent_encode_path(recorded),
+            local_timestamp(SystemTime::now())
+        ),
+    )
+    .with_context(|| format!("Failed to write {}", shown_path(&info_path)))?;
+    if let Err(err) = fs::rename(path, &destination) {
+        let _ = fs::remove_file(&info_path);
+        return Err(err)
+            .with_context(|| format!("Failed to move {} to the trash", shown_path(path)));
+    }
+    Ok(destination)
+}
+
+/// Creates the `files` and `info` directories of `trash`; a per-volume trash is private to
+/// its user, as the freedesktop spec requires.
+fn create_trash_dirs(trash: &SystemTrash, private: bool) -> std::io::Result<()> {
+    let mut builder = fs::DirBuilder::new();
+    builder.recursive(true);
+    #[cfg(unix)]
+    if private {
+        use std::os::unix::fs::DirBuilderExt;
+        builder.mode(0o700);
+    }
+    #[cfg(not(unix))]
+    let _ = private;
+    builder.create(&trash.files_dir)?;
+    if let Some(info_dir) = &trash.info_dir {
+        builder.create(info_dir)?;
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn device_of(path: &Path) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    fs::symlink_metadata(path).ok().map(|meta| meta.dev())
+}
+
+#[cfg(not(unix))]
+fn device_of(_path: &Path) -> Option<u64> {
+    None
+}
+
+#[cfg(unix)]
+fn current_uid() -> u32 {
+    // SAFETY: getuid has no preconditions and cannot fail.
+    unsafe { libc::getuid() }
+}
+
+#[cfg(not(unix))]
+fn current_uid() -> u32 {
+    0
+}
+
+/// Moves a file [`move_to_system_trash`] trashed back to `original` and drops its
+/// `.trashinfo` record.
+pub(crate) fn put_back_from_system_trash(trashed: &Path, original: &Path) -> std::io::Result<()> {
+    fs::rename(trashed, original)?;
+    if let (Some(files_dir), Some(name)) = (trashed.parent(), trashed.file_name()) {
+        let mut info_name = name.to_os_string();
+        info_name.push(".trashinfo");
+        let _ = fs::remove_file(files_dir.with_file_name("info").join(info_name));
+    }
+    Ok(())
+}
+
+/// `dir/name`, or the first free `dir/name.2`, `dir/name.3`, ... when a trashed file of the
+/// same name is there already.
+fn free_trash_path(dir: &Path, name: &std::ffi::OsStr) -> PathBuf {
+    let mut candidate = dir.join(name);
+    let mut counter = 2;
+    while candidate.exists() {
+        let mut numbered = name.to_os_string();
+        numbered.push(format!(".{counter}"));
+        candidate = dir.join(numbered);
+        counter += 1;
+    }
+    candidate
+}
+
+/// `path` with every byte outside unreserved URL characters and `/` escaped, as the
+/// `Path=` key of a `.trashinfo` file requires.
+fn percent_encode_path(path: &Path) -> String {
+    let mut out = String::new();
+    for byte in path.to_string_lossy().bytes() {
+        if byte.is_ascii_alphanumeric() || b"/-_.~".contains(&byte) {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    out
+}
+
+/// `YYYY-MM-DDThh:mm:ss` in local time, as `DeletionDate=` expects.
+#[cfg(unix)]
+fn local_timestamp(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0) as libc::time_t;
+    // SAFETY: `tm` is plain data that localtime_r fills in; both pointers are valid.
+    let mut tm: libc::tm = unsafe { std::mem::zeroed() };
+    if unsafe { libc::localtime_r(&secs, &mut tm) }.is_null() {
+        return "1970-01-01T00:00:00".to_string();
+    }
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}",
+        tm.tm_year + 1900,
+        tm.tm_mon + 1,
+        tm.tm_mday,
+        tm.tm_hour,
+        tm.tm_min,
+        tm.tm_sec
+    )
+}
+
+/// `YYYY-MM-DDThh:mm:ss` in UTC, where the local time zone is unknown.
+#[cfg(not(unix))]
+fn local_timestamp(time: SystemTime) -> String {
+    let secs = time
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    // Civil date from days since 1970-01-01 (Howard Hinnant's algorithm).
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    let time_of_day = secs % 86_400;
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}",
+        time_of_day / 3_600,
+        time_of_day % 3_600 / 60,
+        time_of_day % 60
+    )
+}
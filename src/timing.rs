@@ -0,0 +1,50 @@
+use super::*;
+use std::time::Instant;
+
+/// Splits an analysis run into consecutive phases: each `lap` returns the wall time since the
+/// previous one, so every millisecond is attributed to exactly one phase.
+pub(crate) struct PhaseTimer {
+    started: Instant,
+    last: Instant,
+    pub(crate) timings: PhaseTimings,
+}
+
+impl PhaseTimer {
+    pub(crate) fn start() -> Self {
+        let now = Instant::now();
+        Self {
+            started: now,
+            last: now,
+            timings: PhaseTimings::default(),
+        }
+    }
+
+    /// Milliseconds since the previous lap (or the start).
+    pub(crate) fn lap(&mut self) -> f64 {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.last);
+        self.last = now;
+        elapsed.as_secs_f64() * 1000.0
+    }
+
+    /// Books the remaining time as `other_ms`, fills in the total, and rounds every phase to
+    /// microseconds so the JSON stays readable.
+    pub(crate) fn finish(mut self) -> PhaseTimings {
+        self.timings.other_ms += self.lap();
+        self.timings.total_ms = self.started.elapsed().as_secs_f64() * 1000.0;
+        let timings = &mut self.timings;
+        for ms in [
+            &mut timings.scan_ms,
+            &mut timings.parse_ms,
+            &mut timings.resolve_ms,
+            &mut timings.reachability_ms,
+            &mut timings.assets_ms,
+            &mut timings.exports_ms,
+            &mut timings.other_ms,
+            &mut timings.total_ms,
+        ] {
+            *ms = (*ms * 1000.0).round() / 1000.0;
+        }
+        self.timings
+    }
+}
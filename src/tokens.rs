@@ -1,34 +1,49 @@
 use super::*;
-pub(crate) fn build_file_token_cache(
-    files: &HashSet<PathBuf>,
-) -> Result<HashMap<PathBuf, HashSet<String>>> {
-    let mut cache = HashMap::new();
-
-    for file in files {
-        let source = fs::read_to_string(file).unwrap_or_default();
-        let mut tokens = HashSet::new();
-        for m in IDENT_TOKEN_RE.find_iter(&source) {
-            tokens.insert(m.as_str().to_string());
-        }
-        cache.insert(file.clone(), tokens);
-    }
 
-    Ok(cache)
+/// How many files mention each candidate export name as an identifier.
+#[derive(Debug, Default)]
+pub(crate) struct ExportNameFileCounts {
+    /// Counted over reachable files only.
+    pub(crate) reachable: HashMap<String, usize>,
+    /// Counted over every scanned file.
+    pub(crate) project: HashMap<String, usize>,
+    /// Counted over the `excluded` files, so a view without them can subtract these.
+    pub(crate) excluded: HashMap<String, usize>,
 }
 
-pub(crate) fn count_tokens_in_scope(
-    scope: &HashSet<PathBuf>,
-    token_cache: &HashMap<PathBuf, HashSet<String>>,
-) -> HashMap<String, usize> {
-    let mut counts = HashMap::new();
+/// Second pass of export-name counting: `candidates` holds the export names collected from
+/// parsed modules up front, and each file is read and tokenized one at a time, keeping only
+/// hits on those names. Memory stays proportional to the export names instead of to every
+/// identifier in the project.
+pub(crate) fn count_export_name_files(
+    files: &HashSet<PathBuf>,
+    reachable: &HashSet<PathBuf>,
+    excluded: &HashSet<PathBuf>,
+    candidates: &HashSet<&str>,
+    read_source: impl Fn(&Path) -> String,
+) -> ExportNameFileCounts {
+    let mut counts = ExportNameFileCounts::default();
+    let mut seen_in_file: HashSet<&str> = HashSet::new();
 
-    for file in scope {
-        let Some(tokens) = token_cache.get(file) else {
-            continue;
-        };
+    for file in files {
+        let source = strip_comments(&read_source(file));
+        seen_in_file.clear();
+        for (_, token) in ident_tokens(&source) {
+            if let Some(name) = candidates.get(token) {
+                seen_in_file.insert(name);
+            }
+        }
 
-        for token in tokens {
-            *counts.entry(token.clone()).or_insert(0) += 1;
+        let is_reachable = reachable.contains(file);
+        let is_excluded = excluded.contains(file);
+        for name in &seen_in_file {
+            *counts.project.entry(name.to_string()).or_insert(0) += 1;
+            if is_reachable {
+                *counts.reachable.entry(name.to_string()).or_insert(0) += 1;
+            }
+            if is_excluded {
+                *counts.excluded.entry(name.to_string()).or_insert(0) += 1;
+            }
         }
     }
 
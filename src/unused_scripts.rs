@@ -0,0 +1,234 @@
+use super::*;
+
+/// Scripts npm runs by itself (install and publish lifecycle, `npm start` / `npm test`).
+const LIFECYCLE_SCRIPTS: &[&str] = &[
+    "preinstall",
+    "install",
+    "postinstall",
+    "prepublish",
+    "prepare",
+    "prepublishOnly",
+    "prepack",
+    "postpack",
+    "publish",
+    "preversion",
+    "version",
+    "postversion",
+    "dependencies",
+    "start",
+    "stop",
+    "restart",
+    "test",
+];
+/// Scripts run by hand or by hosting platforms by convention.
+const CONVENTIONAL_SCRIPTS: &[&str] = &[
+    "dev",
+    "build",
+    "serve",
+    "lint",
+    "format",
+    "typecheck",
+    "heroku-prebuild",
+    "heroku-postbuild",
+    "heroku-cleanup",
+    "vercel-build",
+    "now-build",
+    "gcp-build",
+];
+/// Commands whose later words name scripts: package managers (`yarn build`, `pnpm -r run
+/// lint`), `npm-run-all` and its `run-s` / `run-p` shorthands, and monorepo task runners.
+const SCRIPT_RUNNERS: &[&str] = &[
+    "npm",
+    "yarn",
+    "pnpm",
+    "bun",
+    "npm-run-all",
+    "run-s",
+    "run-p",
+    "turbo",
+    "lerna",
+    "nx",
+];
+
+static COMMAND_SEPARATOR_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"&&|\|\||[;|&\n]").unwrap());
+/// `concurrently "npm:watch-*"` style references.
+static RUNNER_PREFIX_RE: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r#"\b(?:npm|yarn|pnpm|bun):([\w:.*-]+)"#).unwrap());
+
+/// Scripts of the root `package.json` and of each workspace package that nothing runs: no
+/// other script, CI or deploy file, git hook, shell script, Makefile, or `turbo.json` names
+/// them, npm does not run them as a lifecycle or `pre` / `post` hook, and they are not one of
+/// the names run by hand or by hosting platforms by convention (`dev`, `build`, `lint`, ...).
+/// Any mention of a script name after a runner counts, whichever package it was meant for, so
+/// `pnpm --filter web build` keeps every package's `build`.
+pub(crate) fn find_unused_scripts(root: &Path, resolver: &Resolver) -> Result<Vec<UnusedScript>> {
+    let mut manifests: Vec<(PathBuf, serde_json::Value)> = Vec::new();
+    if let Some(value) = read_root_package_json(root)? {
+        manifests.push((root.join("package.json"), value));
+    }
+    for package in &resolver.workspace_packages {
+        manifests.push((package.dir.join("package.json"), package.manifest.clone()));
+    }
+
+    let mut invoked = Invoked::default();
+    for (_, manifest) in &manifests {
+        if let Some(scripts) = manifest.get("scripts").and_then(|v| v.as_object()) {
+            for command in scripts.values().filter_map(|v| v.as_str()) {
+                invoked.scan(command);
+            }
+        }
+        let mut hooks = Vec::new();
+        for key in ["lint-staged", "simple-git-hooks", "husky"] {
+            if let Some(value) = manifest.get(key) {
+                collect_strings(value, &mut hooks);
+            }
+        }
+        for command in hooks {
+            invoked.scan(&command);
+        }
+    }
+
+    let mut sources: Vec<&PathBuf> = resolver
+        .path_index
+        .files()
+        .filter(|path| {
+            path.starts_with(root)
+                && !path.components().any(|c| c.as_os_str() == "node_modules")
+                && is_command_source(path)
+        })
+        .collect();
+    sources.sort();
+    for path in sources {
+        let raw = fs::read_to_string(path).unwrap_or_default();
+        if path.file_name().is_some_and(|name| name == "turbo.json") {
+            invoked.add_turbo_tasks(&raw);
+            continue;
+        }
+        let text: Vec<&str> = raw
+            .lines()
+            .filter(|line| !line.trim_start().starts_with('#'))
+            .collect();
+        invoked.scan(&text.join("\n"));
+    }
+
+    let mut unused = Vec::new();
+    for (package_json, manifest) in &manifests {
+        let Some(scripts) = manifest.get("scripts").and_then(|v| v.as_object()) else {
+            continue;
+        };
+        for (name, command) in scripts {
+            let is_hook = ["pre", "post"].iter().any(|prefix| {
+                name.strip_prefix(prefix).is_some_and(|base| {
+                    scripts.contains_key(base) || LIFECYCLE_SCRIPTS.contains(&base)
+                })
+            });
+            if LIFECYCLE_SCRIPTS.contains(&name.as_str())
+                || CONVENTIONAL_SCRIPTS.contains(&name.as_str())
+                || is_hook
+                || invoked.contains(name)
+            {
+                continue;
+            }
+            debug!(package_json = %shown_path(package_json), script = %name, "unused script");
+            unused.push(UnusedScript {
+                package_json: relative_display(root, package_json),
+                name: name.clone(),
+                command: command.as_str().unwrap_or_default().to_string(),
+            });
+        }
+    }
+    unused.sort_by(|a, b| (&a.package_json, &a.name).cmp(&(&b.package_json, &b.name)));
+    Ok(unused)
+}
+
+/// Files whose commands may run scripts: YAML (CI pipelines, Compose, Kubernetes),
+/// Dockerfiles, Procfiles, Makefiles, justfiles, Jenkinsfiles, shell scripts, git hooks, and
+/// `turbo.json`.
+fn is_command_source(path: &Path) -> bool {
+    let Some(name) = path.file_name().and_then(|n| n.to_str()) else {
+        return false;
+    };
+    let in_hooks_dir = path
+        .parent()
+        .and_then(|dir| dir.file_name())
+        .is_some_and(|dir| dir == ".husky");
+    in_hooks_dir
+        || matches!(
+            name,
+            "Makefile" | "makefile" | "justfile" | "Jenkinsfile" | "Procfile" | "turbo.json"
+        )
+        || name == "Dockerfile"
+        || name.starts_with("Dockerfile.")
+        || [".yml", ".yaml", ".sh", ".dockerfile"]
+            .iter()
+            .any(|ext| name.ends_with(ext))
+}
+
+/// Script names some command runs; names with `*` are `npm-run-all` / `concurrently` globs.
+#[derive(Debug, Default)]
+struct Invoked {
+    names: HashSet<String>,
+    patterns: Vec<Regex>,
+}
+
+impl Invoked {
+    fn scan(&mut self, text: &str) {
+        for caps in RUNNER_PREFIX_RE.captures_iter(text) {
+            self.add(&caps[1]);
+        }
+        for command in COMMAND_SEPARATOR_RE.split(text) {
+            let mut words = command
+                .split_whitespace()
+                .map(|word| word.trim_matches(|c| c == '"' || c == '\'' || c == '`'));
+            if !words.any(|word| SCRIPT_RUNNERS.contains(&word.rsplit('/').next().unwrap_or(word)))
+            {
+                continue;
+            }
+            // Over-approximates (`yarn add lodash` adds `add` and `lodash`), which only keeps
+            // scripts that happen to share those names.
+            for word in words.filter(|word| !word.starts_with('-') && !word.contains('=')) {
+                self.add(word);
+                // `nx run app:build` runs the `build` target.
+                if let Some((_, target)) = word.split_once(':')
+                    && !word.contains('*')
+                {
+                    self.add(target);
+                }
+            }
+        }
+    }
+
+    /// Task names in `turbo.json` (`pipeline` in v1, `tasks` in v2); `web#build` names the
+    /// `build` script of one package.
+    fn add_turbo_tasks(&mut self, raw: &str) {
+        let Ok(value) = serde_json::from_str::<serde_json::Value>(&sanitize_jsonc(raw)) else {
+            return;
+        };
+        for key in ["pipeline", "tasks"] {
+            if let Some(tasks) = value.get(key).and_then(|v| v.as_object()) {
+                for task in tasks.keys() {
+                    self.add(task.rsplit('#').next().unwrap_or(task));
+                }
+            }
+        }
+    }
+
+    fn add(&mut self, name: &str) {
+        if !name.contains('*') {
+            self.names.insert(name.to_string());
+            return;
+        }
+        let pattern = name
+            .split('*')
+            .map(regex::escape)
+            .collect::<Vec<_>>()
+            .join(".*");
+        if let Ok(re) = Regex::new(&format!("^{pattern}$")) {
+            self.patterns.push(re);
+        }
+    }
+
+    fn contains(&self, name: &str) -> bool {
+        self.names.contains(name) || self.patterns.iter().any(|re| re.is_match(name))
+    }
+}
@@ -0,0 +1,121 @@
+use super::*;
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::time::Duration;
+
+/// Editors and formatters touch several files per save; events closer together than this are
+/// handled as one change.
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+type FsEvents = Receiver<notify::Result<notify::Event>>;
+
+/// `--watch`: analyzes the project, then re-runs the analysis after every change and prints
+/// the new report (or refreshes the TUI in place). Unchanged files keep their parsed modules
+/// between runs.
+pub(crate) fn watch(cli: &Cli) -> Result<()> {
+    let options = &cli.analysis;
+    let root = fs::canonicalize(&options.root)
+        .with_context(|| format!("Failed to access root: {}", shown_path(&options.root)))?;
+    // A graph saved inside the project would otherwise retrigger the run that wrote it.
+    let ignored_output = match &options.save_graph {
+        Some(path) => Some(std::path::absolute(path)?),
+        None => None,
+    };
+
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx).context("Failed to start file watcher")?;
+    watcher
+        .watch(&root, RecursiveMode::Recursive)
+        .with_context(|| format!("Failed to watch root: {}", shown_path(&root)))?;
+
+    let mut parse_cache = ParseCache::default();
+    let is_change = |event: &notify::Result<notify::Event>| {
+        is_project_change(event, &root, ignored_output.as_deref())
+    };
+
+    if cli.tui {
+        let report = analyze_cached(options, &mut parse_cache)?;
+        let mut refresh = |message: &mut String| -> Result<Option<Report>> {
+            if !rx.try_iter().any(|event| is_change(&event)) {
+                return Ok(None);
+            }
+            settle(&rx);
+            // As below, a file caught mid-write must not end the session.
+            match analyze_cached(options, &mut parse_cache) {
+                Ok(report) => Ok(Some(report)),
+                Err(err) if interrupted() => Err(err),
+                Err(err) => {
+                    *message = format!("Analysis failed; showing the previous report: {err:#}");
+                    Ok(None)
+                }
+            }
+        };
+        return print_tui_report(
+            &report,
+            Some(&mut refresh),
+            cli.on_conflict,
+            cli.trash_options(),
+        );
+    }
+
+    loop {
+        if !cli.json {
+            // Clear the screen so each run replaces the previous report.
+            print!("\x1b[2J\x1b[H");
+        }
+        // Files are often caught mid-write; report the failure and wait for the next change.
+        match analyze_cached(options, &mut parse_cache) {
+            Ok(report) => render_report(report, cli)?,
+            Err(err) if interrupted() => return Err(err),
+            Err(err) => eprintln!("Analysis failed: {err:#}"),
+        }
+        eprintln!(
+            "Watching {} for changes (Ctrl+C to stop)...",
+            shown_path(&root)
+        );
+
+        loop {
+            let event = rx.recv().context("File watcher stopped")?;
+            if is_change(&event) {
+                break;
+            }
+        }
+        settle(&rx);
+    }
+}
+
+/// Drains events until the project has been quiet for [`DEBOUNCE`].
+fn settle(rx: &FsEvents) {
+    loop {
+        match rx.recv_timeout(DEBOUNCE) {
+            Ok(_) => continue,
+            Err(RecvTimeoutError::Timeout | RecvTimeoutError::Disconnected) => return,
+        }
+    }
+}
+
+/// True for modifications of files the scanner could see. Reads, ignored directories
+/// (`node_modules`, `.git`, `.haadi_trash`, ...) and haadi's own output do not count; watcher
+/// errors do, so a lost event never leaves a stale report.
+fn is_project_change(
+    event: &notify::Result<notify::Event>,
+    root: &Path,
+    ignored_output: Option<&Path>,
+) -> bool {
+    let Ok(event) = event else {
+        return true;
+    };
+    if matches!(event.kind, EventKind::Access(_)) {
+        return false;
+    }
+
+    event.paths.iter().any(|path| {
+        if Some(path.as_path()) == ignored_output {
+            return false;
+        }
+        let Ok(rel) = path.strip_prefix(root) else {
+            return false;
+        };
+        !rel.ancestors().any(is_ignored_dir)
+    })
+}
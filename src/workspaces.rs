@@ -0,0 +1,341 @@
+use super::*;
+use walkdir::WalkDir;
+
+const DIST_DIR_PREFIXES: &[&str] = &["dist/", "build/", "lib/", "out/", "esm/", "cjs/"];
+
+#[derive(Debug, Clone)]
+pub(crate) struct WorkspacePackage {
+    pub(crate) name: String,
+    pub(crate) dir: PathBuf,
+    pub(crate) manifest: serde_json::Value,
+}
+
+/// Finds workspace packages declared through `pnpm-workspace.yaml` or the `workspaces` field of
+/// the root `package.json` (array or `{ "packages": [...] }` form).
+pub(crate) fn discover_workspace_packages(root: &Path) -> Result<Vec<WorkspacePackage>> {
+    let mut patterns = pnpm_workspace_patterns(root)?;
+    if let Some(value) = read_root_package_json(root)? {
+        let workspaces = value.get("workspaces");
+        let list = workspaces
+            .and_then(|v| v.as_array())
+            .or_else(|| workspaces.and_then(|v| v.get("packages")?.as_array()));
+        for pattern in list.into_iter().flatten().filter_map(|v| v.as_str()) {
+            patterns.push(pattern.to_string());
+        }
+    }
+
+    if patterns.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut includes = Vec::new();
+    let mut excludes = Vec::new();
+    for pattern in &patterns {
+        let (negated, raw) = match pattern.trim().strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, pattern.trim()),
+        };
+        let normalized = normalize_asset_root(raw);
+        if normalized.is_empty() {
+            continue;
+        }
+        let Ok(re) = Regex::new(&glob_path_pattern_to_regex(&normalized)) else {
+            continue;
+        };
+        if negated {
+            excludes.push(re);
+        } else {
+            includes.push(re);
+        }
+    }
+
+    let mut packages = Vec::new();
+    for entry in WalkDir::new(root)
+        .min_depth(1)
+        .into_iter()
+        .filter_entry(|e| e.file_type().is_dir() && !is_ignored_dir(e.path()))
+        .filter_map(|e| e.ok())
+    {
+        let dir = entry.path();
+        let rel = relative_display(root, dir).replace('\\', "/");
+        if !includes.iter().any(|re| re.is_match(&rel))
+            || excludes.iter().any(|re| re.is_match(&rel))
+        {
+            continue;
+        }
+
+        let manifest_path = dir.join("package.json");
+        if !manifest_path.exists() {
+            continue;
+        }
+        let raw = fs::read_to_string(&manifest_path).unwrap_or_default();
+        let Ok(manifest) = serde_json::from_str::<serde_json::Value>(&raw) else {
+            continue;
+        };
+        let Some(name) = manifest.get("name").and_then(|v| v.as_str()) else {
+            continue;
+        };
+
+        packages.push(WorkspacePackage {
+            name: name.to_string(),
+            dir: fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf()),
+            manifest,
+        });
+    }
+
+    packages.sort_by(|a, b| a.name.cmp(&b.name));
+    packages.dedup_by(|a, b| a.name == b.name);
+    Ok(packages)
+}
+
+/// Minimal reader for the `packages:` list in `pnpm-workspace.yaml`.
+fn pnpm_workspace_patterns(root: &Path) -> Result<Vec<String>> {
+    let path = root.join("pnpm-workspace.yaml");
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let raw = fs::read_to_string(path)?;
+    let mut out = Vec::new();
+    let mut in_packages = false;
+    for line in raw.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if !line.starts_with(char::is_whitespace) && !trimmed.starts_with('-') {
+            in_packages = trimmed == "packages:";
+            continue;
+        }
+        if !in_packages {
+            continue;
+        }
+        if let Some(item) = trimmed.strip_prefix('-') {
+            let item = item.trim().trim_matches(|c| c == '\'' || c == '"');
+            if !item.is_empty() {
+                out.push(item.to_string());
+            }
+        }
+    }
+
+    Ok(out)
+}
+
+/// Splits `@acme/ui/button` into the matching workspace package and its `./button` subpath.
+pub(crate) fn match_workspace_package<'a>(
+    packages: &'a [WorkspacePackage],
+    specifier: &str,
+) -> Option<(&'a WorkspacePackage, String)> {
+    packages.iter().find_map(|package| {
+        if specifier == package.name {
+            return Some((package, ".".to_string()));
+        }
+        let rest = specifier.strip_prefix(&package.name)?.strip_prefix('/')?;
+        Some((package, format!("./{rest}")))
+    })
+}
+
+/// Resolves a workspace package subpath to a local source file through `exports`, falling back
+/// to `source`/`module`/`main` and conventional `src/index` files. Targets inside build output
+/// directories (`dist/`, `lib/`, ...) are mapped back to `src/` because build output is not
+/// scanned.
+pub(crate) fn resolve_workspace_subpath(
+    resolver: &Resolver,
+    package: &WorkspacePackage,
+    subpath: &str,
+) -> Result<Option<PathBuf>> {
+    let mut targets = Vec::new();
+
+    if let Some(exports) = package.manifest.get("exports") {
+        collect_export_targets(exports, subpath, &mut targets);
+    }
+
+    if subpath == "." {
+        for key in ["source", "module", "main"] {
+            if let Some(v) = package.manifest.get(key).and_then(|v| v.as_str()) {
+                targets.push(v.to_string());
+            }
+        }
+        targets.push("./src/index".to_string());
+        targets.push("./index".to_string());
+    } else {
+        targets.push(subpath.to_string());
+        targets.push(format!("./src/{}", subpath.trim_start_matches("./")));
+    }
+
+    for target in targets {
+        if let Some(path) = resolve_workspace_target(resolver, &package.dir, &target)? {
+            return Ok(Some(path));
+        }
+    }
+
+    Ok(None)
+}
+
+fn collect_export_targets(exports: &serde_json::Value, subpath: &str, out: &mut Vec<String>) {
+    let subpath_map = exports
+        .as_object()
+        .filter(|map| map.keys().any(|k| k.starts_with('.')));
+
+    let Some(map) = subpath_map else {
+        // String, array, or conditions object: all apply to the package root.
+        if subpath == "." {
+            collect_strings(exports, out);
+        }
+        return;
+    };
+
+    if let Some(target) = map.get(subpath) {
+        collect_strings(target, out);
+        return;
+    }
+
+    for (key, target) in map {
+        let Some(star) = match_alias(key, subpath) else {
+            continue;
+        };
+        let mut raw = Vec::new();
+        collect_strings(target, &mut raw);
+        out.extend(raw.iter().map(|t| apply_alias_target(t, &star)));
+    }
+}
+
+fn resolve_workspace_target(
+    resolver: &Resolver,
+    package_dir: &Path,
+    target: &str,
+) -> Result<Option<PathBuf>> {
+    if let Some(path) = resolver.resolve_path(&package_dir.join(target))? {
+        return Ok(Some(path));
+    }
+
+    let rel = target.trim_start_matches("./");
+    for prefix in DIST_DIR_PREFIXES {
+        let Some(rest) = rel.strip_prefix(prefix) else {
+            continue;
+        };
+        let source_like = strip_file_extension(rest.trim_end_matches(".d.ts"));
+        if let Some(path) = resolver.resolve_path(&package_dir.join("src").join(source_like))? {
+            return Ok(Some(path));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Adds entries discovered inside each workspace package (its `package.json` fields, default
+/// `src/index.*` files, and framework conventions relative to the package directory).
+pub(crate) fn discover_workspace_entries(
+    resolver: &Resolver,
+    files: &HashSet<PathBuf>,
+    mut entries: BTreeMap<PathBuf, EntryScope>,
+    dist_mappings: &[DistMapping],
+) -> Result<BTreeMap<PathBuf, EntryScope>> {
+    for package in &resolver.workspace_packages {
+        let package_files: HashSet<PathBuf> = files
+            .iter()
+            .filter(|file| file.starts_with(&package.dir))
+            .cloned()
+            .collect();
+        for (path, scope) in
+            discover_entries(&package.dir, &package_files, resolver, &[], dist_mappings)?
+        {
+            add_entry(&mut entries, path, scope);
+        }
+    }
+
+    Ok(entries)
+}
+
+/// Groups findings by owning workspace package (deepest package directory wins).
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn build_package_reports(
+    root: &Path,
+    resolver: &Resolver,
+    modules: &HashMap<PathBuf, ModuleInfo>,
+    files: &HashSet<PathBuf>,
+    reachable: &HashSet<PathBuf>,
+    entries: &[PathBuf],
+    unused_files: &[String],
+    unused_assets: &[String],
+    unused_exports: &[UnusedExport],
+    include_non_prod: bool,
+) -> Result<Vec<PackageReport>> {
+    let packages = &resolver.workspace_packages;
+    let owner_of = |path: &Path| -> Option<usize> {
+        packages
+            .iter()
+            .enumerate()
+            .filter(|(_, package)| path.starts_with(&package.dir))
+            .max_by_key(|(_, package)| package.dir.components().count())
+            .map(|(idx, _)| idx)
+    };
+    let owner_of_rel = |rel: &str| owner_of(&root.join(rel));
+
+    let mut reports = Vec::new();
+    for (idx, package) in packages.iter().enumerate() {
+        let package_files: HashSet<PathBuf> = files
+            .iter()
+            .filter(|file| owner_of(file) == Some(idx))
+            .cloned()
+            .collect();
+        let package_reachable: HashSet<PathBuf> =
+            package_files.intersection(reachable).cloned().collect();
+
+        let mut used = collect_used_packages(&package_reachable, modules, resolver)?;
+        let declared = collect_declared_dependencies(&package.dir)?;
+        used.extend(script_binary_packages(root, &package.dir, &declared)?);
+        used.extend(config_referenced_packages(
+            &package.dir,
+            modules,
+            resolver,
+            &declared,
+        )?);
+        let unused_dependencies = find_unused_dependencies(&declared, &used, include_non_prod);
+
+        let package_entries: Vec<String> = entries
+            .iter()
+            .filter(|entry| owner_of(entry) == Some(idx))
+            .map(|entry| relative_display(root, entry))
+            .collect();
+        let unused_files: Vec<String> = unused_files
+            .iter()
+            .filter(|rel| owner_of_rel(rel) == Some(idx))
+            .cloned()
+            .collect();
+        let unused_assets: Vec<String> = unused_assets
+            .iter()
+            .filter(|rel| owner_of_rel(rel) == Some(idx))
+            .cloned()
+            .collect();
+        let unused_exports: Vec<UnusedExport> = unused_exports
+            .iter()
+            .filter(|item| owner_of_rel(&item.file) == Some(idx))
+            .cloned()
+            .collect();
+
+        let dir = relative_display(root, &package.dir).replace('\\', "/");
+        reports.push(PackageReport {
+            name: package.name.clone(),
+            dir: dir.clone(),
+            summary: PackageSummary {
+                name: package.name.clone(),
+                dir,
+                total_source_files: package_files.len(),
+                total_reachable_files: package_reachable.len(),
+                total_entries: package_entries.len(),
+                unused_files_count: unused_files.len(),
+                unused_assets_count: unused_assets.len(),
+                unused_dependencies_count: unused_dependencies.len(),
+                unused_exports_count: unused_exports.len(),
+            },
+            entries: package_entries,
+            unused_files,
+            unused_assets,
+            unused_dependencies,
+            unused_exports,
+        });
+    }
+
+    Ok(reports)
+}